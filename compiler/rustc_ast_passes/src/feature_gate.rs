@@ -8,7 +8,7 @@
 use rustc_session::parse::{feature_err, feature_err_issue};
 use rustc_session::Session;
 use rustc_span::source_map::Spanned;
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
 
 use tracing::debug;
@@ -46,6 +46,35 @@ macro_rules! gate_feature_post {
     };
 }
 
+/// A form of pattern syntax that is unstable.
+///
+/// All pattern forms are checked against `PATTERN_GATES` after expansion, so gating new pattern
+/// syntax only requires adding an entry there. Errors are reported through `feature_err_issue`,
+/// which points at the tracking issue of the feature and suggests enabling it.
+struct PatternGate {
+    /// The feature which enables this pattern form.
+    feature: Symbol,
+    /// Whether a pattern is of the gated form.
+    matches: fn(&PatKind) -> bool,
+    /// The error message used when the feature isn't enabled.
+    explain: &'static str,
+}
+
+const PATTERN_GATES: &[PatternGate] = &[
+    PatternGate {
+        feature: sym::box_patterns,
+        matches: |kind| matches!(kind, PatKind::Box(..)),
+        explain: "box pattern syntax is experimental",
+    },
+    PatternGate {
+        feature: sym::exclusive_range_pattern,
+        matches: |kind| {
+            matches!(kind, PatKind::Range(_, _, Spanned { node: RangeEnd::Excluded, .. }))
+        },
+        explain: "exclusive range pattern syntax is experimental",
+    },
+];
+
 pub fn check_attribute(attr: &ast::Attribute, sess: &Session, features: &Features) {
     PostExpansionVisitor { sess, features }.visit_attribute(attr)
 }
@@ -322,6 +351,7 @@ macro_rules! gate_doc { ($($name:ident => $feature:ident)*) => {
                     masked => doc_masked
                     notable_trait => doc_notable_trait
                     keyword => doc_keyword
+                    since => doc_since
                 );
             }
         }
@@ -564,24 +594,16 @@ fn visit_expr(&mut self, e: &'a ast::Expr) {
     }
 
     fn visit_pat(&mut self, pattern: &'a ast::Pat) {
-        match &pattern.kind {
-            PatKind::Box(..) => {
-                gate_feature_post!(
-                    &self,
-                    box_patterns,
-                    pattern.span,
-                    "box pattern syntax is experimental"
-                );
-            }
-            PatKind::Range(_, _, Spanned { node: RangeEnd::Excluded, .. }) => {
-                gate_feature_post!(
+        for gate in PATTERN_GATES {
+            if (gate.matches)(&pattern.kind) {
+                gate_feature_fn!(
                     &self,
-                    exclusive_range_pattern,
+                    |features: &Features| features.enabled(gate.feature),
                     pattern.span,
-                    "exclusive range pattern syntax is experimental"
+                    gate.feature,
+                    gate.explain
                 );
             }
-            _ => {}
         }
         visit::walk_pat(self, pattern)
     }
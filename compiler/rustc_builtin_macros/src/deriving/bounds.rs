@@ -23,6 +23,7 @@ pub fn expand_deriving_copy(
         supports_unions: true,
         methods: Vec::new(),
         associated_types: Vec::new(),
+        skip_attr: None,
     };
 
     trait_def.expand(cx, mitem, item, push);
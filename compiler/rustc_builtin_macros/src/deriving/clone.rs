@@ -29,9 +29,14 @@ pub fn expand_deriving_clone(
     //      Unions with generic parameters still can derive Clone because they require Copy
     //      for deriving, Clone alone is not enough.
     //      Whever Clone is implemented for fields is irrelevant so we don't assert it.
+    //
+    // if the item derives Copy but has generic parameters, we emit the long form and mark the
+    // impl with `#[rustc_copy_clone_shim]`: after monomorphization, instances for which the
+    // item is actually Copy get resolved to the clone shim instead, which is a plain copy.
     let bounds;
     let substructure;
     let is_shallow;
+    let mut copy_clone_shim = false;
     match *item {
         Annotatable::Item(ref annitem) => match annitem.kind {
             ItemKind::Struct(_, Generics { ref params, .. })
@@ -50,6 +55,7 @@ pub fn expand_deriving_clone(
                 } else {
                     bounds = vec![];
                     is_shallow = false;
+                    copy_clone_shim = cx.resolver.has_derive_copy(container_id);
                     substructure =
                         combine_substructure(Box::new(|c, s, sub| cs_clone("Clone", c, s, sub)));
                 }
@@ -72,11 +78,14 @@ pub fn expand_deriving_clone(
         _ => cx.span_bug(span, "`#[derive(Clone)]` on trait item or impl item"),
     }
 
-    let inline = cx.meta_word(span, sym::inline);
-    let attrs = vec![cx.attribute(inline)];
+    let mut impl_attrs = Vec::new();
+    if copy_clone_shim {
+        let span = cx.with_def_site_ctxt(span);
+        impl_attrs.push(cx.attribute(cx.meta_word(span, sym::rustc_copy_clone_shim)));
+    }
     let trait_def = TraitDef {
         span,
-        attributes: Vec::new(),
+        attributes: impl_attrs,
         path: path_std!(clone::Clone),
         additional_bounds: bounds,
         generics: Bounds::empty(),
@@ -88,12 +97,13 @@ pub fn expand_deriving_clone(
             explicit_self: borrowed_explicit_self(),
             args: Vec::new(),
             ret_ty: Self_,
-            attributes: attrs,
+            attributes: MethodAttrs::inline(),
             is_unsafe: false,
             unify_fieldless_variants: false,
             combine_substructure: substructure,
         }],
         associated_types: Vec::new(),
+        skip_attr: None,
     };
 
     trait_def.expand_ext(cx, mitem, item, push, is_shallow)
@@ -5,9 +5,12 @@
 use rustc_ast::ptr::P;
 use rustc_ast::{self as ast, Expr, MetaItem};
 use rustc_expand::base::{Annotatable, ExtCtxt};
+use rustc_session::config::DeriveCmpStrategy;
 use rustc_span::symbol::{sym, Ident};
 use rustc_span::Span;
 
+use std::mem;
+
 pub fn expand_deriving_ord(
     cx: &mut ExtCtxt<'_>,
     span: Span,
@@ -15,8 +18,6 @@ pub fn expand_deriving_ord(
     item: &Annotatable,
     push: &mut dyn FnMut(Annotatable),
 ) {
-    let inline = cx.meta_word(span, sym::inline);
-    let attrs = vec![cx.attribute(inline)];
     let trait_def = TraitDef {
         span,
         attributes: Vec::new(),
@@ -31,12 +32,13 @@ pub fn expand_deriving_ord(
             explicit_self: borrowed_explicit_self(),
             args: vec![(borrowed_self(), sym::other)],
             ret_ty: Literal(path_std!(cmp::Ordering)),
-            attributes: attrs,
+            attributes: MethodAttrs::inline(),
             is_unsafe: false,
             unify_fieldless_variants: true,
             combine_substructure: combine_substructure(Box::new(|a, b, c| cs_cmp(a, b, c))),
         }],
         associated_types: Vec::new(),
+        skip_attr: Some(sym::ord),
     };
 
     trait_def.expand(cx, mitem, item, push)
@@ -58,6 +60,10 @@ pub fn cs_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_>) -> P<
     let equals_path = cx.path_global(span, cx.std_path(&[sym::cmp, sym::Ordering, sym::Equal]));
 
     let cmp_path = cx.std_path(&[sym::cmp, sym::Ord, sym::cmp]);
+    let then_with_path = cx.std_path(&[sym::cmp, sym::Ordering, sym::then_with]);
+
+    let chain = cx.sess.opts.debugging_opts.derive_cmp_strategy == DeriveCmpStrategy::Chain;
+    let mut first_field = true;
 
     // Builds:
     //
@@ -72,10 +78,18 @@ pub fn cs_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_>) -> P<
     // cmp => cmp
     // }
     //
-    cs_fold(
+    // or, with `-Z derive-cmp-strategy=chain`:
+    //
+    // ::std::cmp::Ordering::then_with(
+    //     ::std::cmp::Ord::cmp(&self_field1, &other_field1),
+    //     || ::std::cmp::Ord::cmp(&self_field2, &other_field2),
+    // )
+    //
+    cs_fold_unskipped(
         // foldr nests the if-elses correctly, leaving the first field
-        // as the outermost one, and the last as the innermost.
-        false,
+        // as the outermost one, and the last as the innermost. A chain
+        // is built from the first field on instead.
+        chain,
         |cx, span, old, self_f, other_fs| {
             // match new {
             //     ::std::cmp::Ordering::Equal => old,
@@ -94,6 +108,15 @@ pub fn cs_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_>) -> P<
                 cx.expr_call_global(span, cmp_path.clone(), args)
             };
 
+            if chain {
+                // The first field starts the chain, there is nothing to compare before it.
+                if mem::replace(&mut first_field, false) {
+                    return new;
+                }
+                let args = vec![old, cx.lambda0(span, new)];
+                return cx.expr_call_global(span, then_with_path.clone(), args);
+            }
+
             let eq_arm = cx.arm(span, cx.pat_path(span, equals_path.clone()), old);
             let neq_arm = cx.arm(span, cx.pat_ident(span, test_id), cx.expr_ident(span, test_id));
 
@@ -65,15 +65,13 @@ fn cs_ne(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_>) -> P<Expr>
 
     macro_rules! md {
         ($name:expr, $f:ident) => {{
-            let inline = cx.meta_word(span, sym::inline);
-            let attrs = vec![cx.attribute(inline)];
             MethodDef {
                 name: $name,
                 generics: Bounds::empty(),
                 explicit_self: borrowed_explicit_self(),
                 args: vec![(borrowed_self(), sym::other)],
                 ret_ty: Literal(path_local!(bool)),
-                attributes: attrs,
+                attributes: MethodAttrs::inline(),
                 is_unsafe: false,
                 unify_fieldless_variants: true,
                 combine_substructure: combine_substructure(Box::new(|a, b, c| $f(a, b, c))),
@@ -107,6 +105,7 @@ macro_rules! md {
         supports_unions: false,
         methods,
         associated_types: Vec::new(),
+        skip_attr: None,
     };
     trait_def.expand(cx, mitem, item, push)
 }
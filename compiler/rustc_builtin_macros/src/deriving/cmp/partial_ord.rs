@@ -3,11 +3,17 @@
 use crate::deriving::{path_std, pathvec_std};
 
 use rustc_ast::ptr::P;
-use rustc_ast::{Expr, MetaItem};
+use rustc_ast::{self as ast, Expr, ItemKind, MetaItem};
 use rustc_expand::base::{Annotatable, ExtCtxt};
+use rustc_session::config::DeriveCmpStrategy;
+use rustc_session::lint::builtin::PARTIAL_ORD_FIELD_ORDER;
+use rustc_session::lint::BuiltinLintDiagnostics;
+use rustc_session::parse::feature_err;
 use rustc_span::symbol::{sym, Ident};
 use rustc_span::Span;
 
+use std::mem;
+
 pub fn expand_deriving_partial_ord(
     cx: &mut ExtCtxt<'_>,
     span: Span,
@@ -23,8 +29,8 @@ pub fn expand_deriving_partial_ord(
         PathKind::Std,
     ));
 
-    let inline = cx.meta_word(span, sym::inline);
-    let attrs = vec![cx.attribute(inline)];
+    let by_discriminant = is_by_discriminant(cx, item);
+    check_field_order(cx, item);
 
     let partial_cmp_def = MethodDef {
         name: sym::partial_cmp,
@@ -32,11 +38,15 @@ pub fn expand_deriving_partial_ord(
         explicit_self: borrowed_explicit_self(),
         args: vec![(borrowed_self(), sym::other)],
         ret_ty,
-        attributes: attrs,
+        attributes: MethodAttrs::inline(),
         is_unsafe: false,
         unify_fieldless_variants: true,
         combine_substructure: combine_substructure(Box::new(|cx, span, substr| {
-            cs_partial_cmp(cx, span, substr)
+            if by_discriminant {
+                cs_partial_cmp_by_discriminant(cx, span, substr)
+            } else {
+                cs_partial_cmp(cx, span, substr)
+            }
         })),
     };
 
@@ -50,10 +60,100 @@ pub fn expand_deriving_partial_ord(
         supports_unions: false,
         methods: vec![partial_cmp_def],
         associated_types: Vec::new(),
+        skip_attr: Some(sym::partial_ord),
     };
     trait_def.expand(cx, mitem, item, push)
 }
 
+/// Checks whether the item is an enum marked `#[partial_ord(by_discriminant)]`.
+fn is_by_discriminant(cx: &ExtCtxt<'_>, item: &Annotatable) -> bool {
+    let item = match item {
+        Annotatable::Item(item) => item,
+        _ => return false,
+    };
+    let attr = match cx.sess.find_by_name(&item.attrs, sym::partial_ord) {
+        Some(attr) => attr,
+        None => return false,
+    };
+    match attr.meta_item_list().as_deref() {
+        Some([nested]) if nested.is_word() && nested.has_name(sym::by_discriminant) => {}
+        _ => {
+            cx.span_err(attr.span, "expected `#[partial_ord(by_discriminant)]`");
+            return false;
+        }
+    }
+    if !cx.ecfg.features.map_or(true, |features| features.partial_ord_by_discriminant) {
+        feature_err(
+            &cx.sess.parse_sess,
+            sym::partial_ord_by_discriminant,
+            attr.span,
+            "`#[partial_ord(by_discriminant)]` is experimental",
+        )
+        .emit();
+        return false;
+    }
+    if !matches!(item.kind, ItemKind::Enum(..)) {
+        cx.span_err(attr.span, "`#[partial_ord(by_discriminant)]` can only be used on enums");
+        return false;
+    }
+    true
+}
+
+/// Lints structs whose derived ordering is decided by a string field before the fields
+/// following it, like a `version: String` before a `major: u32`: strings are compared
+/// lexicographically, so `"10"` is less than `"9"`, which is rarely the intended order.
+fn check_field_order(cx: &ExtCtxt<'_>, item: &Annotatable) {
+    let item = match item {
+        Annotatable::Item(item) => item,
+        _ => return,
+    };
+    let data = match item.kind {
+        ItemKind::Struct(ref data, _) => data,
+        _ => return,
+    };
+    let compared: Vec<(String, &ast::FieldDef)> = data
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| cx.sess.find_by_name(&field.attrs, sym::partial_ord).is_none())
+        .map(|(i, field)| {
+            (field.ident.map_or_else(|| i.to_string(), |ident| ident.to_string()), field)
+        })
+        .collect();
+    let first_string = match compared.iter().position(|(_, field)| is_string_ty(&field.ty)) {
+        Some(i) if i + 1 < compared.len() => i,
+        _ => return,
+    };
+    let (name, field) = &compared[first_string];
+    cx.parse_sess().buffer_lint_with_diagnostic(
+        PARTIAL_ORD_FIELD_ORDER,
+        field.span,
+        cx.resolver.lint_node_id(cx.current_expansion.id),
+        &format!(
+            "the derived ordering compares the string field `{}` before `{}`",
+            name,
+            compared[first_string + 1].0
+        ),
+        BuiltinLintDiagnostics::PartialOrdFieldOrder(
+            compared.iter().map(|(name, _)| name.clone()).collect(),
+        ),
+    );
+}
+
+/// Whether `ty` is `String` or `&str`, syntactically.
+fn is_string_ty(ty: &ast::Ty) -> bool {
+    match ty.kind {
+        ast::TyKind::Path(None, ref path) => {
+            path.segments.last().map_or(false, |segment| segment.ident.name == sym::String)
+        }
+        ast::TyKind::Rptr(_, ast::MutTy { ref ty, .. }) => match ty.kind {
+            ast::TyKind::Path(None, ref path) => path == &sym::str,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 pub fn cs_partial_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_>) -> P<Expr> {
     let test_id = Ident::new(sym::cmp, span);
     let ordering = cx.path_global(span, cx.std_path(&[sym::cmp, sym::Ordering, sym::Equal]));
@@ -61,6 +161,11 @@ pub fn cs_partial_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_
     let equals_expr = cx.expr_some(span, ordering_expr);
 
     let partial_cmp_path = cx.std_path(&[sym::cmp, sym::PartialOrd, sym::partial_cmp]);
+    let and_then_path = cx.std_path(&[sym::option, sym::Option, sym::and_then]);
+    let is_eq_path = cx.std_path(&[sym::cmp, sym::Ordering, sym::is_eq]);
+
+    let chain = cx.sess.opts.debugging_opts.derive_cmp_strategy == DeriveCmpStrategy::Chain;
+    let mut first_field = true;
 
     // Builds:
     //
@@ -75,10 +180,22 @@ pub fn cs_partial_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_
     // cmp => cmp
     // }
     //
-    cs_fold(
+    // or, with `-Z derive-cmp-strategy=chain`:
+    //
+    // ::std::option::Option::and_then(
+    //     ::std::cmp::PartialOrd::partial_cmp(&self_field1, &other_field1),
+    //     |cmp| if ::std::cmp::Ordering::is_eq(cmp) {
+    //         ::std::cmp::PartialOrd::partial_cmp(&self_field2, &other_field2)
+    //     } else {
+    //         ::std::option::Option::Some(cmp)
+    //     },
+    // )
+    //
+    cs_fold_unskipped(
         // foldr nests the if-elses correctly, leaving the first field
-        // as the outermost one, and the last as the innermost.
-        false,
+        // as the outermost one, and the last as the innermost. A chain
+        // is built from the first field on instead.
+        chain,
         |cx, span, old, self_f, other_fs| {
             // match new {
             //     Some(::std::cmp::Ordering::Equal) => old,
@@ -97,25 +214,78 @@ pub fn cs_partial_cmp(cx: &mut ExtCtxt<'_>, span: Span, substr: &Substructure<'_
                 cx.expr_call_global(span, partial_cmp_path.clone(), args)
             };
 
+            if chain {
+                // The first field starts the chain, there is nothing to compare before it.
+                if mem::replace(&mut first_field, false) {
+                    return new;
+                }
+                let is_eq = cx.expr_call_global(
+                    span,
+                    is_eq_path.clone(),
+                    vec![cx.expr_ident(span, test_id)],
+                );
+                let unequal = cx.expr_some(span, cx.expr_ident(span, test_id));
+                let body = cx.expr_if(span, is_eq, new, Some(unequal));
+                let args = vec![old, cx.lambda1(span, body, test_id)];
+                return cx.expr_call_global(span, and_then_path.clone(), args);
+            }
+
             let eq_arm = cx.arm(span, cx.pat_some(span, cx.pat_path(span, ordering.clone())), old);
             let neq_arm = cx.arm(span, cx.pat_ident(span, test_id), cx.expr_ident(span, test_id));
 
             cx.expr_match(span, new, vec![eq_arm, neq_arm])
         },
         equals_expr,
-        Box::new(|cx, span, (self_args, tag_tuple), _non_self_args| {
-            if self_args.len() != 2 {
-                cx.span_bug(span, "not exactly 2 arguments in `derive(PartialOrd)`")
-            } else {
-                let lft = cx.expr_addr_of(span, cx.expr_ident(span, tag_tuple[0]));
-                let rgt = cx.expr_addr_of(span, cx.expr_ident(span, tag_tuple[1]));
-                let fn_partial_cmp_path =
-                    cx.std_path(&[sym::cmp, sym::PartialOrd, sym::partial_cmp]);
-                cx.expr_call_global(span, fn_partial_cmp_path, vec![lft, rgt])
-            }
-        }),
+        Box::new(cs_partial_cmp_tags),
         cx,
         span,
         substr,
     )
 }
+
+/// Like `cs_partial_cmp`, but for enums marked `#[partial_ord(by_discriminant)]`:
+/// only the discriminants are compared, the fields of the variants are ignored.
+pub fn cs_partial_cmp_by_discriminant(
+    cx: &mut ExtCtxt<'_>,
+    span: Span,
+    substr: &Substructure<'_>,
+) -> P<Expr> {
+    let ordering = cx.path_global(span, cx.std_path(&[sym::cmp, sym::Ordering, sym::Equal]));
+    let equals_expr = cx.expr_some(span, cx.expr_path(ordering));
+
+    // Builds:
+    //
+    // match (&*self, &*other) {
+    // (&A(..), &A(..)) => ::std::option::Option::Some(::std::cmp::Ordering::Equal),
+    // ...
+    // _ => ::std::cmp::PartialOrd::partial_cmp(&__self_vi, &__arg_1_vi),
+    // }
+    //
+    cs_fold(
+        false,
+        // Values of the same variant are equal, whatever their fields are.
+        |_, _, old, _, _| old,
+        equals_expr,
+        Box::new(cs_partial_cmp_tags),
+        cx,
+        span,
+        substr,
+    )
+}
+
+/// Compares the discriminants of two values of different variants.
+fn cs_partial_cmp_tags(
+    cx: &mut ExtCtxt<'_>,
+    span: Span,
+    (self_args, tag_tuple): (&[Ident], &[Ident]),
+    _non_self_args: &[P<Expr>],
+) -> P<Expr> {
+    if self_args.len() != 2 {
+        cx.span_bug(span, "not exactly 2 arguments in `derive(PartialOrd)`")
+    } else {
+        let lft = cx.expr_addr_of(span, cx.expr_ident(span, tag_tuple[0]));
+        let rgt = cx.expr_addr_of(span, cx.expr_ident(span, tag_tuple[1]));
+        let fn_partial_cmp_path = cx.std_path(&[sym::cmp, sym::PartialOrd, sym::partial_cmp]);
+        cx.expr_call_global(span, fn_partial_cmp_path, vec![lft, rgt])
+    }
+}
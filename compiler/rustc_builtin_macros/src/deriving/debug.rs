@@ -37,7 +37,7 @@ pub fn expand_deriving_debug(
             explicit_self: borrowed_explicit_self(),
             args: vec![(fmtr, sym::f)],
             ret_ty: Literal(path_std!(fmt::Result)),
-            attributes: Vec::new(),
+            attributes: MethodAttrs::default(),
             is_unsafe: false,
             unify_fieldless_variants: false,
             combine_substructure: combine_substructure(Box::new(|a, b, c| {
@@ -45,6 +45,7 @@ pub fn expand_deriving_debug(
             })),
         }],
         associated_types: Vec::new(),
+        skip_attr: None,
     };
     trait_def.expand(cx, mitem, item, push)
 }
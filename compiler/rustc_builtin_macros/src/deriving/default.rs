@@ -15,8 +15,6 @@ pub fn expand_deriving_default(
     item: &Annotatable,
     push: &mut dyn FnMut(Annotatable),
 ) {
-    let inline = cx.meta_word(span, sym::inline);
-    let attrs = vec![cx.attribute(inline)];
     let trait_def = TraitDef {
         span,
         attributes: Vec::new(),
@@ -31,7 +29,7 @@ pub fn expand_deriving_default(
             explicit_self: None,
             args: Vec::new(),
             ret_ty: Self_,
-            attributes: attrs,
+            attributes: MethodAttrs::inline(),
             is_unsafe: false,
             unify_fieldless_variants: false,
             combine_substructure: combine_substructure(Box::new(|a, b, c| {
@@ -39,6 +37,7 @@ pub fn expand_deriving_default(
             })),
         }],
         associated_types: Vec::new(),
+        skip_attr: None,
     };
     trait_def.expand(cx, mitem, item, push)
 }
@@ -187,6 +187,7 @@
 use rustc_attr as attr;
 use rustc_data_structures::map_in_place::MapInPlace;
 use rustc_expand::base::{Annotatable, ExtCtxt};
+use rustc_session::parse::feature_err;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::Span;
 
@@ -221,6 +222,10 @@ pub struct TraitDef<'a> {
     pub methods: Vec<MethodDef<'a>>,
 
     pub associated_types: Vec<(Ident, Ty)>,
+
+    /// The helper attribute marking the fields left out of the derived impl, e.g. `ord` for
+    /// `#[ord(skip)]`, if the trait supports skipping fields.
+    pub skip_attr: Option<Symbol>,
 }
 
 pub struct MethodDef<'a> {
@@ -240,7 +245,7 @@ pub struct MethodDef<'a> {
     /// Returns type
     pub ret_ty: Ty,
 
-    pub attributes: Vec<ast::Attribute>,
+    pub attributes: MethodAttrs,
 
     // Is it an `unsafe fn`?
     pub is_unsafe: bool,
@@ -251,6 +256,45 @@ pub struct MethodDef<'a> {
     pub combine_substructure: RefCell<CombineSubstructureFunc<'a>>,
 }
 
+/// The attributes of a derived method. They are built in one place so that all derives
+/// agree on them, each method only picks which of them apply to it.
+#[derive(Clone, Copy, Default)]
+pub struct MethodAttrs {
+    /// `#[inline]`
+    pub inline: bool,
+    /// `#[doc(hidden)]`
+    pub doc_hidden: bool,
+    /// `#[no_coverage]`
+    pub no_coverage: bool,
+}
+
+impl MethodAttrs {
+    /// The attributes of a small method, which is worth inlining.
+    pub fn inline() -> MethodAttrs {
+        MethodAttrs { inline: true, ..MethodAttrs::default() }
+    }
+
+    fn to_attributes(self, cx: &ExtCtxt<'_>, span: Span) -> Vec<ast::Attribute> {
+        let mut attrs = Vec::new();
+        if self.inline {
+            attrs.push(cx.attribute(cx.meta_word(span, sym::inline)));
+        }
+        if self.doc_hidden {
+            attrs.push(list_attribute(cx, span, sym::doc, sym::hidden));
+        }
+        if self.no_coverage {
+            attrs.push(cx.attribute(cx.meta_word(span, sym::no_coverage)));
+        }
+        attrs
+    }
+}
+
+/// Builds `#[name(word)]`.
+fn list_attribute(cx: &ExtCtxt<'_>, span: Span, name: Symbol, word: Symbol) -> ast::Attribute {
+    let word = rustc_ast::attr::mk_nested_word_item(Ident::new(word, span));
+    cx.attribute(rustc_ast::attr::mk_list_item(Ident::new(name, span), vec![word]))
+}
+
 /// All the data about the data structure/method being derived upon.
 pub struct Substructure<'a> {
     /// ident of self
@@ -281,6 +325,9 @@ pub struct FieldInfo<'a> {
     pub other: Vec<P<Expr>>,
     /// The attributes on the field
     pub attrs: &'a [ast::Attribute],
+    /// Whether the field is marked `#[<skip_attr>(skip)]` and is left out of the
+    /// derived implementation.
+    pub skipped: bool,
 }
 
 /// Fields for a static method
@@ -372,6 +419,31 @@ fn visit_mac_call(&mut self, mac: &ast::MacCall) {
     visitor.types
 }
 
+/// Whether one of the type parameters `names` appears in `ty`.
+fn mentions_ty_params(ty: &ast::Ty, names: &[Symbol]) -> bool {
+    use rustc_ast::visit;
+
+    struct Visitor<'b> {
+        names: &'b [Symbol],
+        found: bool,
+    }
+
+    impl<'a, 'b> visit::Visitor<'a> for Visitor<'b> {
+        fn visit_ty(&mut self, ty: &'a ast::Ty) {
+            if let ast::TyKind::Path(_, ref path) = ty.kind {
+                if let Some(segment) = path.segments.first() {
+                    self.found |= self.names.contains(&segment.ident.name);
+                }
+            }
+            visit::walk_ty(self, ty)
+        }
+    }
+
+    let mut visitor = Visitor { names, found: false };
+    visit::Visitor::visit_ty(&mut visitor, ty);
+    visitor.found
+}
+
 impl<'a> TraitDef<'a> {
     pub fn expand(
         self,
@@ -505,12 +577,32 @@ pub fn expand_ext(
     ///
     /// where B1, ..., BN are the bounds given by `bounds_paths`.'. Z is a phantom type, and
     /// therefore does not get bound by the derived trait.
+    ///
+    /// With `#![feature(derive_relaxed_bounds)]`, `bounded_field_tys` holds the types of the
+    /// fields used by the derived impl which mention a type parameter, and the impl is instead
+    /// bounded on them, leaving the type parameters with their declared bounds only:
+    ///
+    /// ```ignore (only-for-syntax-highlight)
+    /// impl<'a, ..., 'z, A, B: DeclaredTrait, C, ... Z> where
+    ///     C:                               WhereTrait,
+    ///     A:                               DerivedTrait + B1 + ... + BN,
+    ///     B::Item:                         DerivedTrait + B1 + ... + BN,
+    ///     <B as DeclaredTrait>::Item:      DerivedTrait + B1 + ... + BN,
+    ///     <C as WhereTrait>::Item:         DerivedTrait + B1 + ... + BN,
+    ///     Option<<C as WhereTrait>::Item>: DerivedTrait + B1 + ... + BN,
+    ///     ...
+    /// ```
+    ///
+    /// This lets a type parameter which is only used by skipped fields, or through a field type
+    /// implementing the trait regardless of it, go unbounded. The declared bounds of the type
+    /// parameters, like `?Sized` for the type of a last field, are kept as they are.
     fn create_derived_impl(
         &self,
         cx: &mut ExtCtxt<'_>,
         type_ident: Ident,
         generics: &Generics,
         field_tys: Vec<P<ast::Ty>>,
+        bounded_field_tys: Option<Vec<P<ast::Ty>>>,
         methods: Vec<P<ast::AssocItem>>,
     ) -> P<ast::Item> {
         let trait_path = self.path.to_path(cx, self.span, type_ident, generics);
@@ -543,6 +635,9 @@ fn create_derived_impl(
         // Create the generic parameters
         params.extend(generics.params.iter().map(|param| match &param.kind {
             GenericParamKind::Lifetime { .. } => param.clone(),
+            GenericParamKind::Type { .. } if bounded_field_tys.is_some() => {
+                cx.typaram(self.span, param.ident, vec![], param.bounds.clone(), None)
+            }
             GenericParamKind::Type { .. } => {
                 // I don't think this can be moved out of the loop, since
                 // a GenericBound requires an ast id
@@ -604,7 +699,24 @@ fn create_derived_impl(
             }
         }));
 
-        {
+        if let Some(bounded_field_tys) = bounded_field_tys {
+            for field_ty in bounded_field_tys {
+                let mut bounds: Vec<_> = self
+                    .additional_bounds
+                    .iter()
+                    .map(|p| cx.trait_bound(p.to_path(cx, self.span, type_ident, generics)))
+                    .collect();
+                bounds.push(cx.trait_bound(trait_path.clone()));
+
+                let predicate = ast::WhereBoundPredicate {
+                    span: self.span,
+                    bound_generic_params: Vec::new(),
+                    bounded_ty: field_ty,
+                    bounds,
+                };
+                where_clause.predicates.push(ast::WherePredicate::BoundPredicate(predicate));
+            }
+        } else {
             // Extra scope required here so ty_params goes out of scope before params is moved
 
             let mut ty_params = params
@@ -680,14 +792,7 @@ fn create_derived_impl(
         // Just mark it now since we know that it'll end up used downstream
         cx.sess.mark_attr_used(&attr);
         let opt_trait_ref = Some(trait_ref);
-        let unused_qual = {
-            let word = rustc_ast::attr::mk_nested_word_item(Ident::new(
-                sym::unused_qualifications,
-                self.span,
-            ));
-            let list = rustc_ast::attr::mk_list_item(Ident::new(sym::allow, self.span), vec![word]);
-            cx.attribute(list)
-        };
+        let unused_qual = list_attribute(cx, self.span, sym::allow, sym::unused_qualifications);
 
         let mut a = vec![attr, unused_qual];
         a.extend(self.attributes.iter().cloned());
@@ -711,6 +816,73 @@ fn create_derived_impl(
         )
     }
 
+    /// With `#![feature(derive_relaxed_bounds)]`, returns the types of the fields used by the
+    /// derived impl which mention a type parameter, to bound the impl on them rather than on
+    /// the type parameters. Fields marked `#[<skip_attr>(skip)]` are left out.
+    fn bounded_field_tys<'b>(
+        &self,
+        cx: &ExtCtxt<'_>,
+        generics: &Generics,
+        fields: impl Iterator<Item = &'b ast::FieldDef>,
+    ) -> Option<Vec<P<ast::Ty>>> {
+        if !cx.ecfg.features.map_or(false, |features| features.derive_relaxed_bounds) {
+            return None;
+        }
+        let ty_param_names: Vec<Symbol> = generics
+            .params
+            .iter()
+            .filter(|param| matches!(param.kind, GenericParamKind::Type { .. }))
+            .map(|param| param.ident.name)
+            .collect();
+        let field_tys = fields
+            .filter(|field| !self.is_skipped_field(cx, &field.attrs))
+            .filter(|field| mentions_ty_params(&field.ty, &ty_param_names))
+            .map(|field| field.ty.clone())
+            .collect();
+        Some(field_tys)
+    }
+
+    /// Whether a field with the given attributes is marked `#[<skip_attr>(skip)]`. Invalid
+    /// arguments of the attribute are reported once by `check_skip_attrs`.
+    fn is_skipped_field(&self, cx: &ExtCtxt<'_>, attrs: &[ast::Attribute]) -> bool {
+        let skip_attr = match self.skip_attr {
+            Some(skip_attr) => skip_attr,
+            None => return false,
+        };
+        let list = cx.sess.find_by_name(attrs, skip_attr).and_then(|attr| attr.meta_item_list());
+        matches!(list.as_deref(), Some([nested]) if nested.is_word() && nested.has_name(sym::skip))
+    }
+
+    /// Reports the `#[<skip_attr>(...)]` attributes of `fields` whose argument is not `skip`,
+    /// which are compared like any other field, and the ones used without the
+    /// `derive_skip_fields` feature.
+    fn check_skip_attrs<'b>(
+        &self,
+        cx: &ExtCtxt<'_>,
+        fields: impl Iterator<Item = &'b ast::FieldDef>,
+    ) {
+        let skip_attr = match self.skip_attr {
+            Some(skip_attr) => skip_attr,
+            None => return,
+        };
+        let enabled = cx.ecfg.features.map_or(true, |features| features.derive_skip_fields);
+        for field in fields {
+            if let Some(attr) = cx.sess.find_by_name(&field.attrs, skip_attr) {
+                if !self.is_skipped_field(cx, &field.attrs) {
+                    cx.span_err(attr.span, &format!("expected `#[{}(skip)]`", skip_attr));
+                } else if !enabled {
+                    feature_err(
+                        &cx.sess.parse_sess,
+                        sym::derive_skip_fields,
+                        attr.span,
+                        &format!("`#[{}(skip)]` is experimental", skip_attr),
+                    )
+                    .emit();
+                }
+            }
+        }
+    }
+
     fn expand_struct_def(
         &self,
         cx: &mut ExtCtxt<'_>,
@@ -722,6 +894,8 @@ fn expand_struct_def(
     ) -> P<ast::Item> {
         let field_tys: Vec<P<ast::Ty>> =
             struct_def.fields().iter().map(|field| field.ty.clone()).collect();
+        self.check_skip_attrs(cx, struct_def.fields().iter());
+        let bounded_field_tys = self.bounded_field_tys(cx, generics, struct_def.fields().iter());
 
         let methods = self
             .methods
@@ -755,7 +929,7 @@ fn expand_struct_def(
             })
             .collect();
 
-        self.create_derived_impl(cx, type_ident, generics, field_tys, methods)
+        self.create_derived_impl(cx, type_ident, generics, field_tys, bounded_field_tys, methods)
     }
 
     fn expand_enum_def(
@@ -771,6 +945,15 @@ fn expand_enum_def(
         for variant in &enum_def.variants {
             field_tys.extend(variant.data.fields().iter().map(|field| field.ty.clone()));
         }
+        self.check_skip_attrs(
+            cx,
+            enum_def.variants.iter().flat_map(|variant| variant.data.fields()),
+        );
+        let bounded_field_tys = self.bounded_field_tys(
+            cx,
+            generics,
+            enum_def.variants.iter().flat_map(|variant| variant.data.fields()),
+        );
 
         let methods = self
             .methods
@@ -803,7 +986,7 @@ fn expand_enum_def(
             })
             .collect();
 
-        self.create_derived_impl(cx, type_ident, generics, field_tys, methods)
+        self.create_derived_impl(cx, type_ident, generics, field_tys, bounded_field_tys, methods)
     }
 }
 
@@ -932,7 +1115,7 @@ fn create_method(
         // Create the method.
         P(ast::AssocItem {
             id: ast::DUMMY_NODE_ID,
-            attrs: self.attributes.clone(),
+            attrs: self.attributes.to_attributes(cx, trait_.span),
             span: trait_.span,
             vis: ast::Visibility {
                 span: trait_lo_sp,
@@ -1026,6 +1209,7 @@ fn expand_struct_method_body<'b>(
                         })
                         .collect(),
                     attrs,
+                    skipped: trait_.is_skipped_field(cx, attrs),
                 })
                 .collect()
         } else {
@@ -1279,6 +1463,7 @@ fn build_enum_match_tuple<'b>(
                             self_: self_getter_expr,
                             other: others,
                             attrs,
+                            skipped: trait_.is_skipped_field(cx, attrs),
                         }
                     })
                     .collect::<Vec<FieldInfo<'_>>>();
@@ -1689,6 +1874,34 @@ pub fn cs_fold<F>(
     }
 }
 
+/// Like `cs_fold`, but leaves out the fields marked `#[<skip_attr>(skip)]`.
+pub fn cs_fold_unskipped<F>(
+    use_foldl: bool,
+    mut f: F,
+    base: P<Expr>,
+    enum_nonmatch_f: EnumNonMatchCollapsedFunc<'_>,
+    cx: &mut ExtCtxt<'_>,
+    trait_span: Span,
+    substructure: &Substructure<'_>,
+) -> P<Expr>
+where
+    F: FnMut(&mut ExtCtxt<'_>, Span, P<Expr>, P<Expr>, &[P<Expr>]) -> P<Expr>,
+{
+    match *substructure.fields {
+        EnumMatching(.., ref all_fields) | Struct(_, ref all_fields) => {
+            let fields = all_fields.iter().filter(|field| !field.skipped);
+            let fields: Vec<_> = if use_foldl { fields.collect() } else { fields.rev().collect() };
+            fields
+                .into_iter()
+                .fold(base, |old, field| f(cx, field.span, old, field.self_.clone(), &field.other))
+        }
+        EnumNonMatchingCollapsed(..) => {
+            cs_fold_enumnonmatch(enum_nonmatch_f, cx, trait_span, substructure)
+        }
+        StaticEnum(..) | StaticStruct(..) => cs_fold_static(cx, trait_span),
+    }
+}
+
 /// Function to fold over fields, with three cases, to generate more efficient and concise code.
 /// When the `substructure` has grouped fields, there are two cases:
 /// Zero fields: call the base case function with `None` (like the usual base case of `cs_fold`).
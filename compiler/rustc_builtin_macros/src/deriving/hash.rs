@@ -34,7 +34,7 @@ pub fn expand_deriving_hash(
             explicit_self: borrowed_explicit_self(),
             args: vec![(Ptr(Box::new(Literal(arg)), Borrowed(None, Mutability::Mut)), sym::state)],
             ret_ty: nil_ty(),
-            attributes: vec![],
+            attributes: MethodAttrs::default(),
             is_unsafe: false,
             unify_fieldless_variants: true,
             combine_substructure: combine_substructure(Box::new(|a, b, c| {
@@ -42,6 +42,7 @@ pub fn expand_deriving_hash(
             })),
         }],
         associated_types: Vec::new(),
+        skip_attr: None,
     };
 
     hash_trait_def.expand(cx, mitem, item, push);
@@ -630,6 +630,56 @@ pub(crate) fn codegen_intrinsic_call<'tcx>(
 
             ret.write_cvalue(fx, res);
         };
+        checked_add | checked_sub | checked_mul | checked_shl | checked_shr, (c x, c y) {
+            let bin_op = match intrinsic {
+                sym::checked_add => BinOp::Add,
+                sym::checked_sub => BinOp::Sub,
+                sym::checked_mul => BinOp::Mul,
+                sym::checked_shl => BinOp::Shl,
+                sym::checked_shr => BinOp::Shr,
+                _ => unreachable!(),
+            };
+
+            let (val, has_overflow) = match intrinsic {
+                sym::checked_shl | sym::checked_shr => {
+                    // The amount is a `u32`, so it has to be checked before it is truncated to the
+                    // width of `x`. Checked 128-bit shifts don't report overflow yet, so the check
+                    // isn't left to `codegen_checked_int_binop`.
+                    let bits = i64::try_from(x.layout().size.bits()).unwrap();
+                    let amount = y.load_scalar(fx);
+                    let has_overflow =
+                        fx.bcx.ins().icmp_imm(IntCC::UnsignedGreaterThanOrEqual, amount, bits);
+                    let val = crate::num::codegen_int_binop(fx, bin_op, x, y).load_scalar(fx);
+                    (val, has_overflow)
+                }
+                _ => crate::num::codegen_checked_int_binop(fx, bin_op, x, y).load_scalar_pair(fx),
+            };
+
+            let option = ret.layout().ty.ty_adt_def().unwrap();
+            let variant = |lang_item| {
+                option.variant_index_with_id(fx.tcx.require_lang_item(lang_item, Some(span)))
+            };
+            let some = variant(rustc_hir::LangItem::OptionSome);
+            let none = variant(rustc_hir::LangItem::OptionNone);
+
+            let some_block = fx.bcx.create_block();
+            let none_block = fx.bcx.create_block();
+            let next_block = fx.bcx.create_block();
+            fx.bcx.ins().brnz(has_overflow, none_block, &[]);
+            fx.bcx.ins().jump(some_block, &[]);
+
+            fx.bcx.switch_to_block(some_block);
+            let payload = ret.downcast_variant(fx, some).place_field(fx, mir::Field::new(0));
+            payload.write_cvalue(fx, CValue::by_val(val, x.layout()));
+            crate::discriminant::codegen_set_discriminant(fx, ret, some);
+            fx.bcx.ins().jump(next_block, &[]);
+
+            fx.bcx.switch_to_block(none_block);
+            crate::discriminant::codegen_set_discriminant(fx, ret, none);
+            fx.bcx.ins().jump(next_block, &[]);
+
+            fx.bcx.switch_to_block(next_block);
+        };
         rotate_left, <T>(v x, v y) {
             let layout = fx.layout_of(T);
             let y = if fx.bcx.func.dfg.value_type(y) == types::I128 {
@@ -3,7 +3,8 @@
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::{ConstValue, ErrorHandled};
 use rustc_middle::ty::layout::HasTyCtxt;
-use rustc_middle::ty::{self, Ty};
+use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::{self, Ty, TypeFoldable};
 use rustc_span::source_map::Span;
 use rustc_target::abi::Abi;
 
@@ -35,7 +36,18 @@ pub fn eval_mir_constant(
                 .tcx()
                 .const_eval_resolve(ty::ParamEnv::reveal_all(), ct, None)
                 .map_err(|err| {
-                    self.cx.tcx().sess.span_err(constant.span, "erroneous constant encountered");
+                    let mut diag = self
+                        .cx
+                        .tcx()
+                        .sess
+                        .struct_span_err(constant.span, "erroneous constant encountered");
+                    if let Some(instantiation) = self.describe_instantiation(constant.literal, ct) {
+                        diag.span_label(
+                            constant.span,
+                            format!("required by the instantiation {} here", instantiation),
+                        );
+                    }
+                    diag.emit();
                     err
                 }),
             ty::ConstKind::Value(value) => Ok(value),
@@ -47,6 +59,34 @@ pub fn eval_mir_constant(
         }
     }
 
+    /// Describes the generic arguments of the constant `ct` which only became known by
+    /// monomorphizing `literal`, as in "`N = 200_u32`".
+    fn describe_instantiation(
+        &self,
+        literal: mir::ConstantKind<'tcx>,
+        ct: ty::Unevaluated<'tcx>,
+    ) -> Option<String> {
+        let tcx = self.cx.tcx();
+        let generic_substs = match literal {
+            mir::ConstantKind::Ty(&ty::Const { val: ty::ConstKind::Unevaluated(uv), .. }) => {
+                uv.substs
+            }
+            _ => return None,
+        };
+        let generics = tcx.generics_of(ct.def.did);
+        let args = generic_substs
+            .iter()
+            .zip(ct.substs)
+            .enumerate()
+            .filter(|(_, (generic_arg, _))| generic_arg.needs_subst())
+            .filter_map(|(i, (_, arg))| match arg.unpack() {
+                GenericArgKind::Lifetime(_) => None,
+                _ => Some(format!("{} = {}", generics.param_at(i, tcx).name, arg)),
+            })
+            .collect::<Vec<_>>();
+        if args.is_empty() { None } else { Some(format!("`{}`", args.join(", "))) }
+    }
+
     /// process constant containing SIMD shuffle indices
     pub fn simd_shuffle_indices(
         &mut self,
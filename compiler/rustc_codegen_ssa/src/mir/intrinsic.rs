@@ -1,11 +1,14 @@
 use super::operand::{OperandRef, OperandValue};
 use super::place::PlaceRef;
 use super::FunctionCx;
+use crate::base;
 use crate::common::{span_invalid_monomorphization_error, IntPredicate};
 use crate::glue;
 use crate::traits::*;
 use crate::MemFlags;
 
+use rustc_hir as hir;
+use rustc_hir::LangItem;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::{sym, Span};
 use rustc_target::abi::call::{FnAbi, PassMode};
@@ -194,6 +197,73 @@ pub fn codegen_intrinsic_call(
                 args[1].val.unaligned_volatile_store(bx, dst);
                 return;
             }
+            sym::checked_add
+            | sym::checked_sub
+            | sym::checked_mul
+            | sym::checked_shl
+            | sym::checked_shr => {
+                let ty = arg_tys[0];
+                let (width, signed) = match int_type_width_signed(ty, bx.tcx()) {
+                    Some(it) => it,
+                    None => {
+                        span_invalid_monomorphization_error(
+                            bx.tcx().sess,
+                            span,
+                            &format!(
+                                "invalid monomorphization of `{}` intrinsic: \
+                                      expected basic integer type, found `{}`",
+                                name, ty
+                            ),
+                        );
+                        return;
+                    }
+                };
+                let (lhs, rhs) = (args[0].immediate(), args[1].immediate());
+                let (val, overflow) = match name {
+                    sym::checked_add => bx.checked_binop(OverflowOp::Add, ty, lhs, rhs),
+                    sym::checked_sub => bx.checked_binop(OverflowOp::Sub, ty, lhs, rhs),
+                    sym::checked_mul => bx.checked_binop(OverflowOp::Mul, ty, lhs, rhs),
+                    sym::checked_shl | sym::checked_shr => {
+                        // The amount is a `u32`, so the overflow check has to happen before
+                        // it is cast to the type of `lhs`. The shifted value is poison if the
+                        // shift overflows, but it is only stored when it does not.
+                        let bits = bx.const_u32(width as u32);
+                        let overflow = bx.icmp(IntPredicate::IntUGE, rhs, bits);
+                        let rhs = base::cast_shift_expr_rhs(bx, hir::BinOpKind::Shl, lhs, rhs);
+                        let val = match name {
+                            sym::checked_shl => bx.shl(lhs, rhs),
+                            _ if signed => bx.ashr(lhs, rhs),
+                            _ => bx.lshr(lhs, rhs),
+                        };
+                        (val, overflow)
+                    }
+                    _ => bug!(),
+                };
+
+                let option = ret_ty.ty_adt_def().unwrap();
+                let variant = |item| {
+                    option.variant_index_with_id(bx.tcx().require_lang_item(item, Some(span)))
+                };
+                let (some, none) = (variant(LangItem::OptionSome), variant(LangItem::OptionNone));
+
+                let mut some_bx = bx.build_sibling_block("checked_some");
+                let mut none_bx = bx.build_sibling_block("checked_none");
+                let next_bx = bx.build_sibling_block("checked_next");
+                bx.cond_br(overflow, none_bx.llbb(), some_bx.llbb());
+
+                let dest =
+                    result.project_downcast(&mut some_bx, some).project_field(&mut some_bx, 0);
+                let val = some_bx.from_immediate(val);
+                some_bx.store(val, dest.llval, dest.align);
+                result.codegen_set_discr(&mut some_bx, some);
+                some_bx.br(next_bx.llbb());
+
+                result.codegen_set_discr(&mut none_bx, none);
+                none_bx.br(next_bx.llbb());
+
+                *bx = next_bx;
+                return;
+            }
             sym::add_with_overflow
             | sym::sub_with_overflow
             | sym::mul_with_overflow
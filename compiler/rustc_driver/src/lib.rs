@@ -417,6 +417,10 @@ fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if sess.opts.debugging_opts.print_generator_sizes {
+                sess.code_stats.print_generator_sizes();
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -24,6 +24,10 @@ pub struct Diagnostic {
     /// as a sort key to sort a buffer of diagnostics.  By default, it is the primary span of
     /// `span` if there is one.  Otherwise, it is `DUMMY_SP`.
     pub sort_span: Span,
+
+    /// Key-value pairs that are only emitted in the JSON output, for tools which need to
+    /// identify what a diagnostic is about, e.g. to recognize the same error across builds.
+    pub tool_metadata: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
@@ -91,6 +95,7 @@ pub fn new_with_code(level: Level, code: Option<DiagnosticId>, message: &str) ->
             children: vec![],
             suggestions: vec![],
             sort_span: DUMMY_SP,
+            tool_metadata: vec![],
         }
     }
 
@@ -537,6 +542,13 @@ pub fn clear_code(&mut self) -> &mut Self {
         self
     }
 
+    /// Adds a key-value pair to the metadata of the JSON output. It is not shown in the
+    /// human-readable output.
+    pub fn tool_metadata(&mut self, key: &str, value: String) -> &mut Self {
+        self.tool_metadata.push((key.to_owned(), value));
+        self
+    }
+
     pub fn get_code(&self) -> Option<DiagnosticId> {
         self.code.clone()
     }
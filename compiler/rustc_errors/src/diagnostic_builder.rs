@@ -364,6 +364,7 @@ pub fn tool_only_span_suggestion(
     forward!(pub fn set_primary_message<M: Into<String>>(&mut self, msg: M) -> &mut Self);
     forward!(pub fn set_span<S: Into<MultiSpan>>(&mut self, sp: S) -> &mut Self);
     forward!(pub fn code(&mut self, s: DiagnosticId) -> &mut Self);
+    forward!(pub fn tool_metadata(&mut self, key: &str, value: String) -> &mut Self);
 
     /// Allow attaching suggestions this diagnostic.
     /// If this is set to `false`, then any suggestions attached with the `span_suggestion_*`
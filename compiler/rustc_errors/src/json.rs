@@ -400,7 +400,7 @@ fn flush(&mut self) -> io::Result<()> {
                 .chain(sugg)
                 .collect(),
             rendered: Some(output),
-            tool_metadata: ToolMetadata::default(),
+            tool_metadata: ToolMetadata::from_pairs(&diag.tool_metadata),
         }
     }
 
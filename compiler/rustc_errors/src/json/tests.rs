@@ -5,7 +5,7 @@
 
 use crate::emitter::{ColorConfig, HumanReadableErrorType};
 use crate::Handler;
-use rustc_serialize::json::decode;
+use rustc_serialize::json::{decode, Json};
 use rustc_span::{BytePos, Span};
 
 use std::str;
@@ -202,3 +202,31 @@ fn span_after_crlf() {
         },
     )
 }
+
+#[test]
+fn tool_metadata() {
+    with_default_session_globals(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            false,
+            HumanReadableErrorType::Short(ColorConfig::Never),
+            None,
+            false,
+        );
+
+        let handler = Handler::with_emitter(true, None, Box::new(je));
+        handler.struct_err("foo").tool_metadata("body", "main".to_owned()).emit();
+        handler.struct_err("bar").emit();
+
+        let bytes = output.lock().unwrap();
+        let mut lines = str::from_utf8(&bytes).unwrap().lines();
+        let foo: Json = lines.next().unwrap().parse().unwrap();
+        assert_eq!(foo["tool_metadata"]["body"], Json::String("main".to_owned()));
+        let bar: Json = lines.next().unwrap().parse().unwrap();
+        assert!(bar.find("tool_metadata").is_none());
+    })
+}
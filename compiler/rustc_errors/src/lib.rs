@@ -88,6 +88,15 @@ fn new(json: Json) -> Self {
         ToolMetadata(Some(json))
     }
 
+    /// Builds a JSON object from the `tool_metadata` of a `Diagnostic`, if there is any.
+    fn from_pairs(pairs: &[(String, String)]) -> Self {
+        if pairs.is_empty() {
+            return ToolMetadata(None);
+        }
+        let object = pairs.iter().map(|(key, value)| (key.clone(), Json::String(value.clone())));
+        ToolMetadata::new(Json::Object(object.collect()))
+    }
+
     fn is_set(&self) -> bool {
         self.0.is_some()
     }
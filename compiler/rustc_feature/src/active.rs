@@ -663,6 +663,24 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows unnamed fields of struct and union type
     (active, unnamed_fields, "1.53.0", Some(49804), None),
 
+    /// Allows `#[doc(since = "...")]` to document the version an item was added in.
+    (active, doc_since, "1.54.0", None, None),
+
+    /// Allows `#[partial_ord(by_discriminant)]` to make `derive(PartialOrd)` on
+    /// enums only compare the discriminants.
+    (active, partial_ord_by_discriminant, "1.54.0", None, None),
+
+    /// Allows `#[partial_ord(skip)]` and `#[ord(skip)]` to leave fields out of
+    /// the comparisons of `derive(PartialOrd)` and `derive(Ord)`.
+    (active, derive_skip_fields, "1.54.0", None, None),
+
+    /// Allows derives to leave out the trait bound of type parameters which only appear in
+    /// skipped fields.
+    (active, derive_relaxed_bounds, "1.54.0", None, None),
+
+    /// Enables borrowck notes about moves out of values that may be pinned.
+    (active, unsafe_pinned, "1.54.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
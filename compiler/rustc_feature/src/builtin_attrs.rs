@@ -441,7 +441,11 @@ macro_rules! experimental {
     // Internal attributes, Macro related:
     // ==========================================================================
 
-    rustc_attr!(rustc_builtin_macro, AssumedUsed, template!(Word, NameValueStr: "name"), IMPL_DETAIL),
+    rustc_attr!(
+        rustc_builtin_macro, AssumedUsed,
+        template!(Word, List: "name, /*opt*/ attributes(name1, name2, ...)", NameValueStr: "name"),
+        IMPL_DETAIL,
+    ),
     rustc_attr!(rustc_proc_macro_decls, Normal, template!(Word), INTERNAL_UNSTABLE),
     rustc_attr!(
         rustc_macro_transparency, AssumedUsed,
@@ -544,6 +548,11 @@ macro_rules! experimental {
         rustc_main, Normal, template!(Word),
         "the `#[rustc_main]` attribute is used internally to specify test entry point function",
     ),
+    rustc_attr!(
+        rustc_copy_clone_shim, Normal, template!(Word),
+        "the `#[rustc_copy_clone_shim]` attribute is used internally by `#[derive(Clone)]` to \
+        replace the derived `clone` with a copy for monomorphizations that are `Copy`",
+    ),
     rustc_attr!(
         rustc_skip_array_during_method_dispatch, Normal, template!(Word),
         "the `#[rustc_skip_array_during_method_dispatch]` attribute is used to exclude a trait \
@@ -559,7 +568,16 @@ macro_rules! experimental {
     rustc_attr!(TEST, rustc_insignificant_dtor, Normal, template!(Word)),
     rustc_attr!(TEST, rustc_variance, Normal, template!(Word)),
     rustc_attr!(TEST, rustc_layout, Normal, template!(List: "field1, field2, ...")),
+    rustc_attr!(
+        TEST, rustc_clone_shim_expect, AssumedUsed,
+        template!(List: "memcpy, no_branches, no_calls")
+    ),
     rustc_attr!(TEST, rustc_regions, Normal, template!(Word)),
+    rustc_attr!(TEST, rustc_dump_move_paths, AssumedUsed, template!(Word)),
+    rustc_attr!(
+        TEST, rustc_ignore_borrowck_error, AssumedUsed,
+        template!(List: "E0XXX, E0YYY, ...")
+    ),
     rustc_attr!(
         TEST, rustc_error, AssumedUsed,
         template!(Word, List: "delay_span_bug_from_inside_query")
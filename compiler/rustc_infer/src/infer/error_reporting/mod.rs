@@ -2342,6 +2342,7 @@ fn report_sub_sup_conflict(
                     );
 
                     err.note_expected_found(&"", sup_expected, &"", sup_found);
+                    self.note_local_key_with_borrow(&mut err, &[sub_region, sup_region]);
                     err.emit();
                     return;
                 }
@@ -2359,9 +2360,30 @@ fn report_sub_sup_conflict(
         );
 
         self.note_region_origin(&mut err, &sub_origin);
+        self.note_local_key_with_borrow(&mut err, &[sub_region, sup_region]);
         err.emit();
     }
 
+    /// Explains why the value of a `thread_local!` can't be returned from the closure passed
+    /// to `LocalKey::with`, if one of `regions` is the region of its argument.
+    fn note_local_key_with_borrow(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        regions: &[Region<'tcx>],
+    ) {
+        let in_local_key_with = regions.iter().any(|region| match region {
+            ty::ReFree(free_region) => self.tcx.is_local_key_with_closure(free_region.scope),
+            _ => false,
+        });
+        if in_local_key_with {
+            err.note(
+                "a thread-local value is only borrowed until the closure passed to \
+                 `LocalKey::with` returns",
+            );
+            err.help("return a copy or a clone of the value from the closure instead");
+        }
+    }
+
     /// Determine whether an error associated with the given span and definition
     /// should be treated as being caused by the implicit `From` conversion
     /// within `?` desugaring.
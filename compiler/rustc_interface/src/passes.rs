@@ -25,7 +25,7 @@
 use rustc_mir as mir;
 use rustc_mir_build as mir_build;
 use rustc_parse::{parse_crate_from_file, parse_crate_from_source_str};
-use rustc_passes::{self, hir_stats, layout_test};
+use rustc_passes::{self, hir_stats, layout_test, move_paths_test};
 use rustc_plugin_impl as plugin;
 use rustc_query_impl::Queries as TcxQueries;
 use rustc_resolve::{Resolver, ResolverArenas};
@@ -886,6 +886,7 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
     });
 
     sess.time("layout_testing", || layout_test::test_layout(tcx));
+    sess.time("move_paths_testing", || move_paths_test::test_move_paths(tcx));
 
     // Avoid overwhelming user with errors if borrow checking failed.
     // I'm not sure how helpful this is, to be honest, but it avoids a
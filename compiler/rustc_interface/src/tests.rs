@@ -2,13 +2,15 @@
 
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
+use rustc_session::config::DumpMovePaths;
 use rustc_session::config::InstrumentCoverage;
+use rustc_session::config::MirPassOrder;
 use rustc_session::config::Strip;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc_session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
 use rustc_session::config::{
-    Externs, OutputType, OutputTypes, SymbolManglingVersion, WasiExecModel,
+    DeriveCmpStrategy, Externs, OutputType, OutputTypes, SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
@@ -142,6 +144,22 @@ fn test_can_print_warnings() {
     });
 }
 
+// Borrowck facts are written per function, so they don't need a single codegen unit
+#[test]
+fn test_borrowck_facts_keep_codegen_units() {
+    rustc_span::with_default_session_globals(|| {
+        let matches = optgroups().parse(&["--emit=borrowck-facts".to_string()]).unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert_eq!(sessopts.cli_forced_codegen_units, None);
+        assert!(!sessopts.cli_forced_thinlto_off);
+
+        let matches = optgroups().parse(&["--emit=mir".to_string()]).unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert_eq!(sessopts.cli_forced_codegen_units, Some(1));
+        assert!(sessopts.cli_forced_thinlto_off);
+    });
+}
+
 #[test]
 fn test_output_types_tracking_hash_different_paths() {
     let mut v1 = Options::default();
@@ -623,6 +641,7 @@ macro_rules! untracked {
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(dump_move_paths, Some(DumpMovePaths::Graphviz));
     untracked!(emit_future_incompat_report, true);
     untracked!(emit_stack_sizes, true);
     untracked!(hir_stats, true);
@@ -637,6 +656,7 @@ macro_rules! untracked {
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
     untracked!(meta_stats, true);
+    untracked!(mir_pass_order, Some(MirPassOrder::Dump));
     untracked!(nll_facts, true);
     untracked!(no_analysis, true);
     untracked!(no_interleave_lints, true);
@@ -647,8 +667,10 @@ macro_rules! untracked {
     // `pre_link_arg` is omitted because it just forwards to `pre_link_args`.
     untracked!(pre_link_args, vec![String::from("abc"), String::from("def")]);
     untracked!(print_link_args, true);
+    untracked!(print_generator_sizes, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
+    untracked!(print_theme_css_ast, true);
     untracked!(print_type_sizes, true);
     untracked!(proc_macro_backtrace, true);
     untracked!(query_dep_graph, true);
@@ -693,6 +715,7 @@ macro_rules! tracked {
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
+    tracked!(derive_cmp_strategy, DeriveCmpStrategy::Chain);
     tracked!(dual_proc_macros, true);
     tracked!(fewer_names, Some(true));
     tracked!(force_overflow_checks, Some(true));
@@ -742,6 +765,7 @@ macro_rules! tracked {
     tracked!(tune_cpu, Some(String::from("abc")));
     tracked!(tls_model, Some(TlsModel::GeneralDynamic));
     tracked!(trap_unreachable, Some(false));
+    tracked!(treat_borrowck_err_as_warn, Some(vec![String::from("E0502")]));
     tracked!(treat_err_as_bug, NonZeroUsize::new(1));
     tracked!(unleash_the_miri_inside_of_you, true);
     tracked!(use_ctors_section, Some(true));
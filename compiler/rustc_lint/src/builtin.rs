@@ -3080,3 +3080,230 @@ fn is_zero(expr: &hir::Expr<'_>) -> bool {
         }
     }
 }
+
+declare_lint! {
+    /// The `inconsistent_ord_impls` lint detects types that derive one of
+    /// [`PartialOrd`] and [`Ord`] but implement the other one by hand.
+    ///
+    /// [`PartialOrd`]: https://doc.rust-lang.org/std/cmp/trait.PartialOrd.html
+    /// [`Ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    ///
+    /// #[derive(PartialEq, Eq, PartialOrd)]
+    /// struct Version {
+    ///     major: u32,
+    ///     minor: u32,
+    /// }
+    ///
+    /// impl Ord for Version {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         self.minor.cmp(&other.minor)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The two traits are expected to agree: `a.partial_cmp(&b)` should always
+    /// be `Some(a.cmp(&b))`. A derived implementation compares the fields in
+    /// declaration order, so a hand-written implementation of the other trait
+    /// is likely to order values differently, which confuses sorting and
+    /// ordered collections. Hand-written implementations which defer to the
+    /// derived one, like `Some(self.cmp(other))` in `partial_cmp` or
+    /// `self.partial_cmp(other).unwrap()` in `cmp`, are not linted, as they
+    /// agree with it by construction.
+    pub INCONSISTENT_ORD_IMPLS,
+    Warn,
+    "detects types deriving one of `PartialOrd` and `Ord` and implementing the other by hand"
+}
+
+declare_lint_pass!(InconsistentOrdImpls => [INCONSISTENT_ORD_IMPLS]);
+
+impl<'tcx> LateLintPass<'tcx> for InconsistentOrdImpls {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let impl_ = match item.kind {
+            hir::ItemKind::Impl(ref impl_) => impl_,
+            _ => return,
+        };
+        let trait_def_id = match impl_.of_trait.as_ref().and_then(|t| t.trait_def_id()) {
+            Some(def_id) => def_id,
+            None => return,
+        };
+        let tcx = cx.tcx;
+        let (partial_ord, ord) =
+            match (tcx.lang_items().partial_ord_trait(), tcx.get_diagnostic_item(sym::Ord)) {
+                (Some(partial_ord), Some(ord)) => (partial_ord, ord),
+                _ => return,
+            };
+        // The derived impls are the ones tagged by the deriving framework.
+        if tcx.has_attr(item.def_id.to_def_id(), sym::automatically_derived) {
+            return;
+        }
+        let (other_trait, derived, by_hand) = if trait_def_id == ord {
+            if defers_to(tcx, impl_, sym::cmp, sym::partial_cmp) {
+                return;
+            }
+            (partial_ord, "PartialOrd", "Ord")
+        } else if trait_def_id == partial_ord {
+            if defers_to(tcx, impl_, sym::partial_cmp, sym::cmp) {
+                return;
+            }
+            (ord, "Ord", "PartialOrd")
+        } else {
+            return;
+        };
+
+        let self_ty = tcx.type_of(item.def_id);
+        let adt_def = match self_ty.ty_adt_def() {
+            Some(adt_def) => adt_def,
+            None => return,
+        };
+        let mut derived_impl = None;
+        tcx.for_each_relevant_impl(other_trait, self_ty, |impl_def_id| {
+            if impl_def_id.is_local()
+                && tcx.type_of(impl_def_id).ty_adt_def().map(|def| def.did) == Some(adt_def.did)
+                && tcx.has_attr(impl_def_id, sym::automatically_derived)
+            {
+                derived_impl = Some(impl_def_id);
+            }
+        });
+        let derived_impl = match derived_impl {
+            Some(derived_impl) => derived_impl,
+            None => return,
+        };
+
+        let span = tcx.def_span(item.def_id);
+        cx.struct_span_lint(INCONSISTENT_ORD_IMPLS, span, |lint| {
+            let mut err = lint.build(&format!(
+                "`{}` is implemented by hand, but `{}` is derived",
+                by_hand, derived,
+            ));
+            err.span_note(
+                tcx.def_span(derived_impl).source_callsite(),
+                &format!(
+                    "`{}` is derived here, comparing the fields in declaration order",
+                    derived
+                ),
+            );
+            let help = if by_hand == "Ord" {
+                "implement `PartialOrd` by hand as well, returning `Some(self.cmp(other))`"
+            } else {
+                "implement `partial_cmp` as `Some(self.cmp(other))`, or implement `Ord` by hand \
+                 as well"
+            };
+            err.help(help);
+            err.emit();
+        });
+    }
+}
+
+/// Returns whether `method` of a hand-written impl calls `callee` with its own
+/// `self` as the receiver and its own `other` as the argument, like
+/// `Some(self.cmp(other))` or `self.partial_cmp(other).unwrap()`, in which case
+/// it agrees with the derived implementation of the other trait.
+fn defers_to<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_: &hir::Impl<'tcx>,
+    method: Symbol,
+    callee: Symbol,
+) -> bool {
+    use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+
+    struct CallFinder {
+        callee: Symbol,
+        self_param: HirId,
+        other_param: HirId,
+        found: bool,
+    }
+
+    impl CallFinder {
+        fn is_self_then_other(&self, args: &[hir::Expr<'_>]) -> bool {
+            match args {
+                [receiver, arg] => {
+                    is_param(receiver, self.self_param) && is_param(arg, self.other_param)
+                }
+                _ => false,
+            }
+        }
+    }
+
+    impl<'v> Visitor<'v> for CallFinder {
+        type Map = intravisit::ErasedMap<'v>;
+
+        fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+            NestedVisitorMap::None
+        }
+
+        fn visit_expr(&mut self, expr: &'v hir::Expr<'v>) {
+            match expr.kind {
+                hir::ExprKind::MethodCall(segment, _, args, _)
+                    if segment.ident.name == self.callee && self.is_self_then_other(args) =>
+                {
+                    self.found = true;
+                }
+                hir::ExprKind::Call(hir::Expr { kind: hir::ExprKind::Path(qpath), .. }, args)
+                    if qpath_ends_with(qpath, self.callee) && self.is_self_then_other(args) =>
+                {
+                    self.found = true;
+                }
+                _ => intravisit::walk_expr(self, expr),
+            }
+        }
+    }
+
+    fn qpath_ends_with(qpath: &hir::QPath<'_>, name: Symbol) -> bool {
+        match qpath {
+            hir::QPath::Resolved(_, path) => {
+                path.segments.last().map_or(false, |segment| segment.ident.name == name)
+            }
+            hir::QPath::TypeRelative(_, segment) => segment.ident.name == name,
+            hir::QPath::LangItem(..) => false,
+        }
+    }
+
+    /// Whether `expr` is the parameter `param`, possibly behind `&` or `*`.
+    fn is_param(mut expr: &hir::Expr<'_>, param: HirId) -> bool {
+        loop {
+            match expr.kind {
+                hir::ExprKind::AddrOf(_, _, inner)
+                | hir::ExprKind::Unary(hir::UnOp::Deref, inner) => expr = inner,
+                hir::ExprKind::Path(hir::QPath::Resolved(None, path)) => {
+                    return path.res == Res::Local(param);
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    for item_ref in impl_.items {
+        if item_ref.ident.name != method {
+            continue;
+        }
+        let body_id = match tcx.hir().impl_item(item_ref.id).kind {
+            hir::ImplItemKind::Fn(_, body_id) => body_id,
+            _ => continue,
+        };
+        let body = tcx.hir().body(body_id);
+        let (self_param, other_param) = match body.params {
+            [self_param, other_param] => match (&self_param.pat.kind, &other_param.pat.kind) {
+                (PatKind::Binding(_, self_param, ..), PatKind::Binding(_, other_param, ..)) => {
+                    (*self_param, *other_param)
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let mut finder = CallFinder { callee, self_param, other_param, found: false };
+        finder.visit_body(body);
+        if finder.found {
+            return true;
+        }
+    }
+    false
+}
@@ -723,6 +723,10 @@ fn lookup_with_diagnostics(
                 BuiltinLintDiagnostics::OrPatternsBackCompat(span,suggestion) => {
                     db.span_suggestion(span, "use pat_param to preserve semantics", suggestion, Applicability::MachineApplicable);
                 }
+                BuiltinLintDiagnostics::PartialOrdFieldOrder(fields) => {
+                    let fields = fields.iter().map(|field| format!("`{}`", field)).collect::<Vec<_>>();
+                    db.note(&format!("fields are compared in declaration order: {}", fields.join(", ")));
+                }
             }
             // Rewrap `db`, and pass control to the user.
             decorate(LintDiagnosticBuilder::new(db));
@@ -204,6 +204,7 @@ macro_rules! late_lint_mod_passes {
                 ExplicitOutlivesRequirements: ExplicitOutlivesRequirements,
                 InvalidValue: InvalidValue,
                 DerefNullPtr: DerefNullPtr,
+                InconsistentOrdImpls: InconsistentOrdImpls,
             ]
         );
     };
@@ -3001,6 +3001,8 @@
         PROC_MACRO_BACK_COMPAT,
         OR_PATTERNS_BACK_COMPAT,
         LARGE_ASSIGNMENTS,
+        PARTIAL_ORD_FIELD_ORDER,
+        UNCHECKED_INTRINSIC_UB,
     ]
 }
 
@@ -3240,3 +3242,64 @@
     Allow,
     "detects usage of old versions of or-patterns",
 }
+
+declare_lint! {
+    /// The `partial_ord_field_order` lint detects `#[derive(PartialOrd)]` on structs
+    /// whose ordering is decided by a string field before the fields following it.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![deny(partial_ord_field_order)]
+    ///
+    /// #[derive(PartialEq, PartialOrd)]
+    /// struct Release {
+    ///     version: String,
+    ///     major: u32,
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The derived implementation compares the fields in the order they are
+    /// declared, and stops at the first field which differs. Strings are
+    /// compared lexicographically, so `"10"` is less than `"9"`, and the fields
+    /// after a string field are only compared when the strings are equal. This
+    /// is rarely the intended order for fields like version numbers, but it is
+    /// fine for many other structs, so this lint is "allow" by default.
+    pub PARTIAL_ORD_FIELD_ORDER,
+    Allow,
+    "detects derived orderings decided by a string field before other fields"
+}
+
+declare_lint! {
+    /// The `unchecked_intrinsic_ub` lint detects calls to the `unchecked_*`
+    /// arithmetic intrinsics, and to the methods of the integer types wrapping
+    /// them, with arguments that are known at compile time to violate their
+    /// preconditions.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// #![feature(core_intrinsics)]
+    /// fn main() {
+    ///     unsafe { std::intrinsics::unchecked_shl(1_u32, 32) };
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Intrinsics such as `unchecked_add` and `unchecked_shl` require their
+    /// arguments not to overflow, and the result of calling them with such
+    /// arguments is undefined behavior. When the arguments of a call in a
+    /// function body can be determined during constant propagation, the call
+    /// is reported here instead of being left to cause undefined behavior at
+    /// runtime.
+    pub UNCHECKED_INTRINSIC_UB,
+    Deny,
+    "calls to unchecked arithmetic intrinsics which are known to have undefined behavior"
+}
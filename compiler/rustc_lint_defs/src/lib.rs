@@ -272,6 +272,7 @@ pub enum BuiltinLintDiagnostics {
     ExternDepSpec(String, ExternDepSpec),
     ProcMacroBackCompat(String),
     OrPatternsBackCompat(Span, String),
+    PartialOrdFieldOrder(Vec<String>),
 }
 
 /// Lints that are buffered up early on in the `Session` before the
@@ -1,13 +1,14 @@
 use super::{AllocId, ConstAlloc, Pointer, Scalar};
 
 use crate::mir::interpret::ConstValue;
-use crate::ty::{layout, query::TyCtxtAt, tls, FnSig, Ty};
+use crate::ty::{layout, query::TyCtxtAt, tls, FnSig, ScalarInt, Ty};
 
 use rustc_data_structures::sync::Lock;
 use rustc_errors::{pluralize, struct_span_err, DiagnosticBuilder, ErrorReported};
 use rustc_macros::HashStable;
 use rustc_session::CtfeBacktrace;
 use rustc_span::def_id::DefId;
+use rustc_span::Symbol;
 use rustc_target::abi::{Align, Size};
 use std::{any::Any, backtrace::Backtrace, fmt};
 
@@ -272,6 +273,13 @@ pub enum UndefinedBehaviorInfo<'tcx> {
         target_size: u64,
         data_size: u64,
     },
+    /// An `unchecked_shl` or `unchecked_shr` by a negative amount, or by at least the width
+    /// of the shifted type.
+    UncheckedShiftOverflow {
+        intrinsic: Symbol,
+        ty: Ty<'tcx>,
+        shift: ScalarInt,
+    },
 }
 
 impl fmt::Display for UndefinedBehaviorInfo<'_> {
@@ -358,6 +366,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 "scalar size mismatch: expected {} bytes but got {} bytes instead",
                 target_size, data_size
             ),
+            UncheckedShiftOverflow { intrinsic, ty, shift } => {
+                let size = shift.size();
+                let bits = shift.assert_bits(size);
+                if ty.is_signed() {
+                    let shift = size.sign_extend(bits) as i128;
+                    write!(f, "overflowing shift by {} in `{}`", shift, intrinsic)
+                } else {
+                    write!(f, "overflowing shift by {} in `{}`", bits, intrinsic)
+                }
+            }
         }
     }
 }
@@ -2837,7 +2837,8 @@ fn predecessors(&self, node: Self::Node) -> <Self as graph::GraphPredecessors<'_
 /// `Location` represents the position of the start of the statement; or, if
 /// `statement_index` equals the number of statements, then the start of the
 /// terminator.
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, HashStable)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(TyEncodable, TyDecodable, HashStable)]
 pub struct Location {
     /// The block that the location is within.
     pub block: BasicBlock,
@@ -15,7 +15,7 @@
 use std::cell::Cell;
 use std::fmt::{self, Debug};
 
-use super::{Field, SourceInfo};
+use super::{Field, Local, Location, Place, SourceInfo};
 
 #[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
 pub enum UnsafetyViolationKind {
@@ -213,6 +213,42 @@ pub struct BorrowCheckResult<'tcx> {
     pub concrete_opaque_types: FxHashMap<DefId, ty::ResolvedOpaqueTy<'tcx>>,
     pub closure_requirements: Option<ClosureRegionRequirements<'tcx>>,
     pub used_mut_upvars: SmallVec<[Field; 8]>,
+    /// The move paths borrowck tracked, as exposed by the `mir_move_paths` query.
+    pub move_paths: MovePathsSummary<'tcx>,
+}
+
+rustc_index::newtype_index! {
+    /// Identifies a move path within a [`MovePathsSummary`].
+    pub struct MovePathId {
+        derive [HashStable]
+        DEBUG_FORMAT = "mp{}"
+    }
+}
+
+/// The result of the `mir_move_paths` query: the places whose initialization
+/// state borrowck tracks in a body, along with where they are moved out of and
+/// (re)initialized. This lets lints reason about moves without re-deriving them.
+#[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct MovePathsSummary<'tcx> {
+    pub paths: IndexVec<MovePathId, MovePathSummary<'tcx>>,
+}
+
+#[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct MovePathSummary<'tcx> {
+    pub place: Place<'tcx>,
+    /// The path of the place this one is a projection of, if any.
+    pub parent: Option<MovePathId>,
+    /// Locations that move out of this path.
+    pub moves: Vec<Location>,
+    /// Sites that initialize this path.
+    pub inits: Vec<InitSite>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum InitSite {
+    /// The path is a function argument, initialized on entry.
+    Argument(Local),
+    Statement(Location),
 }
 
 /// The result of the `mir_const_qualif` query.
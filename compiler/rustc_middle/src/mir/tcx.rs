@@ -135,6 +135,29 @@ pub fn ty<D>(&self, local_decls: &D, tcx: TyCtxt<'tcx>) -> PlaceTy<'tcx>
     {
         Place::ty_from(self.local, &self.projection, local_decls, tcx)
     }
+
+    /// Like `iter_projections`, but also yields the type of the base place of each projection.
+    /// Given the place `a.b.c`, this would yield:
+    /// - (a, .b, type of a)
+    /// - (a.b, .c, type of a.b)
+    ///
+    /// The types are computed incrementally, which avoids the quadratic cost of calling
+    /// `Place::ty_from` on each base.
+    pub fn iter_projections_with_ty<D>(
+        self,
+        local_decls: &D,
+        tcx: TyCtxt<'tcx>,
+    ) -> impl Iterator<Item = (PlaceRef<'tcx>, PlaceElem<'tcx>, PlaceTy<'tcx>)>
+    where
+        D: HasLocalDecls<'tcx>,
+    {
+        let mut place_ty = PlaceTy::from_ty(local_decls.local_decls()[self.local].ty);
+        self.iter_projections().map(move |(base, elem)| {
+            let base_ty = place_ty;
+            place_ty = base_ty.projection_ty(tcx, elem);
+            (base, elem, base_ty)
+        })
+    }
 }
 
 impl<'tcx> PlaceRef<'tcx> {
@@ -257,6 +257,12 @@
         desc { |tcx| "building MIR for `{}`", tcx.def_path_str(key.did.to_def_id()) }
     }
 
+    /// The names of the MIR passes that `#[rustc_mir(skip_pass = "...")]` keeps from running
+    /// on the MIR of `key`.
+    query skipped_mir_passes(key: LocalDefId) -> &'tcx [Symbol] {
+        desc { |tcx| "finding the MIR passes skipped for `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
+
     /// Fetch the MIR for a given `DefId` up till the point where it is
     /// ready for const qualification.
     ///
@@ -757,6 +763,13 @@
         }
     }
 
+    /// The move paths that borrowck tracks in the function body, with the
+    /// locations that move out of and initialize each of them. They are taken
+    /// from the result of `mir_borrowck`.
+    query mir_move_paths(key: LocalDefId) -> &'tcx mir::MovePathsSummary<'tcx> {
+        desc { |tcx| "computing the move paths of `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
+
     /// Gets a complete map from all types to their inherent impls.
     /// Not meant to be used directly outside of coherence.
     query crate_inherent_impls(k: ()) -> CrateInherentImpls {
@@ -13,6 +13,7 @@
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
 use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{GeneratorSizeInfo, SavedLocalInfo, SuspendPointInfo};
 use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::call::{
@@ -1707,6 +1708,54 @@ fn record_layout_for_printing(&self, layout: TyAndLayout<'tcx>) {
         if self.tcx.sess.opts.debugging_opts.print_type_sizes {
             self.record_layout_for_printing_outlined(layout)
         }
+        // Likewise for generators with `-Zprint-generator-sizes`.
+        if self.tcx.sess.opts.debugging_opts.print_generator_sizes {
+            self.record_generator_layout_for_printing(layout)
+        }
+    }
+
+    fn record_generator_layout_for_printing(&self, layout: TyAndLayout<'tcx>) {
+        if layout.ty.has_param_types_or_consts() || !self.param_env.caller_bounds().is_empty() {
+            return;
+        }
+        let (def_id, substs) = match *layout.ty.kind() {
+            ty::Generator(def_id, substs, _) => (def_id, substs),
+            _ => return,
+        };
+
+        let tcx = self.tcx;
+        let (info, local_names) = tcx.generator_layout_and_saved_local_names(def_id);
+        let source_map = tcx.sess.source_map();
+        let saved_local_info = |local: GeneratorSavedLocal| {
+            let ty = info.field_tys[local].subst(tcx, substs);
+            let size = self.layout_of(ty).ok()?.size;
+            Some(SavedLocalInfo {
+                name: local_names[local].map(|name| name.to_string()),
+                ty: ty.to_string(),
+                size: size.bytes(),
+            })
+        };
+        let suspend_points = info
+            .variant_fields
+            .iter_enumerated()
+            .skip(ty::GeneratorSubsts::RESERVED_VARIANTS)
+            .map(|(variant_index, fields)| SuspendPointInfo {
+                location: source_map
+                    .span_to_diagnostic_string(info.variant_source_info[variant_index].span),
+                size: layout.for_variant(self, variant_index).size.bytes(),
+                locals: fields.iter().filter_map(|&local| saved_local_info(local)).collect(),
+            })
+            .collect();
+        let kind =
+            tcx.generator_kind(def_id).map_or("generator".to_string(), |kind| kind.to_string());
+
+        tcx.sess.code_stats.record_generator_size(GeneratorSizeInfo {
+            description: tcx.def_path_str(def_id),
+            kind,
+            align: layout.align.abi.bytes(),
+            overall_size: layout.size.bytes(),
+            suspend_points,
+        });
     }
 
     fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
@@ -585,6 +585,9 @@ impl<'tcx> GeneratorSubsts<'tcx> {
     pub const RETURNED: usize = 1;
     /// Generator has been poisoned.
     pub const POISONED: usize = 2;
+    /// Number of variants used by the states above. The variants of the
+    /// suspension points come after them.
+    pub const RESERVED_VARIANTS: usize = 3;
 
     const UNRESUMED_NAME: &'static str = "Unresumed";
     const RETURNED_NAME: &'static str = "Returned";
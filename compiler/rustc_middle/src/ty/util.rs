@@ -15,9 +15,10 @@
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_errors::ErrorReported;
 use rustc_hir as hir;
-use rustc_hir::def::DefKind;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_macros::HashStable;
+use rustc_span::symbol::sym;
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::{Integer, Size, TargetDataLayout};
 use smallvec::SmallVec;
@@ -436,6 +437,34 @@ pub fn is_closure(self, def_id: DefId) -> bool {
         matches!(self.def_kind(def_id), DefKind::Closure | DefKind::Generator)
     }
 
+    /// Returns `true` if `def_id` refers to a closure passed to `LocalKey::with`, as in
+    /// `FOO.with(|foo| ...)` for a `thread_local!` named `FOO`. Its argument borrows the
+    /// thread-local value, so it cannot escape the closure.
+    pub fn is_local_key_with_closure(self, def_id: DefId) -> bool {
+        let local_def_id = match def_id.as_local() {
+            Some(local_def_id) if self.is_closure(def_id) => local_def_id,
+            _ => return false,
+        };
+        let hir_id = self.hir().local_def_id_to_hir_id(local_def_id);
+        let receiver = match self.hir().get(self.hir().get_parent_node(hir_id)) {
+            hir::Node::Expr(hir::Expr {
+                kind: hir::ExprKind::MethodCall(segment, _, [receiver, _], _),
+                ..
+            }) if segment.ident.name == sym::with => receiver,
+            _ => return false,
+        };
+        match receiver.kind {
+            hir::ExprKind::Path(hir::QPath::Resolved(
+                None,
+                hir::Path { res: Res::Def(DefKind::Const, const_def_id), .. },
+            )) => match self.type_of(*const_def_id).kind() {
+                ty::Adt(adt_def, _) => self.is_diagnostic_item(sym::LocalKey, adt_def.did),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
     /// Returns `true` if `def_id` refers to a trait (i.e., `trait Foo { ... }`).
     pub fn is_trait(self, def_id: DefId) -> bool {
         self.def_kind(def_id) == DefKind::Trait
@@ -1,22 +1,30 @@
 use either::Either;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{Applicability, DiagnosticBuilder};
+use rustc_errors::{pluralize, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::{AsyncGeneratorKind, GeneratorKind};
+use rustc_middle::hir::map::Map;
+use rustc_middle::hir::place::Place as HirPlace;
+use rustc_middle::lint::in_external_macro;
 use rustc_middle::mir::{
-    self, AggregateKind, BindingForm, BorrowKind, ClearCrossCrate, ConstraintCategory,
-    FakeReadCause, LocalDecl, LocalInfo, LocalKind, Location, Operand, Place, PlaceRef,
+    self, AggregateKind, BasicBlock, BindingForm, BorrowKind, ClearCrossCrate, ConstraintCategory,
+    FakeReadCause, Local, LocalDecl, LocalInfo, LocalKind, Location, Operand, Place, PlaceRef,
     ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind, VarBindingForm,
 };
-use rustc_middle::ty::{self, suggest_constraining_type_param, Ty, TypeFoldable};
+use rustc_middle::ty::{self, suggest_constraining_type_param, Ty, TyCtxt, TypeFoldable};
 use rustc_span::source_map::DesugaringKind;
-use rustc_span::symbol::sym;
-use rustc_span::{Span, DUMMY_SP};
+use rustc_span::symbol::{sym, Symbol};
+use rustc_span::{BytePos, Span, DUMMY_SP};
+use std::iter;
+use std::mem;
 
 use crate::dataflow::drop_flag_effects;
 use crate::dataflow::indexes::{MoveOutIndex, MovePathIndex};
 use crate::util::borrowck_errors;
+use crate::util::mutable_uses::Writes;
 
 use crate::borrow_check::{
     borrow_set::BorrowData, diagnostics::Instance, prefixes::IsPrefixOf,
@@ -24,8 +32,9 @@
 };
 
 use super::{
-    explain_borrow::BorrowExplanation, FnSelfUseKind, IncludingDowncast, RegionName,
-    RegionNameSource, UseSpans,
+    explain_borrow::{BorrowExplanation, LaterUseKind},
+    BorrowedContentSource, FnSelfUseKind, IncludingDowncast, RegionName, RegionNameSource,
+    UseSpans,
 };
 
 #[derive(Debug)]
@@ -47,6 +56,108 @@ enum StorageDeadOrDrop<'tcx> {
     Destructor(Ty<'tcx>),
 }
 
+/// The places involved in a conflict between two borrows, as described in diagnostics.
+struct ConflictingPlaces {
+    /// The place both borrows conflict on.
+    desc: String,
+    /// The place of the new borrow, if it isn't `desc` itself.
+    new_via: String,
+    /// The place of the existing borrow, if it isn't `desc` itself.
+    old_via: String,
+    /// The union whose fields are borrowed, if the borrows are of different fields of it.
+    union_type_name: Option<String>,
+}
+
+/// The kinds of borrows told apart when reporting conflicting borrows.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ConflictingBorrowKind {
+    Shared,
+    Shallow,
+    Unique,
+    Mut,
+}
+
+impl From<BorrowKind> for ConflictingBorrowKind {
+    fn from(kind: BorrowKind) -> Self {
+        match kind {
+            BorrowKind::Shared => ConflictingBorrowKind::Shared,
+            BorrowKind::Shallow => ConflictingBorrowKind::Shallow,
+            BorrowKind::Unique => ConflictingBorrowKind::Unique,
+            BorrowKind::Mut { .. } => ConflictingBorrowKind::Mut,
+        }
+    }
+}
+
+/// The error reported for a new borrow which conflicts with an existing one.
+#[derive(Copy, Clone, Debug)]
+enum ConflictingBorrow {
+    /// E0502, naming the mutability of both borrows.
+    AlreadyBorrowed { kind_new: &'static str, kind_old: &'static str },
+    /// E0510 if the existing borrow keeps a matched place immutable in a match guard, E0502
+    /// otherwise.
+    MutatedInImmutableSection,
+    /// E0499.
+    MutablyBorrowedMultiply,
+    /// E0524.
+    UniquelyBorrowedByTwoClosures,
+    /// E0500.
+    UniquelyBorrowedByOneClosure,
+    /// E0501, naming the mutability of the new borrow.
+    AlreadyUniquelyBorrowed { kind_new: &'static str },
+}
+
+/// For each kind of a new borrow and kind of an existing borrow that conflict, the error reported
+/// and how the existing borrow is referred to when explaining why it is still live. Shared
+/// borrows don't conflict with each other, and shallow borrows are never the new borrow.
+const CONFLICTING_BORROWS: &[(
+    ConflictingBorrowKind,
+    ConflictingBorrowKind,
+    ConflictingBorrow,
+    &str,
+)] = {
+    use ConflictingBorrow::*;
+    use ConflictingBorrowKind::*;
+    &[
+        (Shared, Mut, AlreadyBorrowed { kind_new: "immutable", kind_old: "mutable" }, "mutable "),
+        (Shared, Unique, AlreadyUniquelyBorrowed { kind_new: "immutable" }, "first "),
+        (Mut, Shared, AlreadyBorrowed { kind_new: "mutable", kind_old: "immutable" }, "immutable "),
+        (Mut, Shallow, MutatedInImmutableSection, "immutable "),
+        (Mut, Mut, MutablyBorrowedMultiply, "first "),
+        (Mut, Unique, AlreadyUniquelyBorrowed { kind_new: "mutable" }, "first "),
+        (Unique, Shared, UniquelyBorrowedByOneClosure, "first "),
+        (Unique, Shallow, MutatedInImmutableSection, "immutable "),
+        (Unique, Mut, UniquelyBorrowedByOneClosure, "first "),
+        (Unique, Unique, UniquelyBorrowedByTwoClosures, "first "),
+    ]
+};
+
+impl ConflictingBorrow {
+    /// Looks up how a new borrow of kind `new` conflicting with an existing borrow of kind `old`
+    /// is reported, along with how the existing borrow is referred to.
+    fn classify(new: BorrowKind, old: BorrowKind) -> Option<(Self, &'static str)> {
+        let (new, old) = (ConflictingBorrowKind::from(new), ConflictingBorrowKind::from(old));
+        CONFLICTING_BORROWS
+            .iter()
+            .find(|&&(new_kind, old_kind, ..)| new_kind == new && old_kind == old)
+            .map(|&(_, _, conflict, first_borrow_desc)| (conflict, first_borrow_desc))
+    }
+}
+
+fn predecessor_locations(
+    body: &'a mir::Body<'tcx>,
+    location: Location,
+) -> impl Iterator<Item = Location> + 'a {
+    if location.statement_index == 0 {
+        let predecessors = body.predecessors()[location.block].to_vec();
+        Either::Left(predecessors.into_iter().map(move |bb| body.terminator_loc(bb)))
+    } else {
+        Either::Right(std::iter::once(Location {
+            statement_index: location.statement_index - 1,
+            ..location
+        }))
+    }
+}
+
 impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
     pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
         &mut self,
@@ -97,10 +208,16 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                     .unwrap_or_else(|| "_".to_owned()),
             );
             err.span_label(span, format!("use of possibly-uninitialized {}", item_msg));
+            self.label_branches_skipping_init(&mut err, location, mpi, &item_msg);
+            self.add_error_metadata(&mut err, &[("move_path", self.move_path_key(mpi))]);
 
             use_spans.var_span_label_path_only(
                 &mut err,
-                format!("{} occurs due to use{}", desired_action.as_noun(), use_spans.describe()),
+                format!(
+                    "{} occurs due to use{}",
+                    desired_action.as_noun(),
+                    use_spans.describe_captured()
+                ),
             );
 
             err.buffer(&mut self.errors_buffer);
@@ -112,6 +229,15 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                          mois={:?}",
                         move_out_indices
                     );
+                    // Point out this use in the error about the same moves instead, so that
+                    // all the uses after the move can be fixed at once.
+                    let (_, err) = self.move_error_reported.get_mut(&move_out_indices).unwrap();
+                    if !err.span.span_labels().iter().any(|label| label.span == span) {
+                        err.span_label(
+                            span,
+                            format!("value also {} here", desired_action.as_verb_in_past_tense()),
+                        );
+                    }
                     return;
                 }
             }
@@ -131,26 +257,69 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
             let partial_str = if is_partial_move { "partial " } else { "" };
             let partially_str = if is_partial_move { "partially " } else { "" };
 
-            let mut err = self.cannot_act_on_moved_value(
-                span,
-                desired_action.as_noun(),
-                partially_str,
-                self.describe_place_with_options(moved_place, IncludingDowncast(true)),
-            );
+            // A closure called again after a previous call moved it gets an error of its own,
+            // pointing at what makes it `FnOnce`.
+            let moved_by_calls = move_site_vec.iter().all(|move_site| {
+                let move_out = self.move_data.moves[move_site.moi];
+                let moved_place = &self.move_data.move_paths[move_out.path].place;
+                matches!(
+                    self.move_spans(moved_place.as_ref(), move_out.source),
+                    UseSpans::FnSelfUse { kind: FnSelfUseKind::FnOnceCall, .. }
+                )
+            });
+            let called_closure = if moved_by_calls && !is_partial_move {
+                self.invoked_fn_once_closure(location, used_place)
+                    .and_then(|closure| self.closure_kind_origin(closure))
+            } else {
+                None
+            };
 
-            self.add_moved_or_invoked_closure_note(location, used_place, &mut err);
+            let mut err = match called_closure {
+                Some((closure_def_id, ..)) => {
+                    let mut err = self.cannot_call_fn_once_closure_again(
+                        span,
+                        self.describe_place_with_options(moved_place, IncludingDowncast(true)),
+                    );
+                    let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(closure_def_id);
+                    if let hir::ExprKind::Closure(_, _, _, fn_decl_span, _) =
+                        self.infcx.tcx.hir().expect_expr(hir_id).kind
+                    {
+                        err.span_label(fn_decl_span, "closure defined here");
+                    }
+                    err
+                }
+                None => self.cannot_act_on_moved_value(
+                    span,
+                    desired_action.as_noun(),
+                    partially_str,
+                    self.describe_place_with_options(moved_place, IncludingDowncast(true)),
+                ),
+            };
+
+            let closure_note_added =
+                self.add_moved_or_invoked_closure_note(location, used_place, &mut err);
+            self.add_error_metadata(&mut err, &[("move_path", self.move_path_key(mpi))]);
 
             let mut is_loop_move = false;
             let mut in_pattern = false;
+            let mut while_let_scrutinee = None;
 
             for move_site in &move_site_vec {
                 let move_out = self.move_data.moves[(*move_site).moi];
                 let moved_place = &self.move_data.move_paths[move_out.path].place;
 
                 let move_spans = self.move_spans(moved_place.as_ref(), move_out.source);
-                let move_span = move_spans.args_or_use();
-
-                let move_msg = if move_spans.for_closure() { " into closure" } else { "" };
+                let (move_span, move_msg) = match move_spans {
+                    // Point at the adapter, the closure arguments alone do not
+                    // tell which step of an iterator chain the value moved into.
+                    UseSpans::IteratorChainUse { adapter, adapter_span, .. } => {
+                        (adapter_span, format!(" into `{}` closure", adapter))
+                    }
+                    _ if move_spans.for_closure() => {
+                        (move_spans.args_or_use(), " into closure".to_string())
+                    }
+                    _ => (move_spans.args_or_use(), String::new()),
+                };
 
                 let loop_message = if location == move_out.source || move_site.traversed_back_edge {
                     ", in previous iteration of loop"
@@ -162,6 +331,23 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                     is_loop_move = true;
                 }
 
+                if !loop_message.is_empty() && while_let_scrutinee.is_none() {
+                    if let Some(LoopMoveKind::WhileLetScrutinee(scrutinee_span)) =
+                        self.classify_loop_move(move_span)
+                    {
+                        let suggestion = match move_spans {
+                            UseSpans::FnSelfUse {
+                                var_span,
+                                kind: FnSelfUseKind::Normal { implicit_into_iter: false, .. },
+                                ..
+                            } => Some((var_span, true)),
+                            UseSpans::OtherUse(span) => Some((span, false)),
+                            _ => None,
+                        };
+                        while_let_scrutinee = Some((scrutinee_span, *moved_place, suggestion));
+                    }
+                }
+
                 if let UseSpans::FnSelfUse { var_span, fn_call_span, fn_span, kind } = move_spans {
                     let place_name = self
                         .describe_place(moved_place.as_ref())
@@ -196,6 +382,29 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                                 );
                             }
                         }
+                        FnSelfUseKind::TryOperator { is_option } => {
+                            let question_mark_span =
+                                self.infcx.tcx.sess.source_map().end_point(fn_call_span);
+                            err.span_label(
+                                question_mark_span,
+                                &format!(
+                                    "{} {}moved due to the `?` operator{}",
+                                    place_name, partially_str, loop_message
+                                ),
+                            );
+                            err.note(
+                                "value moved by the `?` operator, which takes ownership of the \
+                                 result",
+                            );
+                            if is_option {
+                                err.span_suggestion_verbose(
+                                    question_mark_span.shrink_to_lo(),
+                                    "consider calling `.as_ref()` to borrow the contents instead",
+                                    ".as_ref()".to_string(),
+                                    Applicability::MaybeIncorrect,
+                                );
+                            }
+                        }
                         FnSelfUseKind::Normal {
                             self_arg,
                             implicit_into_iter,
@@ -252,7 +461,7 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
                             format!(
                                 "variable {}moved due to use{}",
                                 partially_str,
-                                move_spans.describe()
+                                move_spans.describe_captured()
                             ),
                             "moved",
                         );
@@ -306,31 +515,113 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
 
             use_spans.var_span_label_path_only(
                 &mut err,
-                format!("{} occurs due to use{}", desired_action.as_noun(), use_spans.describe()),
+                format!(
+                    "{} occurs due to use{}",
+                    desired_action.as_noun(),
+                    use_spans.describe_captured()
+                ),
             );
 
+            if let Some((scrutinee_span, moved_place, suggestion)) = while_let_scrutinee {
+                err.span_note(
+                    scrutinee_span,
+                    "the scrutinee of a `while let` loop is evaluated again on every iteration",
+                );
+                // Only suggest changing how a local which is moved as a whole is used, anything
+                // else is unlikely to be fixed by changing the use alone.
+                let tcx = self.infcx.tcx;
+                let name =
+                    moved_place.as_local().and_then(|_| self.describe_place(moved_place.as_ref()));
+                let suggestion = suggestion.filter(|&(var_span, _)| {
+                    tcx.sess.source_map().span_to_snippet(var_span).ok() == name
+                });
+                if let (Some((var_span, is_receiver)), Some(name)) = (suggestion, name) {
+                    let ty = moved_place.ty(self.body, tcx).ty;
+                    let is_clone = tcx.lang_items().clone_trait().map_or(false, |clone| {
+                        tcx.type_implements_trait((clone, ty, ty::List::empty(), self.param_env))
+                    });
+                    if self.callee_takes_reference(var_span) == Some(true) {
+                        err.span_suggestion_verbose(
+                            var_span,
+                            &format!(
+                                "consider borrowing `{}` in the scrutinee instead of moving it",
+                                name
+                            ),
+                            if is_receiver { format!("(&{})", name) } else { format!("&{}", name) },
+                            Applicability::MaybeIncorrect,
+                        );
+                    } else if is_clone {
+                        err.span_suggestion_verbose(
+                            var_span.shrink_to_hi(),
+                            &format!(
+                                "consider cloning `{}` in the scrutinee instead of moving it",
+                                name
+                            ),
+                            ".clone()".to_owned(),
+                            Applicability::MaybeIncorrect,
+                        );
+                    } else {
+                        err.help(&format!(
+                            "consider moving the use of `{}` before the loop, so that it is only \
+                             moved once",
+                            name
+                        ));
+                    }
+                }
+            }
+
             if !is_loop_move {
-                err.span_label(
-                    span,
+                let label = if called_closure.is_some() {
+                    "closure called again here".to_owned()
+                } else if let InitializationRequiringAction::RawBorrow = desired_action {
+                    format!("raw pointer created here after {}move", partial_str)
+                } else {
                     format!(
                         "value {} here after {}move",
                         desired_action.as_verb_in_past_tense(),
                         partial_str
-                    ),
-                );
+                    )
+                };
+                err.span_label(span, label);
             }
 
             let ty = used_place.ty(self.body, self.infcx.tcx).ty;
             let needs_note = match ty.kind() {
-                ty::Closure(id, _) => {
+                ty::Closure(id, substs) => {
                     let tables = self.infcx.tcx.typeck(id.expect_local());
                     let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(id.expect_local());
 
-                    tables.closure_kind_origins().get(hir_id).is_none()
+                    // A closure which is `FnOnce` is not `Copy` because of what it moves,
+                    // rather than because of its type.
+                    match tables.closure_kind_origins().get(hir_id) {
+                        Some((origin_span, origin_place)) => {
+                            if !closure_note_added
+                                && substs.as_closure().kind() == ty::ClosureKind::FnOnce
+                            {
+                                err.span_note(
+                                    *origin_span,
+                                    &format!(
+                                        "closure is inferred to be `FnOnce` because it moves `{}` \
+                                         here",
+                                        ty::place_to_string_for_capture(
+                                            self.infcx.tcx,
+                                            origin_place
+                                        )
+                                    ),
+                                );
+                            }
+                            false
+                        }
+                        None => true,
+                    }
                 }
                 _ => true,
             };
 
+            if let Some((_, origin_span, ref origin_place)) = called_closure {
+                self.suggest_not_moving_capture(&mut err, origin_span, origin_place);
+            }
+
             let mpi = self.move_data.moves[move_out_indices[0]].path;
             let place = &self.move_data.move_paths[mpi].place;
             let ty = place.ty(self.body, self.infcx.tcx).ty;
@@ -410,6 +701,77 @@ pub(in crate::borrow_check) fn report_use_of_moved_or_uninitialized(
         }
     }
 
+    /// Suggests keeping a closure from moving the captured place `origin_place` at
+    /// `origin_span`, so that it is no longer `FnOnce`.
+    fn suggest_not_moving_capture(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        origin_span: Span,
+        origin_place: &HirPlace<'tcx>,
+    ) {
+        let tcx = self.infcx.tcx;
+        // A function taking the captured value by value can't be given a reference.
+        let takes_reference = self.callee_takes_reference(origin_span).unwrap_or(true);
+
+        let captured = ty::place_to_string_for_capture(tcx, origin_place);
+        let is_clone = tcx.lang_items().clone_trait().map_or(false, |clone| {
+            tcx.type_implements_trait((clone, origin_place.ty(), ty::List::empty(), self.param_env))
+        });
+        if is_clone {
+            err.span_suggestion_verbose(
+                origin_span.shrink_to_hi(),
+                &format!(
+                    "consider cloning `{}`, so that the closure only borrows it and can be \
+                     called more than once",
+                    captured
+                ),
+                ".clone()".to_owned(),
+                Applicability::MaybeIncorrect,
+            );
+        } else if takes_reference {
+            err.span_suggestion_verbose(
+                origin_span.shrink_to_lo(),
+                &format!(
+                    "consider borrowing `{}`, so that the closure does not move it and can be \
+                     called more than once",
+                    captured
+                ),
+                "&".to_owned(),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Returns the function which the expression at `span` is passed to, along with the index
+    /// of the parameter it is passed as.
+    fn callee_of_arg(&self, span: Span) -> Option<(DefId, usize)> {
+        let tcx = self.infcx.tcx;
+        let hir_id = tcx.hir().local_def_id_to_hir_id(self.mir_def_id());
+        let body_id = tcx.hir().maybe_body_owned_by(hir_id)?;
+        let mut finder = CallArgFinder { tcx, span, found: None };
+        finder.visit_body(tcx.hir().body(body_id));
+        let (call, index) = finder.found?;
+
+        let tables = tcx.typeck(self.mir_def_id());
+        let def_id = match call.kind {
+            hir::ExprKind::Call(callee, _) => match *tables.node_type(callee.hir_id).kind() {
+                ty::FnDef(def_id, _) => def_id,
+                _ => return None,
+            },
+            _ => tables.type_dependent_def_id(call.hir_id)?,
+        };
+        Some((def_id, index))
+    }
+
+    /// Whether the function which the expression at `span` is passed to, either as an argument
+    /// or as the receiver of a method, takes that parameter by reference. Returns `None` if the
+    /// expression isn't passed to a known function.
+    fn callee_takes_reference(&self, span: Span) -> Option<bool> {
+        let (def_id, index) = self.callee_of_arg(span)?;
+        let inputs = self.infcx.tcx.fn_sig(def_id).skip_binder().inputs();
+        Some(inputs.get(index).map_or(false, |input| input.is_ref()))
+    }
+
     pub(in crate::borrow_check) fn report_move_out_while_borrowed(
         &mut self,
         location: Location,
@@ -433,15 +795,16 @@ pub(in crate::borrow_check) fn report_move_out_while_borrowed(
             self.cannot_move_when_borrowed(span, &self.describe_any_place(place.as_ref()));
         err.span_label(borrow_span, format!("borrow of {} occurs here", borrow_msg));
         err.span_label(span, format!("move out of {} occurs here", value_msg));
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(borrow))]);
 
         borrow_spans.var_span_label_path_only(
             &mut err,
-            format!("borrow occurs due to use{}", borrow_spans.describe()),
+            format!("borrow occurs due to use{}", borrow_spans.describe_captured()),
         );
 
         move_spans.var_span_label(
             &mut err,
-            format!("move occurs due to use{}", move_spans.describe()),
+            format!("move occurs due to use{}", move_spans.describe_captured()),
             "moved",
         );
 
@@ -489,6 +852,7 @@ pub(in crate::borrow_check) fn report_use_while_mutably_borrowed(
             },
             "mutable",
         );
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(borrow))]);
 
         self.explain_why_borrow_contains_point(location, borrow, None)
             .add_explanation_to_diagnostic(
@@ -521,75 +885,58 @@ pub(in crate::borrow_check) fn report_conflicting_borrow(
             "closure"
         };
 
-        let (desc_place, msg_place, msg_borrow, union_type_name) =
+        let places =
             self.describe_place_for_conflicting_borrow(place, issued_borrow.borrowed_place);
+        let desc_place = places.desc.as_str();
 
         let explanation = self.explain_why_borrow_contains_point(location, issued_borrow, None);
         let second_borrow_desc = if explanation.is_explained() { "second " } else { "" };
 
-        // FIXME: supply non-"" `opt_via` when appropriate
-        let first_borrow_desc;
-        let mut err = match (gen_borrow_kind, issued_borrow.kind) {
-            (BorrowKind::Shared, BorrowKind::Mut { .. }) => {
-                first_borrow_desc = "mutable ";
-                self.cannot_reborrow_already_borrowed(
+        let new_method = match gen_borrow_kind {
+            BorrowKind::Mut { .. } => self
+                .reference_assigned_at(location)
+                .and_then(|assigned_place| self.autoref_mut_method(location, assigned_place)),
+            _ => None,
+        };
+        let old_method = match issued_borrow.kind {
+            BorrowKind::Mut { .. } => self
+                .autoref_mut_method(issued_borrow.reserve_location, issued_borrow.assigned_place),
+            _ => None,
+        };
+
+        let (conflict, first_borrow_desc) =
+            ConflictingBorrow::classify(gen_borrow_kind, issued_borrow.kind).unwrap_or_else(|| {
+                span_bug!(
                     span,
-                    &desc_place,
-                    &msg_place,
-                    "immutable",
-                    issued_span,
-                    "it",
-                    "mutable",
-                    &msg_borrow,
-                    None,
+                    "borrows of kinds {:?} and {:?} don't conflict",
+                    gen_borrow_kind,
+                    issued_borrow.kind
                 )
-            }
-            (BorrowKind::Mut { .. }, BorrowKind::Shared) => {
-                first_borrow_desc = "immutable ";
-                self.cannot_reborrow_already_borrowed(
+            });
+        let mut err = match conflict {
+            ConflictingBorrow::AlreadyBorrowed { kind_new, kind_old } => self
+                .cannot_reborrow_already_borrowed(
                     span,
-                    &desc_place,
-                    &msg_place,
-                    "mutable",
+                    desc_place,
+                    &places.new_via,
+                    kind_new,
+                    new_method,
                     issued_span,
                     "it",
-                    "immutable",
-                    &msg_borrow,
+                    kind_old,
+                    &places.old_via,
+                    old_method,
                     None,
-                )
-            }
-
-            (BorrowKind::Mut { .. }, BorrowKind::Mut { .. }) => {
-                first_borrow_desc = "first ";
-                let mut err = self.cannot_mutably_borrow_multiply(
-                    span,
-                    &desc_place,
-                    &msg_place,
-                    issued_span,
-                    &msg_borrow,
-                    None,
-                );
-                self.suggest_split_at_mut_if_applicable(
-                    &mut err,
-                    place,
-                    issued_borrow.borrowed_place,
-                );
-                err
-            }
-
-            (BorrowKind::Unique, BorrowKind::Unique) => {
-                first_borrow_desc = "first ";
-                self.cannot_uniquely_borrow_by_two_closures(span, &desc_place, issued_span, None)
-            }
+                ),
 
-            (BorrowKind::Mut { .. } | BorrowKind::Unique, BorrowKind::Shallow) => {
+            ConflictingBorrow::MutatedInImmutableSection => {
                 if let Some(immutable_section_description) =
                     self.classify_immutable_section(issued_borrow.assigned_place)
                 {
                     let mut err = self.cannot_mutate_in_immutable_section(
                         span,
                         issued_span,
-                        &desc_place,
+                        desc_place,
                         immutable_section_description,
                         "mutably borrow",
                     );
@@ -604,74 +951,70 @@ pub(in crate::borrow_check) fn report_conflicting_borrow(
                     );
 
                     return err;
-                } else {
-                    first_borrow_desc = "immutable ";
-                    self.cannot_reborrow_already_borrowed(
-                        span,
-                        &desc_place,
-                        &msg_place,
-                        "mutable",
-                        issued_span,
-                        "it",
-                        "immutable",
-                        &msg_borrow,
-                        None,
-                    )
                 }
-            }
-
-            (BorrowKind::Unique, _) => {
-                first_borrow_desc = "first ";
-                self.cannot_uniquely_borrow_by_one_closure(
+                self.cannot_reborrow_already_borrowed(
                     span,
-                    container_name,
-                    &desc_place,
-                    "",
+                    desc_place,
+                    &places.new_via,
+                    "mutable",
+                    new_method,
                     issued_span,
                     "it",
-                    "",
+                    "immutable",
+                    &places.old_via,
+                    None,
                     None,
                 )
             }
 
-            (BorrowKind::Shared, BorrowKind::Unique) => {
-                first_borrow_desc = "first ";
-                self.cannot_reborrow_already_uniquely_borrowed(
+            ConflictingBorrow::MutablyBorrowedMultiply => {
+                let mut err = self.cannot_mutably_borrow_multiply(
                     span,
-                    container_name,
-                    &desc_place,
-                    "",
-                    "immutable",
+                    desc_place,
+                    &places.new_via,
+                    new_method,
                     issued_span,
-                    "",
+                    &places.old_via,
+                    old_method,
                     None,
-                    second_borrow_desc,
-                )
+                );
+                self.suggest_split_at_mut_if_applicable(
+                    &mut err,
+                    place,
+                    issued_borrow.borrowed_place,
+                );
+                err
             }
 
-            (BorrowKind::Mut { .. }, BorrowKind::Unique) => {
-                first_borrow_desc = "first ";
-                self.cannot_reborrow_already_uniquely_borrowed(
+            ConflictingBorrow::UniquelyBorrowedByTwoClosures => {
+                self.cannot_uniquely_borrow_by_two_closures(span, desc_place, issued_span, None)
+            }
+
+            ConflictingBorrow::UniquelyBorrowedByOneClosure => self
+                .cannot_uniquely_borrow_by_one_closure(
                     span,
                     container_name,
-                    &desc_place,
-                    "",
-                    "mutable",
+                    desc_place,
+                    &places.new_via,
                     issued_span,
-                    "",
+                    "it",
+                    &places.old_via,
                     None,
-                    second_borrow_desc,
-                )
-            }
+                ),
 
-            (BorrowKind::Shared, BorrowKind::Shared | BorrowKind::Shallow)
-            | (
-                BorrowKind::Shallow,
-                BorrowKind::Mut { .. }
-                | BorrowKind::Unique
-                | BorrowKind::Shared
-                | BorrowKind::Shallow,
-            ) => unreachable!(),
+            ConflictingBorrow::AlreadyUniquelyBorrowed { kind_new } => self
+                .cannot_reborrow_already_uniquely_borrowed(
+                    span,
+                    container_name,
+                    desc_place,
+                    &places.new_via,
+                    kind_new,
+                    new_method,
+                    issued_span,
+                    &places.old_via,
+                    None,
+                    second_borrow_desc,
+                ),
         };
 
         if issued_spans == borrow_spans {
@@ -704,13 +1047,37 @@ pub(in crate::borrow_check) fn report_conflicting_borrow(
             );
         }
 
-        if union_type_name != "" {
+        if let Some(union_type_name) = &places.union_type_name {
             err.note(&format!(
                 "{} is a field of the union `{}`, so it overlaps the field {}",
-                msg_place, union_type_name, msg_borrow,
+                places.new_via, union_type_name, places.old_via,
             ));
         }
 
+        if let Some(index) = self.describe_same_constant_index(place, issued_borrow.borrowed_place)
+        {
+            err.note(&format!("both borrows are of the element at index {}", index));
+        }
+
+        self.label_static_definition(&mut err, place.as_ref());
+
+        self.suggest_for_overloaded_index_conflict(&mut err, location, place, issued_borrow);
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(issued_borrow))]);
+
+        if let BorrowExplanation::UsedLater(kind, later_use_span, _) = &explanation {
+            self.suggest_moving_first_borrow(&mut err, issued_span, span, *later_use_span);
+            if let LaterUseKind::Call = kind {
+                self.explain_closure_capture_conflict(
+                    &mut err,
+                    issued_borrow,
+                    issued_spans,
+                    borrow_spans,
+                    span,
+                    *later_use_span,
+                );
+            }
+        }
+
         explanation.add_explanation_to_diagnostic(
             self.infcx.tcx,
             &self.body,
@@ -720,55 +1087,415 @@ pub(in crate::borrow_check) fn report_conflicting_borrow(
             None,
         );
 
-        err
+        err
+    }
+
+    /// Points out the borrows besides the one passed to `report_conflicting_borrow`
+    /// which also conflict with the new borrow, so that they don't have to be
+    /// fixed one at a time.
+    pub(in crate::borrow_check) fn label_other_conflicting_borrows<'a>(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        other_borrows: impl Iterator<Item = &'a BorrowData<'tcx>>,
+    ) where
+        'tcx: 'a,
+    {
+        for borrow in other_borrows {
+            let span = self.retrieve_borrow_spans(borrow).args_or_use();
+            // Several borrows can be created by the same expression, e.g. in a loop.
+            if err.span.span_labels().iter().any(|label| label.span == span) {
+                continue;
+            }
+            err.span_label(
+                span,
+                format!("another {} borrow occurs here", borrow.kind.describe_mutability()),
+            );
+        }
+    }
+
+    /// Suggests moving the `let` statement creating the first of two conflicting borrows to
+    /// just before the first borrow is used again, when all uses of the second borrow come
+    /// before that:
+    ///
+    /// ```text
+    /// let a = &mut x;
+    /// let b = &x;
+    /// use_ref(b);
+    /// use_mut(a);
+    /// ```
+    fn suggest_moving_first_borrow(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        first_borrow_span: Span,
+        second_borrow_span: Span,
+        later_use_span: Span,
+    ) {
+        let spans = [first_borrow_span, second_borrow_span, later_use_span];
+        if spans.iter().any(|span| span.from_expansion()) {
+            return;
+        }
+        let tcx = self.infcx.tcx;
+        let hir_id = tcx.hir().local_def_id_to_hir_id(self.mir_def_id());
+        let body_id = match tcx.hir().maybe_body_owned_by(hir_id) {
+            Some(body_id) => body_id,
+            None => return,
+        };
+        let mut finder = InnermostBlockFinder { spans, found: None };
+        finder.visit_body(tcx.hir().body(body_id));
+        let block = match finder.found {
+            Some(block) => block,
+            None => return,
+        };
+
+        let stmt_index = |span: Span| block.stmts.iter().position(|stmt| stmt.span.contains(span));
+        let (first, second, later) = match (
+            stmt_index(first_borrow_span),
+            stmt_index(second_borrow_span),
+            stmt_index(later_use_span),
+        ) {
+            (Some(first), Some(second), Some(later)) if first < second && second < later => {
+                (first, second, later)
+            }
+            _ => return,
+        };
+        let binding = |stmt: &hir::Stmt<'_>| match stmt.kind {
+            hir::StmtKind::Local(local) => match local.pat.kind {
+                hir::PatKind::Binding(_, hir_id, _, None) => Some(hir_id),
+                _ => None,
+            },
+            _ => None,
+        };
+        // The first borrow must be bound to a variable which is not used before the
+        // statement it is moved to...
+        let first_local = match binding(&block.stmts[first]) {
+            Some(local) => local,
+            None => return,
+        };
+        if LocalUseFinder::is_used_in(tcx, first_local, &block.stmts[first + 1..later], None) {
+            return;
+        }
+        // ...and the second borrow must be bound to a variable whose uses all come before it.
+        let second_local = match binding(&block.stmts[second]) {
+            Some(local) => local,
+            None => return,
+        };
+        if !LocalUseFinder::is_used_in(tcx, second_local, &block.stmts[second + 1..later], None)
+            || LocalUseFinder::is_used_in(tcx, second_local, &block.stmts[later..], block.expr)
+        {
+            return;
+        }
+
+        let sm = tcx.sess.source_map();
+        let (first_stmt, later_stmt) = (&block.stmts[first], &block.stmts[later]);
+        let (snippet, indent) =
+            match (sm.span_to_snippet(first_stmt.span), sm.span_to_margin(later_stmt.span)) {
+                (Ok(snippet), Some(indent)) => (snippet, indent),
+                _ => return,
+            };
+        let span = first_stmt.span;
+        let (prev, next) = match (sm.span_to_prev_source(span), sm.span_to_next_source(span)) {
+            (Ok(prev), Ok(next)) => (prev, next),
+            _ => return,
+        };
+        // Remove the statement and the blanks after it, and its whole line if nothing else is on
+        // it. Comments around the statement are left alone.
+        let is_blank = |c: char| c == ' ' || c == '\t';
+        let leading = prev.len() - prev.trim_end_matches(is_blank).len();
+        let trailing = next.len() - next.trim_start_matches(is_blank).len();
+        let removal =
+            if prev[..prev.len() - leading].ends_with('\n') && next[trailing..].starts_with('\n') {
+                span.with_lo(span.lo() - BytePos(leading as u32))
+                    .with_hi(span.hi() + BytePos(trailing as u32 + 1))
+            } else {
+                span.with_hi(span.hi() + BytePos(trailing as u32))
+            };
+        err.multipart_suggestion(
+            "consider moving the first borrow to just before its next use",
+            vec![
+                (removal, String::new()),
+                (later_stmt.span.shrink_to_lo(), format!("{}\n{}", snippet, " ".repeat(indent))),
+            ],
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    /// Explains a conflict between a borrow captured by a closure and a use of the same
+    /// place by the enclosing function before the closure is called again:
+    ///
+    /// ```text
+    /// let mut inc = || x += 1;
+    /// println!("{}", x);
+    /// inc();
+    /// ```
+    fn explain_closure_capture_conflict(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        issued_borrow: &BorrowData<'tcx>,
+        issued_spans: UseSpans<'tcx>,
+        borrow_spans: UseSpans<'tcx>,
+        span: Span,
+        later_use_span: Span,
+    ) {
+        let capture_span = match issued_spans {
+            UseSpans::ClosureUse { generator_kind: None, path_span, .. } => path_span,
+            _ => return,
+        };
+        // The second borrow has to come from the enclosing function, and has to be over
+        // before the closure is called, or calling the closure earlier wouldn't help.
+        if borrow_spans.for_closure()
+            || borrow_spans.for_generator()
+            || span.hi() > later_use_span.lo()
+        {
+            return;
+        }
+        let capture_kind = match issued_borrow.kind {
+            BorrowKind::Mut { .. } => "by mutable reference",
+            BorrowKind::Unique => "by unique immutable borrow",
+            BorrowKind::Shared | BorrowKind::Shallow => "by immutable reference",
+        };
+        let place_desc = self.describe_any_place(issued_borrow.borrowed_place.as_ref());
+        err.span_note(
+            capture_span,
+            &format!(
+                "the closure captures {} {}, and keeps it borrowed for as long as the closure \
+                 is used",
+                place_desc, capture_kind,
+            ),
+        );
+        err.help(&format!(
+            "consider calling the closure before {} is used again, or limiting the closure's \
+             lifetime with a block that ends before that use",
+            place_desc,
+        ));
+    }
+
+    /// If both places are the same element of an array or slice, selected by the same
+    /// constant index (as slice patterns do), returns that index for use in diagnostics.
+    ///
+    /// Provably different constant indices don't conflict at all, and an index from the
+    /// start of a slice only *may* overlap with an index from its end, so those cases
+    /// aren't described.
+    fn describe_same_constant_index(
+        &self,
+        place: Place<'tcx>,
+        borrowed_place: Place<'tcx>,
+    ) -> Option<String> {
+        if place.local != borrowed_place.local {
+            return None;
+        }
+        match (place.projection.split_last()?, borrowed_place.projection.split_last()?) {
+            (
+                (ProjectionElem::ConstantIndex { offset, from_end, .. }, base),
+                (
+                    ProjectionElem::ConstantIndex {
+                        offset: borrowed_offset,
+                        from_end: borrowed_from_end,
+                        ..
+                    },
+                    borrowed_base,
+                ),
+            ) if base == borrowed_base
+                && offset == borrowed_offset
+                && from_end == borrowed_from_end =>
+            {
+                Some(if *from_end {
+                    format!("`len - {}`", offset)
+                } else {
+                    format!("`{}`", offset)
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn suggest_split_at_mut_if_applicable(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        place: Place<'tcx>,
+        borrowed_place: Place<'tcx>,
+    ) {
+        if let ([ProjectionElem::Index(_)], [ProjectionElem::Index(_)]) =
+            (&place.projection[..], &borrowed_place.projection[..])
+        {
+            err.help(
+                "consider using `.split_at_mut(position)` or similar method to obtain \
+                     two mutable non-overlapping sub-slices",
+            );
+        }
+    }
+
+    /// Explains conflicts involving borrows taken by overloaded `Index`/`IndexMut` calls,
+    /// as in `v[0].push(v[1].len())`: those borrow the whole container rather than just
+    /// the indexed element.
+    fn suggest_for_overloaded_index_conflict(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        location: Location,
+        place: Place<'tcx>,
+        issued_borrow: &BorrowData<'tcx>,
+    ) {
+        let new_index = self
+            .reference_assigned_at(location)
+            .and_then(|assigned_place| self.overloaded_index_receiver(location, assigned_place));
+        let issued_index = self.overloaded_index_receiver(
+            issued_borrow.reserve_location,
+            issued_borrow.assigned_place,
+        );
+        // Only the borrow which is kept alive matters: when it comes from something else, like
+        // a call to `get_mut`, indexing the container again doesn't help.
+        let container_ty = match issued_index {
+            Some(ty) => ty,
+            None => return,
+        };
+        let place_desc = match self.describe_place(place.as_ref()) {
+            Some(desc) => desc,
+            None => return,
+        };
+
+        let tcx = self.infcx.tcx;
+        let is_adt = |names: &[Symbol]| match container_ty.kind() {
+            ty::Adt(def, _) => names.iter().any(|&name| tcx.is_diagnostic_item(name, def.did)),
+            _ => false,
+        };
+        if is_adt(&[sym::hashmap_type, sym::BTreeMap]) {
+            err.note(&format!(
+                "indexing borrows all of `{}`, not just the indexed entry",
+                place_desc
+            ));
+            err.help(&format!(
+                "consider reading the values you need from `{}` into local variables first, \
+                 then use `.get_mut()` or the `entry` API to modify the map",
+                place_desc,
+            ));
+        } else if new_index.is_some() {
+            err.note(&format!(
+                "indexing borrows all of `{}`, not just the indexed element",
+                place_desc
+            ));
+            if is_adt(&[sym::vec_type, sym::vecdeque_type])
+                || matches!(container_ty.kind(), ty::Array(..) | ty::Slice(_))
+            {
+                err.help(
+                    "consider reading one of the elements into a local variable first, or using \
+                     `.split_at_mut(position)` to obtain two mutable non-overlapping sub-slices",
+                );
+            }
+        }
+    }
+
+    /// Returns the place that the statement at `location` assigns a new reference to, if any.
+    fn reference_assigned_at(&self, location: Location) -> Option<Place<'tcx>> {
+        match self.body[location.block].statements.get(location.statement_index) {
+            Some(Statement {
+                kind: StatementKind::Assign(box (assigned_place, Rvalue::Ref(..))),
+                ..
+            }) => Some(*assigned_place),
+            _ => None,
+        }
+    }
+
+    /// If the reference assigned to `assigned_place` at `location` is passed as the `self`
+    /// argument of an overloaded `Index::index` or `IndexMut::index_mut` call, returns the
+    /// type of the indexed container.
+    fn overloaded_index_receiver(
+        &self,
+        location: Location,
+        assigned_place: Place<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        let func_ty = self.borrow_receiving_call(location, assigned_place)?;
+        match BorrowedContentSource::from_call(func_ty, self.infcx.tcx) {
+            Some(BorrowedContentSource::OverloadedIndex(ty)) => Some(ty),
+            _ => None,
+        }
     }
 
-    fn suggest_split_at_mut_if_applicable(
+    /// If the mutable reference assigned to `assigned_place` at `location` was created for an
+    /// `IndexMut::index_mut` or `DerefMut::deref_mut` call, returns the name of that method.
+    fn autoref_mut_method(
         &self,
-        err: &mut DiagnosticBuilder<'_>,
-        place: Place<'tcx>,
-        borrowed_place: Place<'tcx>,
-    ) {
-        if let ([ProjectionElem::Index(_)], [ProjectionElem::Index(_)]) =
-            (&place.projection[..], &borrowed_place.projection[..])
+        location: Location,
+        assigned_place: Place<'tcx>,
+    ) -> Option<Symbol> {
+        let tcx = self.infcx.tcx;
+        let def_id = match *self.borrow_receiving_call(location, assigned_place)?.kind() {
+            ty::FnDef(def_id, _) => def_id,
+            _ => return None,
+        };
+        let trait_id = tcx.trait_of_item(def_id)?;
+        let lang_items = tcx.lang_items();
+        if Some(trait_id) == lang_items.index_mut_trait()
+            || Some(trait_id) == lang_items.deref_mut_trait()
         {
-            err.help(
-                "consider using `.split_at_mut(position)` or similar method to obtain \
-                     two mutable non-overlapping sub-slices",
-            );
+            Some(tcx.item_name(def_id))
+        } else {
+            None
         }
     }
 
-    /// Returns the description of the root place for a conflicting borrow and the full
-    /// descriptions of the places that caused the conflict.
-    ///
-    /// In the simplest case, where there are no unions involved, if a mutable borrow of `x` is
-    /// attempted while a shared borrow is live, then this function will return:
-    ///
-    ///     ("x", "", "")
+    /// Returns the type of the function called with the reference assigned to
+    /// `assigned_place` at `location` as its first argument, if that call follows it.
+    fn borrow_receiving_call(
+        &self,
+        location: Location,
+        assigned_place: Place<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        let mut visited = FxHashSet::default();
+        let mut block = location.block;
+        // The index operand is evaluated after the autoref, and may contain overflow
+        // checks or calls of its own, e.g. `v[v[0]]`, so skip over those.
+        while visited.insert(block) {
+            match self.body[block].terminator().kind {
+                TerminatorKind::Call { ref func, ref args, ref destination, .. } => {
+                    match (args.first(), destination) {
+                        (Some(Operand::Move(arg)), _) if *arg == assigned_place => {
+                            return Some(func.ty(self.body, self.infcx.tcx));
+                        }
+                        (_, Some((_, target))) => block = *target,
+                        (_, None) => return None,
+                    }
+                }
+                TerminatorKind::Goto { target } | TerminatorKind::Assert { target, .. } => {
+                    block = target;
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Describes the places involved in a conflict between a borrow of `first_borrowed_place`
+    /// and an existing borrow of `second_borrowed_place`.
     ///
-    /// In the simple union case, if a mutable borrow of a union field `x.z` is attempted while
-    /// a shared borrow of another field `x.y`, then this function will return:
+    /// In the simplest case, where both borrows are of the same place, or a mutable borrow of
+    /// `x.y` is attempted while a shared borrow of `x` is live, the conflict is described as being
+    /// on the place of the new borrow, without any "via" qualifiers:
     ///
-    ///     ("x", "x.z", "x.y")
+    /// ```text
+    /// cannot borrow `x.y` as mutable because it is also borrowed as immutable [E0502]
+    /// ```
     ///
-    /// In the more complex union case, where the union is a field of a struct, then if a mutable
-    /// borrow of a union field in a struct `x.u.z` is attempted while a shared borrow of
-    /// another field `x.u.y`, then this function will return:
+    /// If instead the existing borrow is of a place reached from the new one through field
+    /// accesses and dereferences, like a borrow of `*x.p` or a closure capturing `x.y`, it is
+    /// qualified with the place it actually borrows:
     ///
-    ///     ("x.u", "x.u.z", "x.u.y")
+    /// ```text
+    /// cannot borrow `x` as mutable because it is also borrowed as immutable (via `*x.p`) [E0502]
+    /// ```
     ///
-    /// This is used when creating error messages like below:
+    /// If a mutable borrow of a union field `x.z` is attempted while a shared borrow of another
+    /// field `x.y` is live, then the conflict is on the union `x`, and both borrows are qualified.
+    /// This also works for a union which is a field of a struct, so borrows of `a.u.z.c` and
+    /// `a.u.s.b` are reported as:
     ///
     /// ```text
     /// cannot borrow `a.u` (via `a.u.z.c`) as immutable because it is also borrowed as
     /// mutable (via `a.u.s.b`) [E0502]
     /// ```
-    pub(in crate::borrow_check) fn describe_place_for_conflicting_borrow(
+    fn describe_place_for_conflicting_borrow(
         &self,
         first_borrowed_place: Place<'tcx>,
         second_borrowed_place: Place<'tcx>,
-    ) -> (String, String, String, String) {
+    ) -> ConflictingPlaces {
         // Define a small closure that we can use to check if the type of a place
         // is a union.
         let union_ty = |place_base| {
@@ -809,12 +1536,13 @@ pub(in crate::borrow_check) fn describe_place_for_conflicting_borrow(
                     if let ProjectionElem::Field(field, _) = elem {
                         if let Some(union_ty) = union_ty(place_base) {
                             if field != target_field && place_base == target_base {
-                                return Some((
-                                    self.describe_any_place(place_base),
-                                    self.describe_any_place(first_borrowed_place.as_ref()),
-                                    self.describe_any_place(second_borrowed_place.as_ref()),
-                                    union_ty.to_string(),
-                                ));
+                                return Some(ConflictingPlaces {
+                                    desc: self.describe_any_place(place_base),
+                                    new_via: self.describe_any_place(first_borrowed_place.as_ref()),
+                                    old_via: self
+                                        .describe_any_place(second_borrowed_place.as_ref()),
+                                    union_type_name: Some(union_ty.to_string()),
+                                });
                             }
                         }
                     }
@@ -822,17 +1550,41 @@ pub(in crate::borrow_check) fn describe_place_for_conflicting_borrow(
                 None
             })
             .unwrap_or_else(|| {
-                // If we didn't find a field access into a union, or both places match, then
-                // only return the description of the first place.
-                (
-                    self.describe_any_place(first_borrowed_place.as_ref()),
-                    "".to_string(),
-                    "".to_string(),
-                    "".to_string(),
-                )
+                // If we didn't find a field access into a union, then the conflict is on the
+                // place of the first borrow, which the second borrow may only reach through
+                // further projections.
+                ConflictingPlaces {
+                    desc: self.describe_any_place(first_borrowed_place.as_ref()),
+                    new_via: String::new(),
+                    old_via: self
+                        .describe_extended_place(first_borrowed_place, second_borrowed_place)
+                        .unwrap_or_default(),
+                    union_type_name: None,
+                }
             })
     }
 
+    /// Describes `extended` if it is reached from `base` only through field accesses and
+    /// dereferences, like `x.y` or `*x.p` from `x`, and isn't described the same as `base`.
+    fn describe_extended_place(&self, base: Place<'tcx>, extended: Place<'tcx>) -> Option<String> {
+        if base.local != extended.local {
+            return None;
+        }
+        let projections = extended.projection.strip_prefix(&base.projection[..])?;
+        if projections.is_empty()
+            || !projections
+                .iter()
+                .all(|elem| matches!(elem, ProjectionElem::Field(..) | ProjectionElem::Deref))
+        {
+            return None;
+        }
+        let desc = self.describe_place(extended.as_ref())?;
+        if self.describe_place(base.as_ref()).as_ref() == Some(&desc) {
+            return None;
+        }
+        Some(format!("`{}`", desc))
+    }
+
     /// Reports StorageDeadOrDrop of `place` conflicts with `borrow`.
     ///
     /// This means that some data referenced by `borrow` needs to live
@@ -885,8 +1637,9 @@ pub(in crate::borrow_check) fn report_borrowed_value_does_not_live_long_enough(
 
         let borrowed_local = borrow.borrowed_place.local;
         if self.body.local_decls[borrowed_local].is_ref_to_thread_local() {
-            let err =
+            let mut err =
                 self.report_thread_local_value_does_not_live_long_enough(drop_span, borrow_span);
+            self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(borrow))]);
             err.buffer(&mut self.errors_buffer);
             return;
         }
@@ -908,6 +1661,15 @@ pub(in crate::borrow_check) fn report_borrowed_value_does_not_live_long_enough(
 
         let place_desc = self.describe_place(borrow.borrowed_place.as_ref());
 
+        // Locals of a `const` or `static` initializer are dropped once the initializer has been
+        // evaluated, which is a different situation from a function returning early.
+        let const_context = self
+            .infcx
+            .tcx
+            .hir()
+            .body_const_context(self.mir_def_id())
+            .filter(|ccx| *ccx != hir::ConstContext::ConstFn);
+
         let kind_place = kind.filter(|_| place_desc.is_some()).map(|k| (k, place_span.0));
         let explanation = self.explain_why_borrow_contains_point(location, &borrow, kind_place);
 
@@ -915,7 +1677,7 @@ pub(in crate::borrow_check) fn report_borrowed_value_does_not_live_long_enough(
             "report_borrowed_value_does_not_live_long_enough(place_desc: {:?}, explanation: {:?})",
             place_desc, explanation
         );
-        let err = match (place_desc, explanation) {
+        let mut err = match (place_desc, explanation) {
             // If the outlives constraint comes from inside the closure,
             // for example:
             //
@@ -970,6 +1732,7 @@ pub(in crate::borrow_check) fn report_borrowed_value_does_not_live_long_enough(
                 drop_span,
                 borrow_spans,
                 explanation,
+                const_context,
             ),
             (None, explanation) => self.report_temporary_value_does_not_live_long_enough(
                 location,
@@ -980,6 +1743,7 @@ pub(in crate::borrow_check) fn report_borrowed_value_does_not_live_long_enough(
                 explanation,
             ),
         };
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(borrow))]);
 
         err.buffer(&mut self.errors_buffer);
     }
@@ -992,12 +1756,13 @@ fn report_local_value_does_not_live_long_enough(
         drop_span: Span,
         borrow_spans: UseSpans<'tcx>,
         explanation: BorrowExplanation,
+        const_context: Option<hir::ConstContext>,
     ) -> DiagnosticBuilder<'cx> {
         debug!(
             "report_local_value_does_not_live_long_enough(\
-             {:?}, {:?}, {:?}, {:?}, {:?}\
+             {:?}, {:?}, {:?}, {:?}, {:?}, {:?}\
              )",
-            location, name, borrow, drop_span, borrow_spans
+            location, name, borrow, drop_span, borrow_spans, const_context
         );
 
         let borrow_span = borrow_spans.var_or_use_path_span();
@@ -1080,7 +1845,18 @@ fn report_local_value_does_not_live_long_enough(
             }
         } else {
             err.span_label(borrow_span, "borrowed value does not live long enough");
-            err.span_label(drop_span, format!("`{}` dropped here while still borrowed", name));
+            if let Some(ccx) = const_context {
+                err.span_label(
+                    drop_span,
+                    format!(
+                        "`{}` dropped here, at the end of the {}'s initializer, \
+                         while still borrowed",
+                        name, ccx
+                    ),
+                );
+            } else {
+                err.span_label(drop_span, format!("`{}` dropped here while still borrowed", name));
+            }
 
             let within = if borrow_spans.for_generator() { " by generator" } else { "" };
 
@@ -1094,6 +1870,18 @@ fn report_local_value_does_not_live_long_enough(
                 "",
                 None,
             );
+
+            if let Some(ccx) = const_context {
+                err.note(&format!(
+                    "references in the final value of a {} must outlive its initializer, \
+                     usually for `'static`",
+                    ccx
+                ));
+                err.note(
+                    "only constant expressions such as `&0` are promoted to live for `'static`; \
+                     a borrow of a local variable is never promoted",
+                );
+            }
         }
 
         err
@@ -1118,6 +1906,7 @@ fn report_borrow_conflicts_with_destructor(
         let borrow_span = borrow_spans.var_or_use();
 
         let mut err = self.cannot_borrow_across_destructor(borrow_span);
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(borrow))]);
 
         let what_was_dropped = match self.describe_place(place.as_ref()) {
             Some(name) => format!("`{}`", name),
@@ -1175,11 +1964,18 @@ fn report_thread_local_value_does_not_live_long_enough(
             drop_span, borrow_span
         );
 
+        // The borrow may be taken inside of a macro from another crate, which the user
+        // can't change, so point at its invocation instead.
+        let borrow_span = if in_external_macro(self.infcx.tcx.sess, borrow_span) {
+            borrow_span.source_callsite()
+        } else {
+            borrow_span
+        };
         let mut err = self.thread_local_value_does_not_live_long_enough(borrow_span);
 
         err.span_label(
             borrow_span,
-            "thread-local variables cannot be borrowed beyond the end of the function",
+            "thread-local variables cannot be borrowed past the end of the function",
         );
         err.span_label(drop_span, "end of enclosing function is here");
 
@@ -1225,7 +2021,15 @@ fn report_temporary_value_does_not_live_long_enough(
             | BorrowExplanation::UsedLaterInLoop(..)
             | BorrowExplanation::UsedLaterWhenDropped { .. } => {
                 // Only give this note and suggestion if it could be relevant.
-                err.note("consider using a `let` binding to create a longer lived value");
+                let msg = "consider using a `let` binding to create a longer lived value";
+                match self.suggest_let_binding_for_temporary(borrow, proper_span, drop_span) {
+                    Some(suggestion) => {
+                        err.multipart_suggestion(msg, suggestion, Applicability::MaybeIncorrect);
+                    }
+                    None => {
+                        err.note(msg);
+                    }
+                }
             }
             _ => {}
         }
@@ -1245,6 +2049,94 @@ fn report_temporary_value_does_not_live_long_enough(
         err
     }
 
+    /// Classifies the innermost loop around the move at `move_span`, which is recovered from
+    /// the HIR as the MIR building erases the difference between the kinds of loops.
+    fn classify_loop_move(&self, move_span: Span) -> Option<LoopMoveKind> {
+        if move_span.from_expansion() {
+            return None;
+        }
+        let tcx = self.infcx.tcx;
+        let hir_id = tcx.hir().local_def_id_to_hir_id(self.mir_def_id());
+        let body_id = tcx.hir().maybe_body_owned_by(hir_id)?;
+
+        let mut classifier = LoopMoveClassifier { move_span, found: None };
+        classifier.visit_body(tcx.hir().body(body_id));
+        classifier.found
+    }
+
+    /// Suggests binding the temporary created by the expression at `temp_span` to a variable,
+    /// declared right before the statement which creates the temporary. This is only done if
+    /// that statement always evaluates the expression, as it would be hoisted out of it, and
+    /// if the temporary is dropped at the end of that statement, as the variable would not
+    /// live any longer otherwise. Hoisting the expression must also not reorder any side
+    /// effects, so it may not have any, and it must be the only temporary borrowed by the
+    /// statement, as the others would need a binding of their own.
+    fn suggest_let_binding_for_temporary(
+        &self,
+        borrow: &BorrowData<'tcx>,
+        temp_span: Span,
+        drop_span: Span,
+    ) -> Option<Vec<(Span, String)>> {
+        if temp_span.from_expansion() {
+            return None;
+        }
+        let tcx = self.infcx.tcx;
+        let hir_id = tcx.hir().local_def_id_to_hir_id(self.mir_def_id());
+        let body_id = tcx.hir().maybe_body_owned_by(hir_id)?;
+
+        let mut finder =
+            TemporaryStatementFinder { temp_span, stmt: None, conditional: false, found: None };
+        finder.visit_body(tcx.hir().body(body_id));
+        let (stmt_span, temp_expr) = finder.found?;
+        if stmt_span.from_expansion() || !stmt_span.contains(drop_span) {
+            return None;
+        }
+
+        let mut side_effects =
+            SideEffectFinder { typeck_results: tcx.typeck(self.mir_def_id()), found: false };
+        side_effects.visit_expr(temp_expr);
+        if side_effects.found {
+            return None;
+        }
+        let temporaries = self
+            .borrow_set
+            .iter_enumerated()
+            .filter(|(_, borrow)| {
+                borrow.borrowed_place.as_local().map_or(false, |local| {
+                    self.body.local_kind(local) == LocalKind::Temp
+                        && stmt_span.contains(self.body.source_info(borrow.reserve_location).span)
+                })
+            })
+            .count();
+        if temporaries > 1 {
+            return None;
+        }
+
+        // Pick a name which doesn't shadow any of the variables of the body.
+        let is_taken = |name: &str| {
+            self.local_names.iter().flatten().any(|local_name| local_name.as_str() == name)
+                || self
+                    .upvars
+                    .iter()
+                    .any(|upvar| tcx.hir().name(upvar.place.get_root_variable()).as_str() == name)
+        };
+        let name = iter::once("binding".to_string())
+            .chain((1..).map(|i| format!("binding_{}", i)))
+            .find(|name| !is_taken(name))?;
+
+        let sm = tcx.sess.source_map();
+        let indent = sm.span_to_margin(stmt_span)?;
+        let snippet = sm.span_to_snippet(temp_span).ok()?;
+        let mutability = if let BorrowKind::Mut { .. } = borrow.kind { "mut " } else { "" };
+        Some(vec![
+            (
+                stmt_span.shrink_to_lo(),
+                format!("let {}{} = {};\n{}", mutability, name, snippet, " ".repeat(indent)),
+            ),
+            (temp_span, name),
+        ])
+    }
+
     fn try_report_cannot_return_reference_to_local(
         &self,
         borrow: &BorrowData<'tcx>,
@@ -1456,22 +2348,133 @@ fn report_escaping_data(
         err
     }
 
-    fn get_moved_indexes(&mut self, location: Location, mpi: MovePathIndex) -> Vec<MoveSite> {
-        fn predecessor_locations(
-            body: &'a mir::Body<'tcx>,
-            location: Location,
-        ) -> impl Iterator<Item = Location> + 'a {
-            if location.statement_index == 0 {
-                let predecessors = body.predecessors()[location.block].to_vec();
-                Either::Left(predecessors.into_iter().map(move |bb| body.terminator_loc(bb)))
+    /// Labels the conditions of the `if`s that decide whether `mpi` is initialized before its
+    /// use at `location`, with the outcome for which it is not.
+    fn label_branches_skipping_init(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        location: Location,
+        mpi: MovePathIndex,
+        item_msg: &str,
+    ) {
+        let body = self.body;
+
+        // Walk backwards from the use, stopping at the initializations of `mpi`. The
+        // locations we reach are the ones from which the use can be reached uninitialized.
+        let mut uninit_locations = FxHashSet::default();
+        let mut init_blocks = FxHashSet::default();
+        let mut visited = FxHashSet::default();
+        // The fake edges between match arms are not paths the program can take.
+        let real_predecessors = |location: Location| {
+            predecessor_locations(body, location).filter(move |predecessor| {
+                match body[predecessor.block].terminator().kind {
+                    TerminatorKind::FalseEdge { real_target, .. } => real_target == location.block,
+                    _ => true,
+                }
+            })
+        };
+        let mut stack: Vec<_> = real_predecessors(location).collect();
+        while let Some(location) = stack.pop() {
+            if !visited.insert(location) {
+                continue;
+            }
+            let mut is_init = false;
+            drop_flag_effects::for_location_inits(
+                self.infcx.tcx,
+                body,
+                self.move_data,
+                location,
+                |m| is_init |= m == mpi,
+            );
+            if is_init {
+                init_blocks.insert(location.block);
             } else {
-                Either::Right(std::iter::once(Location {
-                    statement_index: location.statement_index - 1,
-                    ..location
-                }))
+                uninit_locations.insert(location);
+                stack.extend(real_predecessors(location));
+            }
+        }
+        if init_blocks.is_empty() {
+            // The variable is never initialized, so no branch is to blame.
+            return;
+        }
+
+        let skips_init =
+            |bb| uninit_locations.contains(&Location { block: bb, statement_index: 0 });
+        let reaches = |start, is_target: &dyn Fn(BasicBlock) -> bool| {
+            let mut visited = FxHashSet::default();
+            let mut stack = vec![start];
+            while let Some(bb) = stack.pop() {
+                if is_target(bb) {
+                    return true;
+                }
+                if visited.insert(bb) {
+                    stack.extend(body[bb].terminator().successors().copied());
+                }
+            }
+            false
+        };
+        let reaches_init = |start| reaches(start, &|bb| init_blocks.contains(&bb));
+
+        for (bb, data) in body.basic_blocks().iter_enumerated() {
+            if !uninit_locations.contains(&body.terminator_loc(bb)) {
+                continue;
+            }
+            // A condition checked again on every iteration of a loop, like the one of a `while`,
+            // may be taken either way after an earlier iteration initialized the variable.
+            if data.terminator().successors().any(|&succ| reaches(succ, &|target| target == bb)) {
+                continue;
+            }
+            // An `if` switches on a temporary holding its condition, to the `else` branch on
+            // `false` and to the `then` branch otherwise.
+            let (discr, targets) = match &data.terminator().kind {
+                TerminatorKind::SwitchInt { discr, targets, .. } => (discr, targets),
+                _ => continue,
+            };
+            let cond_decl = match discr.place().and_then(|place| place.as_local()) {
+                Some(local) if body.local_kind(local) == LocalKind::Temp => {
+                    &body.local_decls[local]
+                }
+                _ => continue,
+            };
+            // The condition may be matched on through a desugared temporary.
+            let cond_span = match discr {
+                Operand::Move(_) => cond_decl.source_info.span,
+                _ if cond_decl.source_info.span.is_desugaring(DesugaringKind::CondTemporary) => {
+                    match cond_decl.source_info.span.parent() {
+                        Some(span) => span,
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            if !cond_decl.ty.is_bool() || cond_span.from_expansion() {
+                continue;
             }
+            let (else_bb, then_bb) = match targets.iter().next() {
+                Some((0, else_bb)) => (else_bb, targets.otherwise()),
+                _ => continue,
+            };
+            // Only blame the branch if none of its paths initialize the variable, and the other
+            // branch can.
+            let skipping_value =
+                if skips_init(else_bb) && !reaches_init(else_bb) && reaches_init(then_bb) {
+                    "false"
+                } else if skips_init(then_bb) && !reaches_init(then_bb) && reaches_init(else_bb) {
+                    "true"
+                } else {
+                    continue;
+                };
+            err.span_label(
+                cond_span,
+                format!(
+                    "if this condition is `{}`, {} is not initialized",
+                    skipping_value, item_msg
+                ),
+            );
         }
+    }
 
+    fn get_moved_indexes(&mut self, location: Location, mpi: MovePathIndex) -> Vec<MoveSite> {
         let mut stack = Vec::new();
         stack.extend(predecessor_locations(self.body, location).map(|predecessor| {
             let is_back_edge = location.dominates(predecessor, &self.dominators);
@@ -1589,9 +2592,10 @@ pub(in crate::borrow_check) fn report_illegal_mutation_of_borrowed(
                 );
                 loan_spans.var_span_label(
                     &mut err,
-                    format!("borrow occurs due to use{}", loan_spans.describe()),
+                    format!("borrow occurs due to use{}", loan_spans.describe_captured()),
                     loan.kind.describe_mutability(),
                 );
+                self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(loan))]);
 
                 err.buffer(&mut self.errors_buffer);
 
@@ -1600,10 +2604,11 @@ pub(in crate::borrow_check) fn report_illegal_mutation_of_borrowed(
         }
 
         let mut err = self.cannot_assign_to_borrowed(span, loan_span, &descr_place);
+        self.add_error_metadata(&mut err, &[("borrow", self.borrow_key(loan))]);
 
         loan_spans.var_span_label(
             &mut err,
-            format!("borrow occurs due to use{}", loan_spans.describe()),
+            format!("borrow occurs due to use{}", loan_spans.describe_captured()),
             loan.kind.describe_mutability(),
         );
 
@@ -1616,11 +2621,93 @@ pub(in crate::borrow_check) fn report_illegal_mutation_of_borrowed(
             None,
         );
 
+        self.label_reborrow_chain(&mut err, place, &descr_place);
         self.explain_deref_coercion(loan, &mut err);
+        self.label_static_definition(&mut err, place.as_ref());
 
         err.buffer(&mut self.errors_buffer);
     }
 
+    /// When `place` is reached through references which were created by borrowing other places,
+    /// like `*b` after `let a = &mut x; let b = &mut *a;`, labels each of those borrows and names
+    /// the place that `place` refers to in the end.
+    fn label_reborrow_chain(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        place: Place<'tcx>,
+        descr_place: &str,
+    ) {
+        let (reborrows, referenced_place) = self.reborrow_chain(place);
+        if reborrows.is_empty() {
+            return;
+        }
+        for &(local, borrow) in &reborrows {
+            let borrow_span = self.body.source_info(borrow.reserve_location).span;
+            let borrowed_place = self.describe_any_place(borrow.borrowed_place.as_ref());
+            let is_reborrow = borrow.borrowed_place.is_indirect();
+            let label = match self.local_names[local] {
+                Some(name) => format!(
+                    "`{}` {} {} here",
+                    name,
+                    if is_reborrow { "reborrows" } else { "borrows" },
+                    borrowed_place
+                ),
+                None => format!(
+                    "{} is {} here",
+                    borrowed_place,
+                    if is_reborrow { "reborrowed" } else { "borrowed" }
+                ),
+            };
+            err.span_label(borrow_span, label);
+        }
+        if let Some(referenced_place) = self.describe_place(referenced_place.as_ref()) {
+            err.note(&format!(
+                "{} refers to `{}` through the borrow{} labeled above",
+                descr_place,
+                referenced_place,
+                pluralize!(reborrows.len()),
+            ));
+        }
+    }
+
+    /// Follows `place` back through the references it is reached from, as long as each of them
+    /// is only assigned once, by borrowing a place that can be described. Returns the local
+    /// holding each reference along with the borrow creating it, starting from the reference
+    /// closest to `place`, and the place that `place` refers to in the end.
+    fn reborrow_chain(
+        &self,
+        mut place: Place<'tcx>,
+    ) -> (Vec<(Local, &BorrowData<'tcx>)>, Place<'tcx>) {
+        let mut reborrows: Vec<(Local, &BorrowData<'tcx>)> = vec![];
+        loop {
+            let rest = match place.projection.split_first() {
+                Some((ProjectionElem::Deref, rest)) => rest,
+                _ => break,
+            };
+            let local = place.local;
+            let borrow = match self.mutable_use(local).writes {
+                Writes::Once(location) => match self.borrow_set.location_map.get(&location) {
+                    Some(borrow) if borrow.assigned_place.as_local() == Some(local) => borrow,
+                    _ => break,
+                },
+                Writes::None | Writes::Several => break,
+            };
+            if reborrows.iter().any(|&(seen, _)| seen == local)
+                || self.describe_place(borrow.borrowed_place.as_ref()).is_none()
+            {
+                break;
+            }
+            let projection: Vec<_> =
+                borrow.borrowed_place.projection.iter().chain(rest.iter().copied()).collect();
+            place = Place {
+                local: borrow.borrowed_place.local,
+                projection: self.infcx.tcx.intern_place_elems(&projection),
+            };
+            reborrows.push((local, borrow));
+        }
+        (reborrows, place)
+    }
+
     fn explain_deref_coercion(&mut self, loan: &BorrowData<'tcx>, err: &mut DiagnosticBuilder<'_>) {
         let tcx = self.infcx.tcx;
         if let (
@@ -1707,14 +2794,42 @@ pub(in crate::borrow_check) fn report_illegal_reassignment(
                 err.span_label(assigned_span, format!("first assignment to {}", place_description));
             }
         }
+        // An assignment which is the only one to a variable can only be reported when it runs
+        // again, in a loop, in which case declaring the variable in the loop may be the fix.
+        let assigned_in_loop = !from_arg
+            && matches!(
+                err_place.as_local().map(|local| self.mutable_use(local).writes),
+                Some(Writes::Once(_))
+            );
+        if assigned_in_loop {
+            err.note(&format!(
+                "{} is only assigned here, but the assignment runs again on each iteration of \
+                 the loop",
+                place_description
+            ));
+        }
         if let Some(decl) = local_decl {
             if let Some(name) = local_name {
                 if decl.can_be_made_mutable() {
+                    // The span of a binding with a subpattern, like `x @ Some(_)`, covers the
+                    // subpattern too, so keep it in the rewrite.
+                    let span = decl.source_info.span;
+                    let binding = match self.infcx.tcx.sess.source_map().span_to_snippet(span) {
+                        Ok(snippet) if snippet.starts_with(&*name.as_str()) => snippet,
+                        _ => name.to_string(),
+                    };
+                    // Rewriting a pattern generated by a macro may not be possible at the
+                    // macro's call site.
+                    let applicability = if span.from_expansion() || assigned_in_loop {
+                        Applicability::MaybeIncorrect
+                    } else {
+                        Applicability::MachineApplicable
+                    };
                     err.span_suggestion(
-                        decl.source_info.span,
+                        span,
                         "consider making this binding mutable",
-                        format!("mut {}", name),
-                        Applicability::MachineApplicable,
+                        format!("mut {}", binding),
+                        applicability,
                     );
                 }
             }
@@ -2223,3 +3338,230 @@ pub(in crate::borrow_check) fn emit(
         }
     }
 }
+
+/// The position of a move relative to the innermost loop around it, as written in the source.
+#[derive(Clone, Copy, Debug)]
+enum LoopMoveKind {
+    /// The move happens in the scrutinee of a `while let` loop, whose span is stored.
+    WhileLetScrutinee(Span),
+    /// The move happens anywhere else in a loop of the given kind.
+    Other(hir::LoopSource),
+}
+
+/// Finds the innermost loop around the expression at `move_span`.
+struct LoopMoveClassifier {
+    move_span: Span,
+    found: Option<LoopMoveKind>,
+}
+
+impl<'v> Visitor<'v> for LoopMoveClassifier {
+    type Map = intravisit::ErasedMap<'v>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'v hir::Expr<'v>) {
+        if !expr.span.contains(self.move_span) {
+            return;
+        }
+        match expr.kind {
+            hir::ExprKind::Match(scrutinee, _, hir::MatchSource::WhileLetDesugar)
+                if scrutinee.span.contains(self.move_span) =>
+            {
+                self.found = Some(LoopMoveKind::WhileLetScrutinee(scrutinee.span));
+            }
+            hir::ExprKind::Loop(_, _, source, _) => {
+                self.found = Some(LoopMoveKind::Other(source));
+            }
+            _ => {}
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Finds the innermost block containing all of `spans`.
+struct InnermostBlockFinder<'hir> {
+    spans: [Span; 3],
+    found: Option<&'hir hir::Block<'hir>>,
+}
+
+impl<'hir> Visitor<'hir> for InnermostBlockFinder<'hir> {
+    type Map = intravisit::ErasedMap<'hir>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_block(&mut self, block: &'hir hir::Block<'hir>) {
+        if self.spans.iter().all(|span| block.span.contains(*span)) {
+            self.found = Some(block);
+            intravisit::walk_block(self, block);
+        }
+    }
+}
+
+/// Finds the call which the expression at `span` is an argument of, including in closures,
+/// along with the index of that argument. The receiver of a method call is its first argument.
+struct CallArgFinder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    span: Span,
+    found: Option<(&'tcx hir::Expr<'tcx>, usize)>,
+}
+
+impl<'tcx> Visitor<'tcx> for CallArgFinder<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        let args = match expr.kind {
+            hir::ExprKind::Call(_, args) | hir::ExprKind::MethodCall(_, _, args, _) => args,
+            _ => &[],
+        };
+        if let Some(index) = args.iter().position(|arg| arg.span == self.span) {
+            self.found = Some((expr, index));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Finds uses of the variable `local`, including in closures.
+struct LocalUseFinder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    local: hir::HirId,
+    found: bool,
+}
+
+impl<'tcx> LocalUseFinder<'tcx> {
+    fn is_used_in(
+        tcx: TyCtxt<'tcx>,
+        local: hir::HirId,
+        stmts: &'tcx [hir::Stmt<'tcx>],
+        expr: Option<&'tcx hir::Expr<'tcx>>,
+    ) -> bool {
+        let mut finder = LocalUseFinder { tcx, local, found: false };
+        for stmt in stmts {
+            finder.visit_stmt(stmt);
+        }
+        if let Some(expr) = expr {
+            finder.visit_expr(expr);
+        }
+        finder.found
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for LocalUseFinder<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = expr.kind {
+            if path.res == Res::Local(self.local) {
+                self.found = true;
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Finds the innermost statement which unconditionally evaluates the expression at `temp_span`,
+/// along with that expression.
+struct TemporaryStatementFinder<'hir> {
+    temp_span: Span,
+    /// The span of the innermost statement being visited.
+    stmt: Option<Span>,
+    /// Whether the expression being visited may not be evaluated, or only after other
+    /// statements, when `stmt` is.
+    conditional: bool,
+    found: Option<(Span, &'hir hir::Expr<'hir>)>,
+}
+
+impl<'hir> Visitor<'hir> for TemporaryStatementFinder<'hir> {
+    type Map = intravisit::ErasedMap<'hir>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_stmt(&mut self, stmt: &'hir hir::Stmt<'hir>) {
+        let outer_stmt = self.stmt.replace(stmt.span);
+        let conditional = mem::replace(&mut self.conditional, false);
+        intravisit::walk_stmt(self, stmt);
+        self.stmt = outer_stmt;
+        self.conditional = conditional;
+    }
+
+    fn visit_block(&mut self, block: &'hir hir::Block<'hir>) {
+        let conditional = mem::replace(&mut self.conditional, true);
+        intravisit::walk_block(self, block);
+        self.conditional = conditional;
+    }
+
+    fn visit_arm(&mut self, arm: &'hir hir::Arm<'hir>) {
+        let conditional = mem::replace(&mut self.conditional, true);
+        intravisit::walk_arm(self, arm);
+        self.conditional = conditional;
+    }
+
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+        if expr.span == self.temp_span {
+            if self.found.is_none() && !self.conditional {
+                self.found = self.stmt.map(|stmt| (stmt, expr));
+            }
+            return;
+        }
+        match expr.kind {
+            hir::ExprKind::Binary(op, lhs, rhs) if op.node.is_lazy() => {
+                self.visit_expr(lhs);
+                let conditional = mem::replace(&mut self.conditional, true);
+                self.visit_expr(rhs);
+                self.conditional = conditional;
+            }
+            _ => intravisit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Finds whether evaluating an expression may have side effects, that is, whether it calls
+/// anything but constructors of tuple structs and variants, or assigns to anything.
+struct SideEffectFinder<'tcx> {
+    typeck_results: &'tcx ty::TypeckResults<'tcx>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for SideEffectFinder<'tcx> {
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        self.found |= match expr.kind {
+            hir::ExprKind::Call(callee, _) => match callee.kind {
+                hir::ExprKind::Path(ref qpath) => !matches!(
+                    self.typeck_results.qpath_res(qpath, callee.hir_id),
+                    Res::Def(DefKind::Ctor(..), _)
+                ),
+                _ => true,
+            },
+            hir::ExprKind::MethodCall(..)
+            | hir::ExprKind::Assign(..)
+            | hir::ExprKind::AssignOp(..)
+            | hir::ExprKind::InlineAsm(..)
+            | hir::ExprKind::LlvmInlineAsm(..)
+            | hir::ExprKind::Yield(..) => true,
+            // Overloaded operators call their trait methods.
+            _ => self.typeck_results.is_method_call(expr),
+        } || expr.span.from_expansion();
+        if !self.found {
+            intravisit::walk_expr(self, expr);
+        }
+    }
+}
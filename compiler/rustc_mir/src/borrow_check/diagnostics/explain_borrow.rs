@@ -4,8 +4,12 @@
 
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, DiagnosticBuilder};
+use rustc_hir as hir;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_index::vec::IndexVec;
 use rustc_infer::infer::NllRegionVariableOrigin;
+use rustc_middle::hir::map::Map;
+use rustc_middle::middle::region::ScopeData;
 use rustc_middle::mir::{
     Body, CastKind, ConstraintCategory, FakeReadCause, Local, Location, Operand, Place, Rvalue,
     Statement, StatementKind, TerminatorKind,
@@ -30,7 +34,9 @@ pub(in crate::borrow_check) enum BorrowExplanation {
     UsedLaterWhenDropped {
         drop_loc: Location,
         dropped_local: Local,
-        should_note_order: bool,
+        /// The named local whose storage ends before `dropped_local` is dropped, when the
+        /// order in which the two are dropped is what makes the borrow invalid.
+        borrowed_local: Option<Local>,
     },
     MustBeValidFor {
         category: ConstraintCategory,
@@ -131,11 +137,7 @@ pub(in crate::borrow_check) fn add_explanation_to_diagnostic<'tcx>(
                     }
                 }
             }
-            BorrowExplanation::UsedLaterWhenDropped {
-                drop_loc,
-                dropped_local,
-                should_note_order,
-            } => {
+            BorrowExplanation::UsedLaterWhenDropped { drop_loc, dropped_local, borrowed_local } => {
                 let local_decl = &body.local_decls[dropped_local];
                 let (dtor_desc, type_desc) = match local_decl.ty.kind() {
                     // If type is an ADT that implements Drop, then
@@ -164,11 +166,40 @@ pub(in crate::borrow_check) fn add_explanation_to_diagnostic<'tcx>(
                         );
                         err.span_label(body.source_info(drop_loc).span, message);
 
-                        if should_note_order {
+                        if let Some(borrowed_local) = borrowed_local {
                             err.note(
                                 "values in a scope are dropped \
                                  in the opposite order they are defined",
                             );
+                            let borrowed_name = local_names[borrowed_local].unwrap();
+                            if declared_before_in_block(tcx, body, dropped_local, borrowed_local) {
+                                err.help(&format!(
+                                    "consider declaring `{}` before `{}`, so that it is dropped \
+                                     after `{}`",
+                                    borrowed_name, local_name, local_name
+                                ));
+                            }
+                            if let ty::Adt(adt, _) = local_decl.ty.kind() {
+                                match tcx.adt_destructor(adt.did) {
+                                    Some(dtor)
+                                        if dtor.did.is_local()
+                                            && !adt.is_box()
+                                            && !tcx.def_span(dtor.did).from_expansion() =>
+                                    {
+                                        err.span_note(
+                                            tcx.def_span(dtor.did),
+                                            &format!(
+                                                "`Drop::drop` for `{}` runs when `{}` is dropped, \
+                                                 and may use the borrow of `{}`",
+                                                tcx.def_path_str(adt.did),
+                                                local_name,
+                                                borrowed_name
+                                            ),
+                                        );
+                                    }
+                                    _ => {}
+                                }
+                            }
                         }
                     }
                     _ => {
@@ -358,13 +389,13 @@ pub(in crate::borrow_check) fn explain_why_borrow_contains_point(
             }
 
             Some(Cause::DropVar(local, location)) => {
-                let mut should_note_order = false;
+                let mut borrowed_local = None;
                 if self.local_names[local].is_some() {
                     if let Some((WriteKind::StorageDeadOrDrop, place)) = kind_place {
-                        if let Some(borrowed_local) = place.as_local() {
-                            if self.local_names[borrowed_local].is_some() && local != borrowed_local
+                        if let Some(local_in_place) = place.as_local() {
+                            if self.local_names[local_in_place].is_some() && local != local_in_place
                             {
-                                should_note_order = true;
+                                borrowed_local = Some(local_in_place);
                             }
                         }
                     }
@@ -373,7 +404,7 @@ pub(in crate::borrow_check) fn explain_why_borrow_contains_point(
                 BorrowExplanation::UsedLaterWhenDropped {
                     drop_loc: location,
                     dropped_local: local,
-                    should_note_order,
+                    borrowed_local,
                 }
             }
 
@@ -544,11 +575,13 @@ fn later_use_kind(
         location: Location,
     ) -> (LaterUseKind, Span, Option<Span>) {
         match use_spans {
-            UseSpans::ClosureUse { capture_kind_span, path_span, .. } => {
+            UseSpans::ClosureUse { capture_kind_span, path_span, .. }
+            | UseSpans::IteratorChainUse { capture_kind_span, path_span, .. } => {
                 // Used in a closure.
                 (LaterUseKind::ClosureCapture, capture_kind_span, Some(path_span))
             }
             UseSpans::PatUse(span)
+            | UseSpans::GuardUse { binding_span: span, .. }
             | UseSpans::OtherUse(span)
             | UseSpans::FnSelfUse { var_span: span, .. } => {
                 let block = &self.body.basic_blocks()[location.block];
@@ -732,3 +765,51 @@ fn was_captured_by_trait_object(&self, borrow: &BorrowData<'tcx>) -> bool {
         false
     }
 }
+
+/// Returns whether `first` and `second` are both bound by `let` statements of the same block, with
+/// `first` bound before `second`, according to the scope tree. `second` is then dropped first.
+fn declared_before_in_block(tcx: TyCtxt<'_>, body: &Body<'_>, first: Local, second: Local) -> bool {
+    let def_id = tcx.closure_base_def_id(body.source.def_id());
+    let hir_id = tcx.hir().local_def_id_to_hir_id(def_id.expect_local());
+    let hir_body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+    let scope_tree = tcx.region_scope_tree(def_id);
+    let var_scope = |local: Local| {
+        let mut finder =
+            BindingFinder { tcx, span: body.local_decls[local].source_info.span, hir_id: None };
+        finder.visit_body(hir_body);
+        finder.hir_id.map(|hir_id| scope_tree.var_scope(hir_id.local_id))
+    };
+    match (var_scope(first), var_scope(second)) {
+        (Some(first), Some(second)) => match (first.data, second.data) {
+            (ScopeData::Remainder(first_index), ScopeData::Remainder(second_index)) => {
+                first.item_local_id() == second.item_local_id() && first_index < second_index
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Finds the binding pattern with the given span.
+struct BindingFinder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    span: Span,
+    hir_id: Option<hir::HirId>,
+}
+
+impl<'tcx> Visitor<'tcx> for BindingFinder<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        if let hir::PatKind::Binding(..) = pat.kind {
+            if pat.span == self.span {
+                self.hir_id = Some(pat.hir_id);
+            }
+        }
+        intravisit::walk_pat(self, pat);
+    }
+}
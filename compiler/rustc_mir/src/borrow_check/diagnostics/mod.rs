@@ -3,25 +3,22 @@
 use rustc_errors::DiagnosticBuilder;
 use rustc_hir as hir;
 use rustc_hir::def::Namespace;
-use rustc_hir::def_id::DefId;
-use rustc_hir::lang_items::LangItemGroup;
-use rustc_hir::GeneratorKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::hir::place::Place as HirPlace;
 use rustc_middle::mir::{
-    AggregateKind, Constant, FakeReadCause, Field, Local, LocalInfo, LocalKind, Location, Operand,
-    Place, PlaceRef, ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
+    Constant, Field, Local, LocalInfo, Location, Operand, Place, PlaceRef, ProjectionElem, Rvalue,
+    StatementKind, Terminator, TerminatorKind,
 };
 use rustc_middle::ty::print::Print;
-use rustc_middle::ty::{self, DefIdTree, Instance, Ty, TyCtxt};
-use rustc_span::{
-    hygiene::{DesugaringKind, ForLoopLoc},
-    symbol::sym,
-    Span,
-};
+use rustc_middle::ty::{self, DefIdTree, Ty, TyCtxt};
+use rustc_span::{symbol::sym, Span};
 use rustc_target::abi::VariantIdx;
 
 use super::borrow_set::BorrowData;
 use super::MirBorrowckCtxt;
-use crate::dataflow::move_paths::{InitLocation, LookupResult};
+use crate::dataflow::move_paths::{InitLocation, LookupResult, MovePathIndex};
+use crate::util::mutable_uses::{FindMutableUses, MutableUse};
+use crate::util::spans;
 
 mod find_use;
 mod outlives_suggestion;
@@ -34,11 +31,11 @@
 mod mutability_errors;
 mod region_errors;
 
+crate use crate::util::spans::{FnSelfUseKind, UseSpans};
 crate use mutability_errors::AccessKind;
 crate use outlives_suggestion::OutlivesSuggestionBuilder;
 crate use region_errors::{ErrorConstraintInfo, RegionErrorKind, RegionErrors};
 crate use region_name::{RegionName, RegionNameSource};
-use rustc_span::symbol::Ident;
 
 pub(super) struct IncludingDowncast(pub(super) bool);
 
@@ -54,18 +51,83 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
     /// LL |         for (key, value) in dict {
     ///    |                             ^^^^
     /// ```
+    ///
+    /// Returns whether a note was added.
     pub(super) fn add_moved_or_invoked_closure_note(
         &self,
         location: Location,
         place: PlaceRef<'tcx>,
         diag: &mut DiagnosticBuilder<'_>,
-    ) {
+    ) -> bool {
         debug!("add_moved_or_invoked_closure_note: location={:?} place={:?}", location, place);
+        let target = self.follow_moves_in_block(location, place);
+
+        // Check if we are attempting to call a closure after it has been invoked.
+        if let Some(args) = self.fn_once_call_args(location) {
+            let closure = match args.first() {
+                Some(Operand::Copy(ref place)) | Some(Operand::Move(ref place))
+                    if target == place.local_or_deref_local() =>
+                {
+                    place.local_or_deref_local().unwrap()
+                }
+                _ => return false,
+            };
+
+            debug!("add_moved_or_invoked_closure_note: closure={:?}", closure);
+            if let Some((_, span, hir_place)) = self.closure_kind_origin(closure) {
+                diag.span_note(
+                    span,
+                    &format!(
+                        "closure cannot be invoked more than once because it moves the \
+                        variable `{}` out of its environment",
+                        ty::place_to_string_for_capture(self.infcx.tcx, &hir_place)
+                    ),
+                );
+                return true;
+            }
+        }
+
+        // Check if we are just moving a closure after it has been invoked.
+        if let Some((_, span, hir_place)) = target.and_then(|t| self.closure_kind_origin(t)) {
+            diag.span_note(
+                span,
+                &format!(
+                    "closure cannot be moved more than once as it is not `Copy` due to \
+                     moving the variable `{}` out of its environment",
+                    ty::place_to_string_for_capture(self.infcx.tcx, &hir_place)
+                ),
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Returns the closure called by the terminator of the block of `location` through
+    /// `FnOnce`, if it is `place`, as when a closure is called again after a call moved it.
+    pub(super) fn invoked_fn_once_closure(
+        &self,
+        location: Location,
+        place: PlaceRef<'tcx>,
+    ) -> Option<Local> {
+        let target = self.follow_moves_in_block(location, place)?;
+        match self.fn_once_call_args(location)?.first() {
+            Some(Operand::Copy(place) | Operand::Move(place))
+                if place.local_or_deref_local() == Some(target) =>
+            {
+                Some(target)
+            }
+            _ => None,
+        }
+    }
+
+    /// Follows the copies and moves of `place` made by the statements of the block of
+    /// `location` from `location` on, and returns the local it ends up in.
+    fn follow_moves_in_block(&self, location: Location, place: PlaceRef<'tcx>) -> Option<Local> {
         let mut target = place.local_or_deref_local();
         for stmt in &self.body[location.block].statements[location.statement_index..] {
-            debug!("add_moved_or_invoked_closure_note: stmt={:?} target={:?}", stmt, target);
+            debug!("follow_moves_in_block: stmt={:?} target={:?}", stmt, target);
             if let StatementKind::Assign(box (into, Rvalue::Use(from))) = &stmt.kind {
-                debug!("add_fnonce_closure_note: into={:?} from={:?}", into, from);
+                debug!("follow_moves_in_block: into={:?} from={:?}", into, from);
                 match from {
                     Operand::Copy(ref place) | Operand::Move(ref place)
                         if target == place.local_or_deref_local() =>
@@ -76,10 +138,14 @@ pub(super) fn add_moved_or_invoked_closure_note(
                 }
             }
         }
+        target
+    }
 
-        // Check if we are attempting to call a closure after it has been invoked.
+    /// Returns the arguments of the terminator of the block of `location`, if it calls a
+    /// method of `FnOnce`.
+    fn fn_once_call_args(&self, location: Location) -> Option<&[Operand<'tcx>]> {
         let terminator = self.body[location.block].terminator();
-        debug!("add_moved_or_invoked_closure_note: terminator={:?}", terminator);
+        debug!("fn_once_call_args: terminator={:?}", terminator);
         if let TerminatorKind::Call {
             func: Operand::Constant(box Constant { literal, .. }),
             args,
@@ -87,60 +153,37 @@ pub(super) fn add_moved_or_invoked_closure_note(
         } = &terminator.kind
         {
             if let ty::FnDef(id, _) = *literal.ty().kind() {
-                debug!("add_moved_or_invoked_closure_note: id={:?}", id);
+                debug!("fn_once_call_args: id={:?}", id);
                 if self.infcx.tcx.parent(id) == self.infcx.tcx.lang_items().fn_once_trait() {
-                    let closure = match args.first() {
-                        Some(Operand::Copy(ref place)) | Some(Operand::Move(ref place))
-                            if target == place.local_or_deref_local() =>
-                        {
-                            place.local_or_deref_local().unwrap()
-                        }
-                        _ => return,
-                    };
-
-                    debug!("add_moved_or_invoked_closure_note: closure={:?}", closure);
-                    if let ty::Closure(did, _) = self.body.local_decls[closure].ty.kind() {
-                        let did = did.expect_local();
-                        let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(did);
-
-                        if let Some((span, hir_place)) =
-                            self.infcx.tcx.typeck(did).closure_kind_origins().get(hir_id)
-                        {
-                            diag.span_note(
-                                *span,
-                                &format!(
-                                    "closure cannot be invoked more than once because it moves the \
-                                    variable `{}` out of its environment",
-                                    ty::place_to_string_for_capture(self.infcx.tcx, hir_place)
-                                ),
-                            );
-                            return;
-                        }
-                    }
+                    return Some(args);
                 }
             }
         }
+        None
+    }
 
-        // Check if we are just moving a closure after it has been invoked.
-        if let Some(target) = target {
-            if let ty::Closure(did, _) = self.body.local_decls[target].ty.kind() {
-                let did = did.expect_local();
-                let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(did);
-
-                if let Some((span, hir_place)) =
-                    self.infcx.tcx.typeck(did).closure_kind_origins().get(hir_id)
-                {
-                    diag.span_note(
-                        *span,
-                        &format!(
-                            "closure cannot be moved more than once as it is not `Copy` due to \
-                             moving the variable `{}` out of its environment",
-                            ty::place_to_string_for_capture(self.infcx.tcx, hir_place)
-                        ),
-                    );
-                }
-            }
+    /// Returns the definition of the closure `closure` has the type of, along with the
+    /// span and the captured place of the move which makes it `FnOnce`, if any.
+    pub(super) fn closure_kind_origin(
+        &self,
+        closure: Local,
+    ) -> Option<(LocalDefId, Span, HirPlace<'tcx>)> {
+        if let ty::Closure(did, _) = self.body.local_decls[closure].ty.kind() {
+            let did = did.expect_local();
+            let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(did);
+            let typeck_results = self.infcx.tcx.typeck(did);
+            return typeck_results
+                .closure_kind_origins()
+                .get(hir_id)
+                .map(|(span, hir_place)| (did, *span, hir_place.clone()));
         }
+        None
+    }
+
+    /// Tells how `local` is mutated by the body. It is computed for all the locals the
+    /// first time it is needed.
+    pub(super) fn mutable_use(&self, local: Local) -> MutableUse {
+        self.mutable_uses.get_or_init(|| self.body.find_mutable_uses())[local]
     }
 
     /// End-user visible description of `place` if one can be found.
@@ -180,6 +223,32 @@ pub(super) fn describe_place_with_options(
         }
     }
 
+    /// If `place` is based on a local static which is described by its path rather than by its
+    /// name, because other items have the same name, points at the definition of the static.
+    /// The definition of a `static mut` is already pointed at by the note added to move errors.
+    pub(super) fn label_static_definition(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        place: PlaceRef<'tcx>,
+    ) {
+        let def_id = match self.body.local_decls[place.local].local_info {
+            Some(box LocalInfo::StaticRef { def_id, .. }) => def_id,
+            _ => return,
+        };
+        let tcx = self.infcx.tcx;
+        if !def_id.is_local() || tcx.is_mutable_static(def_id) {
+            return;
+        }
+        let path = tcx.def_path_str(def_id);
+        if path == &*tcx.item_name(def_id).as_str() {
+            return;
+        }
+        err.span_label(
+            tcx.sess.source_map().guess_head_span(tcx.def_span(def_id)),
+            format!("`{}` is defined here", path),
+        );
+    }
+
     /// Appends end-user visible description of `place` to `buf`.
     fn append_place_to_string(
         &self,
@@ -207,7 +276,7 @@ fn append_place_to_string(
             {
                 let local_info = &self.body.local_decls[local].local_info;
                 if let Some(box LocalInfo::StaticRef { def_id, .. }) = *local_info {
-                    buf.push_str(&self.infcx.tcx.item_name(def_id).as_str());
+                    buf.push_str(&self.infcx.tcx.def_path_str(def_id));
                 } else {
                     unreachable!();
                 }
@@ -266,8 +335,10 @@ fn append_place_to_string(
                             let name = self.upvars[var_index].place.to_string(self.infcx.tcx);
                             buf.push_str(&name);
                         } else {
+                            // Fields that cannot be described are referred to by their index.
                             let field_name = self
-                                .describe_field(PlaceRef { local, projection: proj_base }, *field);
+                                .describe_field(PlaceRef { local, projection: proj_base }, *field)
+                                .unwrap_or_else(|| field.index().to_string());
                             self.append_place_to_string(
                                 PlaceRef { local, projection: proj_base },
                                 buf,
@@ -326,8 +397,9 @@ fn append_local_to_string(&self, local: Local, buf: &mut String) -> Result<(), (
         }
     }
 
-    /// End-user visible description of the `field`nth field of `base`
-    fn describe_field(&self, place: PlaceRef<'tcx>, field: Field) -> String {
+    /// End-user visible description of the `field`nth field of `base`, or `None` if it cannot
+    /// be described.
+    fn describe_field(&self, place: PlaceRef<'tcx>, field: Field) -> Option<String> {
         // FIXME Place2 Make this work iteratively
         match place {
             PlaceRef { local, projection: [] } => {
@@ -354,13 +426,14 @@ fn describe_field(&self, place: PlaceRef<'tcx>, field: Field) -> String {
         }
     }
 
-    /// End-user visible description of the `field_index`nth field of `ty`
+    /// End-user visible description of the `field_index`nth field of `ty`, or `None` if fields
+    /// of `ty` cannot be described.
     fn describe_field_from_ty(
         &self,
         ty: Ty<'_>,
         field: Field,
         variant_index: Option<VariantIdx>,
-    ) -> String {
+    ) -> Option<String> {
         if ty.is_box() {
             // If the type is a box, the field is described from the boxed type
             self.describe_field_from_ty(&ty.boxed_ty(), field, variant_index)
@@ -373,9 +446,9 @@ fn describe_field_from_ty(
                     } else {
                         def.non_enum_variant()
                     };
-                    variant.fields[field.index()].ident.to_string()
+                    Some(variant.fields.get(field.index())?.ident.to_string())
                 }
-                ty::Tuple(_) => field.index().to_string(),
+                ty::Tuple(_) => Some(field.index().to_string()),
                 ty::Ref(_, ty, _) | ty::RawPtr(ty::TypeAndMut { ty, .. }) => {
                     self.describe_field_from_ty(&ty, field, variant_index)
                 }
@@ -383,26 +456,21 @@ fn describe_field_from_ty(
                     self.describe_field_from_ty(&ty, field, variant_index)
                 }
                 ty::Closure(def_id, _) | ty::Generator(def_id, _, _) => {
-                    // We won't be borrowck'ing here if the closure came from another crate,
-                    // so it's safe to call `expect_local`.
-                    //
-                    // We know the field exists so it's safe to call operator[] and `unwrap` here.
+                    // The captures of closures and generators from other crates are not known.
+                    let local_def_id = def_id.as_local()?;
                     let var_id = self
                         .infcx
                         .tcx
-                        .typeck(def_id.expect_local())
+                        .typeck(local_def_id)
                         .closure_min_captures_flattened(def_id)
-                        .nth(field.index())
-                        .unwrap()
+                        .nth(field.index())?
                         .get_root_variable();
 
-                    self.infcx.tcx.hir().name(var_id).to_string()
-                }
-                _ => {
-                    // Might need a revision when the fields in trait RFC is implemented
-                    // (https://github.com/rust-lang/rfcs/pull/1546)
-                    bug!("End-user description not implemented for field access on `{:?}`", ty);
+                    Some(self.infcx.tcx.hir().name(var_id).to_string())
                 }
+                // Might need a revision when the fields in trait RFC is implemented
+                // (https://github.com/rust-lang/rfcs/pull/1546)
+                _ => None,
             }
         }
     }
@@ -538,194 +606,6 @@ pub(super) fn get_region_name_for_ty(&self, ty: Ty<'tcx>, counter: usize) -> Str
     }
 }
 
-/// The span(s) associated to a use of a place.
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-pub(super) enum UseSpans<'tcx> {
-    /// The access is caused by capturing a variable for a closure.
-    ClosureUse {
-        /// This is true if the captured variable was from a generator.
-        generator_kind: Option<GeneratorKind>,
-        /// The span of the args of the closure, including the `move` keyword if
-        /// it's present.
-        args_span: Span,
-        /// The span of the use resulting in capture kind
-        /// Check `ty::CaptureInfo` for more details
-        capture_kind_span: Span,
-        /// The span of the use resulting in the captured path
-        /// Check `ty::CaptureInfo` for more details
-        path_span: Span,
-    },
-    /// The access is caused by using a variable as the receiver of a method
-    /// that takes 'self'
-    FnSelfUse {
-        /// The span of the variable being moved
-        var_span: Span,
-        /// The span of the method call on the variable
-        fn_call_span: Span,
-        /// The definition span of the method being called
-        fn_span: Span,
-        kind: FnSelfUseKind<'tcx>,
-    },
-    /// This access is caused by a `match` or `if let` pattern.
-    PatUse(Span),
-    /// This access has a single span associated to it: common case.
-    OtherUse(Span),
-}
-
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-pub(super) enum FnSelfUseKind<'tcx> {
-    /// A normal method call of the form `receiver.foo(a, b, c)`
-    Normal {
-        self_arg: Ident,
-        implicit_into_iter: bool,
-        /// Whether the self type of the method call has an `.as_ref()` method.
-        /// Used for better diagnostics.
-        is_option_or_result: bool,
-    },
-    /// A call to `FnOnce::call_once`, desugared from `my_closure(a, b, c)`
-    FnOnceCall,
-    /// A call to an operator trait, desuraged from operator syntax (e.g. `a << b`)
-    Operator { self_arg: Ident },
-    DerefCoercion {
-        /// The `Span` of the `Target` associated type
-        /// in the `Deref` impl we are using.
-        deref_target: Span,
-        /// The type `T::Deref` we are dereferencing to
-        deref_target_ty: Ty<'tcx>,
-    },
-}
-
-impl UseSpans<'_> {
-    pub(super) fn args_or_use(self) -> Span {
-        match self {
-            UseSpans::ClosureUse { args_span: span, .. }
-            | UseSpans::PatUse(span)
-            | UseSpans::OtherUse(span) => span,
-            UseSpans::FnSelfUse {
-                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
-            } => fn_call_span,
-            UseSpans::FnSelfUse { var_span, .. } => var_span,
-        }
-    }
-
-    /// Returns the span of `self`, in the case of a `ClosureUse` returns the `path_span`
-    pub(super) fn var_or_use_path_span(self) -> Span {
-        match self {
-            UseSpans::ClosureUse { path_span: span, .. }
-            | UseSpans::PatUse(span)
-            | UseSpans::OtherUse(span) => span,
-            UseSpans::FnSelfUse {
-                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
-            } => fn_call_span,
-            UseSpans::FnSelfUse { var_span, .. } => var_span,
-        }
-    }
-
-    /// Returns the span of `self`, in the case of a `ClosureUse` returns the `capture_kind_span`
-    pub(super) fn var_or_use(self) -> Span {
-        match self {
-            UseSpans::ClosureUse { capture_kind_span: span, .. }
-            | UseSpans::PatUse(span)
-            | UseSpans::OtherUse(span) => span,
-            UseSpans::FnSelfUse {
-                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
-            } => fn_call_span,
-            UseSpans::FnSelfUse { var_span, .. } => var_span,
-        }
-    }
-
-    pub(super) fn generator_kind(self) -> Option<GeneratorKind> {
-        match self {
-            UseSpans::ClosureUse { generator_kind, .. } => generator_kind,
-            _ => None,
-        }
-    }
-
-    // Add a span label to the arguments of the closure, if it exists.
-    pub(super) fn args_span_label(
-        self,
-        err: &mut DiagnosticBuilder<'_>,
-        message: impl Into<String>,
-    ) {
-        if let UseSpans::ClosureUse { args_span, .. } = self {
-            err.span_label(args_span, message);
-        }
-    }
-
-    // Add a span label to the use of the captured variable, if it exists.
-    // only adds label to the `path_span`
-    pub(super) fn var_span_label_path_only(
-        self,
-        err: &mut DiagnosticBuilder<'_>,
-        message: impl Into<String>,
-    ) {
-        if let UseSpans::ClosureUse { path_span, .. } = self {
-            err.span_label(path_span, message);
-        }
-    }
-
-    // Add a span label to the use of the captured variable, if it exists.
-    pub(super) fn var_span_label(
-        self,
-        err: &mut DiagnosticBuilder<'_>,
-        message: impl Into<String>,
-        kind_desc: impl Into<String>,
-    ) {
-        if let UseSpans::ClosureUse { capture_kind_span, path_span, .. } = self {
-            if capture_kind_span == path_span {
-                err.span_label(capture_kind_span, message);
-            } else {
-                let capture_kind_label =
-                    format!("capture is {} because of use here", kind_desc.into());
-                let path_label = message;
-                err.span_label(capture_kind_span, capture_kind_label);
-                err.span_label(path_span, path_label);
-            }
-        }
-    }
-
-    /// Returns `false` if this place is not used in a closure.
-    pub(super) fn for_closure(&self) -> bool {
-        match *self {
-            UseSpans::ClosureUse { generator_kind, .. } => generator_kind.is_none(),
-            _ => false,
-        }
-    }
-
-    /// Returns `false` if this place is not used in a generator.
-    pub(super) fn for_generator(&self) -> bool {
-        match *self {
-            UseSpans::ClosureUse { generator_kind, .. } => generator_kind.is_some(),
-            _ => false,
-        }
-    }
-
-    /// Describe the span associated with a use of a place.
-    pub(super) fn describe(&self) -> String {
-        match *self {
-            UseSpans::ClosureUse { generator_kind, .. } => {
-                if generator_kind.is_some() {
-                    " in generator".to_string()
-                } else {
-                    " in closure".to_string()
-                }
-            }
-            _ => String::new(),
-        }
-    }
-
-    pub(super) fn or_else<F>(self, if_other: F) -> Self
-    where
-        F: FnOnce() -> Self,
-    {
-        match self {
-            closure @ UseSpans::ClosureUse { .. } => closure,
-            UseSpans::PatUse(_) | UseSpans::OtherUse(_) => if_other(),
-            fn_self @ UseSpans::FnSelfUse { .. } => fn_self,
-        }
-    }
-}
-
 pub(super) enum BorrowedContentSource<'tcx> {
     DerefRawPointer,
     DerefMutableRef,
@@ -815,278 +695,57 @@ pub(super) fn move_spans(
         moved_place: PlaceRef<'tcx>, // Could also be an upvar.
         location: Location,
     ) -> UseSpans<'tcx> {
-        use self::UseSpans::*;
-
-        let stmt = match self.body[location.block].statements.get(location.statement_index) {
-            Some(stmt) => stmt,
-            None => return OtherUse(self.body.source_info(location).span),
-        };
-
-        debug!("move_spans: moved_place={:?} location={:?} stmt={:?}", moved_place, location, stmt);
-        if let StatementKind::Assign(box (_, Rvalue::Aggregate(ref kind, ref places))) = stmt.kind {
-            match kind {
-                box AggregateKind::Closure(def_id, _)
-                | box AggregateKind::Generator(def_id, _, _) => {
-                    debug!("move_spans: def_id={:?} places={:?}", def_id, places);
-                    if let Some((args_span, generator_kind, capture_kind_span, path_span)) =
-                        self.closure_span(*def_id, moved_place, places)
-                    {
-                        return ClosureUse {
-                            generator_kind,
-                            args_span,
-                            capture_kind_span,
-                            path_span,
-                        };
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        // StatementKind::FakeRead only contains a def_id if they are introduced as a result
-        // of pattern matching within a closure.
-        if let StatementKind::FakeRead(box (cause, ref place)) = stmt.kind {
-            match cause {
-                FakeReadCause::ForMatchedPlace(Some(closure_def_id))
-                | FakeReadCause::ForLet(Some(closure_def_id)) => {
-                    debug!("move_spans: def_id={:?} place={:?}", closure_def_id, place);
-                    let places = &[Operand::Move(*place)];
-                    if let Some((args_span, generator_kind, capture_kind_span, path_span)) =
-                        self.closure_span(closure_def_id, moved_place, places)
-                    {
-                        return ClosureUse {
-                            generator_kind,
-                            args_span,
-                            capture_kind_span,
-                            path_span,
-                        };
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        let normal_ret =
-            if moved_place.projection.iter().any(|p| matches!(p, ProjectionElem::Downcast(..))) {
-                PatUse(stmt.source_info.span)
-            } else {
-                OtherUse(stmt.source_info.span)
-            };
-
-        // We are trying to find MIR of the form:
-        // ```
-        // _temp = _moved_val;
-        // ...
-        // FnSelfCall(_temp, ...)
-        // ```
-        //
-        // where `_moved_val` is the place we generated the move error for,
-        // `_temp` is some other local, and `FnSelfCall` is a function
-        // that has a `self` parameter.
-
-        let target_temp = match stmt.kind {
-            StatementKind::Assign(box (temp, _)) if temp.as_local().is_some() => {
-                temp.as_local().unwrap()
-            }
-            _ => return normal_ret,
-        };
-
-        debug!("move_spans: target_temp = {:?}", target_temp);
-
-        if let Some(Terminator {
-            kind: TerminatorKind::Call { fn_span, from_hir_call, .. }, ..
-        }) = &self.body[location.block].terminator
-        {
-            let (method_did, method_substs) = if let Some(info) =
-                crate::util::find_self_call(self.infcx.tcx, &self.body, target_temp, location.block)
-            {
-                info
-            } else {
-                return normal_ret;
-            };
-
-            let tcx = self.infcx.tcx;
-            let parent = tcx.parent(method_did);
-            let is_fn_once = parent == tcx.lang_items().fn_once_trait();
-            let is_operator = !from_hir_call
-                && parent.map_or(false, |p| tcx.lang_items().group(LangItemGroup::Op).contains(&p));
-            let is_deref = !from_hir_call && tcx.is_diagnostic_item(sym::deref_method, method_did);
-            let fn_call_span = *fn_span;
-
-            let self_arg = tcx.fn_arg_names(method_did)[0];
-
-            debug!(
-                "terminator = {:?} from_hir_call={:?}",
-                self.body[location.block].terminator, from_hir_call
-            );
-
-            // Check for a 'special' use of 'self' -
-            // an FnOnce call, an operator (e.g. `<<`), or a
-            // deref coercion.
-            let kind = if is_fn_once {
-                Some(FnSelfUseKind::FnOnceCall)
-            } else if is_operator {
-                Some(FnSelfUseKind::Operator { self_arg })
-            } else if is_deref {
-                let deref_target =
-                    tcx.get_diagnostic_item(sym::deref_target).and_then(|deref_target| {
-                        Instance::resolve(tcx, self.param_env, deref_target, method_substs)
-                            .transpose()
-                    });
-                if let Some(Ok(instance)) = deref_target {
-                    let deref_target_ty = instance.ty(tcx, self.param_env);
-                    Some(FnSelfUseKind::DerefCoercion {
-                        deref_target: tcx.def_span(instance.def_id()),
-                        deref_target_ty,
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            let kind = kind.unwrap_or_else(|| {
-                // This isn't a 'special' use of `self`
-                debug!("move_spans: method_did={:?}, fn_call_span={:?}", method_did, fn_call_span);
-                let implicit_into_iter = matches!(
-                    fn_call_span.desugaring_kind(),
-                    Some(DesugaringKind::ForLoop(ForLoopLoc::IntoIter))
-                );
-                let parent_self_ty = parent
-                    .filter(|did| tcx.def_kind(*did) == rustc_hir::def::DefKind::Impl)
-                    .and_then(|did| match tcx.type_of(did).kind() {
-                        ty::Adt(def, ..) => Some(def.did),
-                        _ => None,
-                    });
-                let is_option_or_result = parent_self_ty.map_or(false, |def_id| {
-                    tcx.is_diagnostic_item(sym::option_type, def_id)
-                        || tcx.is_diagnostic_item(sym::result_type, def_id)
-                });
-                FnSelfUseKind::Normal { self_arg, implicit_into_iter, is_option_or_result }
-            });
-
-            return FnSelfUse {
-                var_span: stmt.source_info.span,
-                fn_call_span,
-                fn_span: self
-                    .infcx
-                    .tcx
-                    .sess
-                    .source_map()
-                    .guess_head_span(self.infcx.tcx.def_span(method_did)),
-                kind,
-            };
-        }
-        normal_ret
+        spans::move_spans(self.infcx.tcx, self.body, self.param_env, moved_place, location)
     }
 
     /// Finds the span of arguments of a closure (within `maybe_closure_span`)
     /// and its usage of the local assigned at `location`.
-    /// This is done by searching in statements succeeding `location`
-    /// and originating from `maybe_closure_span`.
     pub(super) fn borrow_spans(&self, use_span: Span, location: Location) -> UseSpans<'tcx> {
-        use self::UseSpans::*;
-        debug!("borrow_spans: use_span={:?} location={:?}", use_span, location);
-
-        let target = match self.body[location.block].statements.get(location.statement_index) {
-            Some(&Statement { kind: StatementKind::Assign(box (ref place, _)), .. }) => {
-                if let Some(local) = place.as_local() {
-                    local
-                } else {
-                    return OtherUse(use_span);
-                }
-            }
-            _ => return OtherUse(use_span),
-        };
-
-        if self.body.local_kind(target) != LocalKind::Temp {
-            // operands are always temporaries.
-            return OtherUse(use_span);
-        }
-
-        for stmt in &self.body[location.block].statements[location.statement_index + 1..] {
-            if let StatementKind::Assign(box (_, Rvalue::Aggregate(ref kind, ref places))) =
-                stmt.kind
-            {
-                let (def_id, is_generator) = match kind {
-                    box AggregateKind::Closure(def_id, _) => (def_id, false),
-                    box AggregateKind::Generator(def_id, _, _) => (def_id, true),
-                    _ => continue,
-                };
-
-                debug!(
-                    "borrow_spans: def_id={:?} is_generator={:?} places={:?}",
-                    def_id, is_generator, places
-                );
-                if let Some((args_span, generator_kind, capture_kind_span, path_span)) =
-                    self.closure_span(*def_id, Place::from(target).as_ref(), places)
-                {
-                    return ClosureUse { generator_kind, args_span, capture_kind_span, path_span };
-                } else {
-                    return OtherUse(use_span);
-                }
-            }
-
-            if use_span != stmt.source_info.span {
-                break;
-            }
-        }
+        spans::borrow_spans(self.infcx.tcx, self.body, use_span, location)
+    }
 
-        OtherUse(use_span)
+    /// Helper to retrieve span(s) of given borrow from the current MIR
+    /// representation
+    pub(super) fn retrieve_borrow_spans(&self, borrow: &BorrowData<'_>) -> UseSpans<'tcx> {
+        let span = self.body.source_info(borrow.reserve_location).span;
+        self.borrow_spans(span, borrow.reserve_location)
     }
 
-    /// Finds the spans of a captured place within a closure or generator.
-    /// The first span is the location of the use resulting in the capture kind of the capture
-    /// The second span is the location the use resulting in the captured path of the capture
-    fn closure_span(
+    /// Records what an error is about, like the place it moves or the borrow it conflicts
+    /// with, in the JSON output. Together with the body they are from, these let tools
+    /// recognize the same error across compilations.
+    pub(super) fn add_error_metadata(
         &self,
-        def_id: DefId,
-        target_place: PlaceRef<'tcx>,
-        places: &[Operand<'tcx>],
-    ) -> Option<(Span, Option<GeneratorKind>, Span, Span)> {
-        debug!(
-            "closure_span: def_id={:?} target_place={:?} places={:?}",
-            def_id, target_place, places
-        );
-        let local_did = def_id.as_local()?;
-        let hir_id = self.infcx.tcx.hir().local_def_id_to_hir_id(local_did);
-        let expr = &self.infcx.tcx.hir().expect_expr(hir_id).kind;
-        debug!("closure_span: hir_id={:?} expr={:?}", hir_id, expr);
-        if let hir::ExprKind::Closure(.., body_id, args_span, _) = expr {
-            for (captured_place, place) in self
-                .infcx
-                .tcx
-                .typeck(def_id.expect_local())
-                .closure_min_captures_flattened(def_id)
-                .zip(places)
-            {
-                match place {
-                    Operand::Copy(place) | Operand::Move(place)
-                        if target_place == place.as_ref() =>
-                    {
-                        debug!("closure_span: found captured local {:?}", place);
-                        let body = self.infcx.tcx.hir().body(*body_id);
-                        let generator_kind = body.generator_kind();
-
-                        return Some((
-                            *args_span,
-                            generator_kind,
-                            captured_place.get_capture_kind_span(self.infcx.tcx),
-                            captured_place.get_path_span(self.infcx.tcx),
-                        ));
-                    }
-                    _ => {}
-                }
-            }
+        err: &mut DiagnosticBuilder<'_>,
+        entries: &[(&str, String)],
+    ) {
+        err.tool_metadata("body", self.infcx.tcx.def_path_str(self.mir_def_id().to_def_id()));
+        for (key, value) in entries {
+            err.tool_metadata(key, value.clone());
         }
-        None
     }
 
-    /// Helper to retrieve span(s) of given borrow from the current MIR
-    /// representation
-    pub(super) fn retrieve_borrow_spans(&self, borrow: &BorrowData<'_>) -> UseSpans<'tcx> {
+    /// Identifies a move path by its place, as move path indices depend on the order in
+    /// which the places of the body are visited.
+    pub(super) fn move_path_key(&self, mpi: MovePathIndex) -> String {
+        let place = self.move_data.move_paths[mpi].place;
+        self.describe_place(place.as_ref()).unwrap_or_else(|| "_".to_owned())
+    }
+
+    /// Identifies a borrow by its kind, its place and its position relative to the start
+    /// of the body, e.g. "mutable borrow of v at 2:13", as borrow indices depend on the
+    /// other borrows of the body.
+    pub(super) fn borrow_key(&self, borrow: &BorrowData<'_>) -> String {
+        let source_map = self.infcx.tcx.sess.source_map();
         let span = self.body.source_info(borrow.reserve_location).span;
-        self.borrow_spans(span, borrow.reserve_location)
+        let start = source_map.lookup_char_pos(self.body.span.lo());
+        let loc = source_map.lookup_char_pos(span.lo());
+        format!(
+            "{} borrow of {} at {}:{}",
+            borrow.kind.describe_mutability(),
+            self.describe_place(borrow.borrowed_place.as_ref()).unwrap_or_else(|| "_".to_owned()),
+            loc.line.saturating_sub(start.line),
+            loc.col.0 + 1,
+        )
     }
 }
@@ -1,14 +1,16 @@
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_middle::mir::*;
-use rustc_middle::ty;
+use rustc_middle::ty::{self, Ty, TypeFoldable};
 use rustc_span::source_map::DesugaringKind;
-use rustc_span::{sym, Span};
+use rustc_span::symbol::kw;
+use rustc_span::{sym, Span, Symbol};
 
 use crate::borrow_check::diagnostics::UseSpans;
 use crate::borrow_check::prefixes::PrefixSet;
 use crate::borrow_check::MirBorrowckCtxt;
 use crate::dataflow::move_paths::{
-    IllegalMoveOrigin, IllegalMoveOriginKind, LookupResult, MoveError, MovePathIndex,
+    IllegalMoveOrigin, IllegalMoveOriginKind, InitLocation, LookupResult, MoveError,
+    MovePathIndex,
 };
 
 // Often when desugaring a pattern match we may have many individual moves in
@@ -252,7 +254,7 @@ fn report(&mut self, error: GroupedMoveError<'tcx>) {
                             use_spans,
                         ),
                     IllegalMoveOriginKind::InteriorOfTypeWithDestructor { container_ty: ty } => {
-                        self.cannot_move_out_of_interior_of_drop(span, ty)
+                        self.report_cannot_move_from_drop_type(original_path, span, ty)
                     }
                     IllegalMoveOriginKind::InteriorOfSliceOrArray { ty, is_index } => {
                         self.cannot_move_out_of_interior_noncopy(span, ty, Some(*is_index))
@@ -283,7 +285,90 @@ fn report_cannot_move_from_static(
             )
         };
 
-        self.cannot_move_out_of(span, &description)
+        let mut err = self.cannot_move_out_of(span, &description);
+        self.add_static_mut_note(&mut err, place.as_ref());
+        self.label_static_definition(&mut err, place.as_ref());
+        err
+    }
+
+    /// If `place` is based on a `static mut`, points at the static and explains that the
+    /// `unsafe` block needed to access it doesn't allow moving out of it.
+    fn add_static_mut_note(&self, err: &mut DiagnosticBuilder<'_>, place: PlaceRef<'tcx>) {
+        let def_id = match self.body.local_decls[place.local].local_info {
+            Some(box LocalInfo::StaticRef { def_id, .. }) => def_id,
+            _ => return,
+        };
+        let tcx = self.infcx.tcx;
+        if !tcx.is_mutable_static(def_id) {
+            return;
+        }
+        err.span_note(
+            tcx.sess.source_map().guess_head_span(tcx.def_span(def_id)),
+            &format!(
+                "`{}` is a `static mut`, so it can be read and assigned inside `unsafe`, but \
+                 values cannot be moved out of it",
+                tcx.def_path_str(def_id)
+            ),
+        );
+    }
+
+    fn report_cannot_move_from_drop_type(
+        &mut self,
+        move_place: Place<'tcx>,
+        span: Span,
+        container_ty: Ty<'tcx>,
+    ) -> DiagnosticBuilder<'a> {
+        let mut err = self.cannot_move_out_of_interior_of_drop(span, container_ty);
+
+        // Moving a field out of `self` is usually an attempt to consume the value in one of
+        // its methods, explain how to get the field out anyway.
+        if self.local_names[move_place.local] != Some(kw::SelfLower)
+            || !matches!(move_place.projection.last(), Some(ProjectionElem::Field(..)))
+        {
+            return err;
+        }
+        err.note(&format!(
+            "the destructor of `{}` still needs its fields once `self` goes out of scope",
+            container_ty,
+        ));
+
+        let tcx = self.infcx.tcx;
+        let snippet = match tcx.sess.source_map().span_to_snippet(span) {
+            Ok(snippet) => snippet,
+            Err(_) => return err,
+        };
+        let field_ty = tcx.erase_regions(move_place.ty(self.body, tcx).ty);
+        let (msg, replacement) = match field_ty.kind() {
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::option_type, def.did) => (
+                "consider taking the value out of the `Option`, leaving `None` in its place",
+                format!("{}.take()", snippet),
+            ),
+            _ if tcx.get_diagnostic_item(sym::Default).map_or(false, |default| {
+                tcx.type_implements_trait((default, field_ty, ty::List::empty(), self.param_env))
+            }) =>
+            {
+                (
+                    "consider taking the value, leaving its default value in its place",
+                    format!("std::mem::take(&mut {})", snippet),
+                )
+            }
+            _ => {
+                err.help(
+                    "consider using `std::mem::replace` to move the value out while putting \
+                     another value in its place",
+                );
+                return err;
+            }
+        };
+
+        // Both suggestions need to mutate `self`.
+        let self_decl = &self.body.local_decls[move_place.local];
+        let mut suggestion = vec![(span, replacement)];
+        if self_decl.mutability == Mutability::Not {
+            suggestion.push((self_decl.source_info.span.shrink_to_lo(), "mut ".to_string()));
+        }
+        err.multipart_suggestion(msg, suggestion, Applicability::MaybeIncorrect);
+        err
     }
 
     fn report_cannot_move_from_borrowed_content(
@@ -384,6 +469,9 @@ fn report_cannot_move_from_borrowed_content(
                 }
             }
         };
+        if self.infcx.tcx.features().unsafe_pinned {
+            self.add_move_out_of_pinned_note(&mut err, move_place, deref_base);
+        }
         if let Ok(snippet) = self.infcx.tcx.sess.source_map().span_to_snippet(span) {
             let def_id = match *move_place.ty(self.body, self.infcx.tcx).ty.kind() {
                 ty::Adt(self_def, _) => self_def.did,
@@ -421,6 +509,77 @@ fn report_cannot_move_from_borrowed_content(
         err
     }
 
+    /// Notes that moving out of `move_place` may violate pinning guarantees, when its type does
+    /// not implement `Unpin` or when `deref_base` was obtained from `Pin::get_unchecked_mut`
+    /// or `Pin::map_unchecked_mut`.
+    fn add_move_out_of_pinned_note(
+        &self,
+        err: &mut DiagnosticBuilder<'a>,
+        move_place: Place<'tcx>,
+        deref_base: PlaceRef<'tcx>,
+    ) {
+        let tcx = self.infcx.tcx;
+        let ty = tcx.erase_regions(move_place.ty(self.body, tcx).ty);
+        // Type parameters and projections usually aren't bounded by `Unpin`, which doesn't make
+        // them any more likely to be pinned than other types.
+        let is_unpin = ty.has_param_types_or_consts()
+            || tcx.lang_items().unpin_trait().map_or(true, |unpin| {
+                tcx.type_implements_trait((unpin, ty, ty::List::empty(), self.param_env))
+            });
+        if !is_unpin {
+            err.note(&format!(
+                "`{}` does not implement `Unpin`, so it may be pinned and moving it out could \
+                 violate its pinning guarantees",
+                ty,
+            ));
+        } else if let Some(method) = self.pin_unchecked_source(deref_base) {
+            err.note(&format!(
+                "this value was obtained through `Pin::{}`, so moving out of it could violate \
+                 pinning guarantees",
+                method,
+            ));
+        }
+    }
+
+    /// Returns the name of the `Pin` method `deref_base` was initialized by, if it is one of
+    /// those giving out unchecked access to the pinned value.
+    fn pin_unchecked_source(&self, deref_base: PlaceRef<'tcx>) -> Option<Symbol> {
+        let tcx = self.infcx.tcx;
+        let mpi = match self.move_data.rev_lookup.find(deref_base) {
+            LookupResult::Exact(mpi) => mpi,
+            LookupResult::Parent(_) => return None,
+        };
+        self.move_data.init_path_map[mpi].iter().find_map(|&init| {
+            let loc = match self.move_data.inits[init].location {
+                InitLocation::Statement(loc) => loc,
+                InitLocation::Argument(_) => return None,
+            };
+            let func = match &self.body[loc.block].terminator {
+                Some(Terminator { kind: TerminatorKind::Call { func, .. }, .. })
+                    if self.body[loc.block].statements.len() == loc.statement_index =>
+                {
+                    func
+                }
+                _ => return None,
+            };
+            let def_id = match *func.ty(self.body, tcx).kind() {
+                ty::FnDef(def_id, _) => def_id,
+                _ => return None,
+            };
+            let impl_ty = tcx.type_of(tcx.impl_of_method(def_id)?);
+            let name = tcx.item_name(def_id);
+            match impl_ty.kind() {
+                ty::Adt(def, _)
+                    if Some(def.did) == tcx.lang_items().pin_type()
+                        && (name == sym::get_unchecked_mut || name == sym::map_unchecked_mut) =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            }
+        })
+    }
+
     fn add_move_hints(
         &self,
         error: GroupedMoveError<'tcx>,
@@ -478,7 +637,7 @@ fn add_move_hints(
                 use_spans.args_span_label(err, format!("move out of {} occurs here", place_desc));
                 use_spans.var_span_label(
                     err,
-                    format!("move occurs due to use{}", use_spans.describe()),
+                    format!("move occurs due to use{}", use_spans.describe_captured()),
                     "moved",
                 );
             }
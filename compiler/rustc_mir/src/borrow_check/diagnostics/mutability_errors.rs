@@ -14,6 +14,7 @@
 use crate::borrow_check::diagnostics::BorrowedContentSource;
 use crate::borrow_check::MirBorrowckCtxt;
 use crate::util::collect_writes::FindAssignments;
+use crate::util::mutable_uses::Writes;
 use rustc_errors::{Applicability, DiagnosticBuilder};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -106,7 +107,7 @@ pub(crate) fn report_mutability_error(
                     item_msg = format!("`{}`", access_place_desc.unwrap());
                     let local_info = &self.body.local_decls[local].local_info;
                     if let Some(box LocalInfo::StaticRef { def_id, .. }) = *local_info {
-                        let static_name = &self.infcx.tcx.item_name(def_id);
+                        let static_name = self.infcx.tcx.def_path_str(def_id);
                         reason = format!(", as `{}` is an immutable static item", static_name);
                     } else {
                         bug!("is_ref_to_static return true, but not ref to static?");
@@ -427,6 +428,13 @@ pub(crate) fn report_mutability_error(
                             Some((true, err_help_span, suggested_code)) => {
                                 let (is_trait_sig, local_trait) = self.is_error_in_trait(local);
                                 if !is_trait_sig {
+                                    // Only the first assignment of the reference is changed.
+                                    let applicability = match self.mutable_use(local).writes {
+                                        Writes::None | Writes::Once(_) => {
+                                            Applicability::MachineApplicable
+                                        }
+                                        Writes::Several => Applicability::MaybeIncorrect,
+                                    };
                                     err.span_suggestion(
                                         err_help_span,
                                         &format!(
@@ -434,8 +442,23 @@ pub(crate) fn report_mutability_error(
                                             pointer_desc
                                         ),
                                         suggested_code,
-                                        Applicability::MachineApplicable,
+                                        applicability,
                                     );
+                                    if let Some(borrowed) =
+                                        self.immutable_binding_borrowed_by(local)
+                                    {
+                                        let borrowed_name = self.local_names[borrowed].unwrap();
+                                        err.span_suggestion(
+                                            self.body.local_decls[borrowed].source_info.span,
+                                            &format!(
+                                                "consider also making `{}` mutable, so that it \
+                                                 can be borrowed mutably",
+                                                borrowed_name
+                                            ),
+                                            format!("mut {}", borrowed_name),
+                                            applicability,
+                                        );
+                                    }
                                 } else if let Some(x) = local_trait {
                                     err.span_suggestion(
                                         x,
@@ -512,9 +535,37 @@ pub(crate) fn report_mutability_error(
             }
         }
 
+        self.label_static_definition(&mut err, the_place_err);
         err.buffer(&mut self.errors_buffer);
     }
 
+    /// Returns the immutable binding which `local` is a reference to, if `local` is only
+    /// assigned once, with a shared borrow of the whole binding, as in `let r = &x;`.
+    fn immutable_binding_borrowed_by(&self, local: Local) -> Option<Local> {
+        let location = match self.mutable_use(local).writes {
+            Writes::Once(location) => location,
+            Writes::None | Writes::Several => return None,
+        };
+        let statement = self.body[location.block].statements.get(location.statement_index)?;
+        let borrowed = match &statement.kind {
+            mir::StatementKind::Assign(box (
+                _,
+                mir::Rvalue::Ref(_, mir::BorrowKind::Shared, borrowed),
+            )) => borrowed.as_local()?,
+            _ => return None,
+        };
+        let decl = &self.body.local_decls[borrowed];
+        if decl.mutability == Mutability::Not
+            && decl.can_be_made_mutable()
+            && self.local_names[borrowed].is_some()
+            && !decl.source_info.span.from_expansion()
+        {
+            Some(borrowed)
+        } else {
+            None
+        }
+    }
+
     /// User cannot make signature of a trait mutable without changing the
     /// trait. So we find if this error belongs to a trait and if so we move
     /// suggestion to the trait or disable it if it is out of scope of this crate
@@ -275,6 +275,12 @@ pub(in crate::borrow_check) fn report_region_error(
         outlives_suggestion: &mut OutlivesSuggestionBuilder,
     ) {
         debug!("report_region_error(fr={:?}, outlived_fr={:?})", fr, outlived_fr);
+        // Both regions are universal, which are numbered after the order in which they
+        // appear in the signature.
+        let metadata = [
+            ("region", fr.index().to_string()),
+            ("outlived_region", outlived_fr.index().to_string()),
+        ];
 
         let BlameConstraint { category, span, variance_info, from_closure: _ } =
             self.regioncx.best_blame_constraint(&self.body, fr, fr_origin, |r| {
@@ -285,7 +291,8 @@ pub(in crate::borrow_check) fn report_region_error(
         // Check if we can use one of the "nice region errors".
         if let (Some(f), Some(o)) = (self.to_error_region(fr), self.to_error_region(outlived_fr)) {
             let nice = NiceRegionError::new_from_span(self.infcx, span, o, f);
-            if let Some(diag) = nice.try_report_from_nll() {
+            if let Some(mut diag) = nice.try_report_from_nll() {
+                self.add_error_metadata(&mut diag, &metadata);
                 diag.buffer(&mut self.errors_buffer);
                 return;
             }
@@ -333,6 +340,12 @@ pub(in crate::borrow_check) fn report_region_error(
             }
         };
 
+        if let (ConstraintCategory::Return(_), true, false) =
+            (category, fr_is_local, outlived_fr_is_local)
+        {
+            self.note_local_key_with_borrow(&mut diag);
+        }
+
         match variance_info {
             ty::VarianceDiagInfo::None => {}
             ty::VarianceDiagInfo::Mut { kind, ty } => {
@@ -346,6 +359,7 @@ pub(in crate::borrow_check) fn report_region_error(
             }
         }
 
+        self.add_error_metadata(&mut diag, &metadata);
         diag.buffer(&mut self.errors_buffer);
     }
 
@@ -425,6 +439,20 @@ fn report_fnmut_error(
         diag
     }
 
+    /// Explains why the value of a `thread_local!` can't be returned from the closure passed
+    /// to `LocalKey::with`, if that's the closure being checked.
+    fn note_local_key_with_borrow(&self, diag: &mut DiagnosticBuilder<'_>) {
+        if let DefiningTy::Closure(def_id, _) = self.regioncx.universal_regions().defining_ty {
+            if self.infcx.tcx.is_local_key_with_closure(def_id) {
+                diag.note(
+                    "a thread-local value is only borrowed until the closure passed to \
+                     `LocalKey::with` returns",
+                );
+                diag.help("return a copy or a clone of the value from the closure instead");
+            }
+        }
+    }
+
     /// Reports a error specifically for when data is escaping a closure.
     ///
     /// ```text
@@ -5,6 +5,7 @@
 use rustc_index::vec::Idx;
 use rustc_middle::mir::Local;
 use rustc_middle::ty::{RegionVid, TyCtxt};
+use rustc_session::config::OutputType;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fs::{self, File};
@@ -26,7 +27,7 @@ impl polonius_engine::FactTypes for RustcFacts {
 
 crate trait AllFactsExt {
     /// Returns `true` if there is a need to gather `AllFacts` given the
-    /// current `-Z` flags.
+    /// current `-Z` flags and `--emit` kinds.
     fn enabled(tcx: TyCtxt<'_>) -> bool;
 
     fn write_to_dir(
@@ -39,7 +40,9 @@ fn write_to_dir(
 impl AllFactsExt for AllFacts {
     /// Return
     fn enabled(tcx: TyCtxt<'_>) -> bool {
-        tcx.sess.opts.debugging_opts.nll_facts || tcx.sess.opts.debugging_opts.polonius
+        tcx.sess.opts.debugging_opts.nll_facts
+            || tcx.sess.opts.debugging_opts.polonius
+            || tcx.sess.opts.output_types.contains_key(&OutputType::BorrowckFacts)
     }
 
     fn write_to_dir(
@@ -2,7 +2,11 @@
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::graph::dominators::Dominators;
-use rustc_errors::{Applicability, Diagnostic, DiagnosticBuilder, ErrorReported};
+use rustc_data_structures::sync::OnceCell;
+use rustc_errors::{
+    Applicability, Diagnostic, DiagnosticBuilder, DiagnosticId, ErrorReported, Level,
+};
+use rustc_graphviz as dot;
 use rustc_hir as hir;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::Node;
@@ -14,17 +18,20 @@
     PlaceRef, VarDebugInfoContents,
 };
 use rustc_middle::mir::{AggregateKind, BasicBlock, BorrowCheckResult, BorrowKind};
-use rustc_middle::mir::{Field, ProjectionElem, Promoted, Rvalue, Statement, StatementKind};
+use rustc_middle::mir::{Field, MovePathsSummary, ProjectionElem, Promoted};
+use rustc_middle::mir::{Rvalue, Statement, StatementKind};
 use rustc_middle::mir::{InlineAsmOperand, Terminator, TerminatorKind};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, CapturedPlace, ParamEnv, RegionVid, TyCtxt};
+use rustc_session::config::DumpMovePaths;
 use rustc_session::lint::builtin::{MUTABLE_BORROW_RESERVATION_CONFLICT, UNUSED_MUT};
-use rustc_span::{Span, Symbol, DUMMY_SP};
+use rustc_span::{sym, Span, Symbol, DUMMY_SP};
 
 use either::Either;
 use smallvec::SmallVec;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::io;
 use std::iter;
 use std::mem;
 use std::rc::Rc;
@@ -34,9 +41,11 @@
     Borrows, EverInitializedPlaces, MaybeInitializedPlaces, MaybeUninitializedPlaces,
 };
 use crate::dataflow::indexes::{BorrowIndex, InitIndex, MoveOutIndex, MovePathIndex};
-use crate::dataflow::move_paths::{InitLocation, LookupResult, MoveData, MoveError};
+use crate::dataflow::move_paths::{graphviz, InitLocation, LookupResult, MoveData, MoveError};
 use crate::dataflow::MoveDataParamEnv;
 use crate::dataflow::{Analysis, BorrowckFlowState as Flows, BorrowckResults};
+use crate::util::mutable_uses::MutableUse;
+use crate::util::pretty;
 
 use self::diagnostics::{AccessKind, RegionName};
 use self::location::LocationTable;
@@ -94,10 +103,19 @@ pub fn provide(providers: &mut Providers) {
         mir_borrowck_const_arg: |tcx, (did, param_did)| {
             mir_borrowck(tcx, ty::WithOptConstParam { did, const_param_did: Some(param_did) })
         },
+        mir_move_paths,
         ..*providers
     };
 }
 
+fn mir_move_paths<'tcx>(tcx: TyCtxt<'tcx>, did: LocalDefId) -> &'tcx MovePathsSummary<'tcx> {
+    let result = match ty::WithOptConstParam::try_lookup(did, tcx) {
+        Some((did, param_did)) => tcx.mir_borrowck_const_arg((did, param_did)),
+        None => tcx.mir_borrowck(did),
+    };
+    &result.move_paths
+}
+
 fn mir_borrowck<'tcx>(
     tcx: TyCtxt<'tcx>,
     def: ty::WithOptConstParam<LocalDefId>,
@@ -115,6 +133,39 @@ fn mir_borrowck<'tcx>(
     tcx.arena.alloc(opt_closure_req)
 }
 
+/// Returns the error codes listed in `#[rustc_ignore_borrowck_error(..)]` on the function
+/// being checked, or on the function enclosing it for closures. Borrowck errors with these
+/// codes are emitted as warnings, so that tests and migration tooling can look at the
+/// behavior of code that would otherwise be rejected.
+fn ignored_error_codes(tcx: TyCtxt<'_>, def_id: LocalDefId) -> FxHashSet<String> {
+    let base_def_id = tcx.closure_base_def_id(def_id.to_def_id());
+    // Only report malformed codes once, not again for each closure.
+    let report_malformed = base_def_id == def_id.to_def_id();
+    let mut codes = FxHashSet::default();
+    for attr in tcx.get_attrs(base_def_id).iter() {
+        if !tcx.sess.check_name(attr, sym::rustc_ignore_borrowck_error) {
+            continue;
+        }
+        for item in attr.meta_item_list().unwrap_or_default() {
+            let code = item.ident().filter(|_| item.is_word()).map(|ident| ident.name.as_str());
+            match code {
+                Some(code)
+                    if code.len() == 5
+                        && code.starts_with('E')
+                        && code[1..].bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    codes.insert(code.to_string());
+                }
+                _ if report_malformed => {
+                    tcx.sess.span_err(item.span(), "expected an error code like `E0502`");
+                }
+                _ => {}
+            }
+        }
+    }
+    codes
+}
+
 fn do_mir_borrowck<'a, 'tcx>(
     infcx: &InferCtxt<'a, 'tcx>,
     input_body: &Body<'tcx>,
@@ -127,6 +178,7 @@ fn do_mir_borrowck<'a, 'tcx>(
     let tcx = infcx.tcx;
     let param_env = tcx.param_env(def.did);
     let id = tcx.hir().local_def_id_to_hir_id(def.did);
+    let ignored_error_codes = ignored_error_codes(tcx, def.did);
 
     let mut local_names = IndexVec::from_elem(None, &input_body.local_decls);
     for var_debug_info in &input_body.var_debug_info {
@@ -276,6 +328,7 @@ fn do_mir_borrowck<'a, 'tcx>(
                 locals_are_invalidated_at_exit,
                 access_place_error_reported: Default::default(),
                 reservation_error_reported: Default::default(),
+                conflicting_borrows_reported: Default::default(),
                 reservation_warnings: Default::default(),
                 move_error_reported: BTreeMap::new(),
                 uninitialized_error_reported: Default::default(),
@@ -290,6 +343,8 @@ fn do_mir_borrowck<'a, 'tcx>(
                 region_names: RefCell::default(),
                 next_region_name: RefCell::new(1),
                 polonius_output: None,
+                ignored_error_codes: Default::default(),
+                mutable_uses: Default::default(),
             };
             promoted_mbcx.report_move_errors(move_errors);
             errors_buffer = promoted_mbcx.errors_buffer;
@@ -309,6 +364,7 @@ fn do_mir_borrowck<'a, 'tcx>(
         fn_self_span_reported: Default::default(),
         access_place_error_reported: Default::default(),
         reservation_error_reported: Default::default(),
+        conflicting_borrows_reported: Default::default(),
         reservation_warnings: Default::default(),
         move_error_reported: BTreeMap::new(),
         uninitialized_error_reported: Default::default(),
@@ -323,8 +379,14 @@ fn do_mir_borrowck<'a, 'tcx>(
         region_names: RefCell::default(),
         next_region_name: RefCell::new(1),
         polonius_output,
+        ignored_error_codes,
+        mutable_uses: Default::default(),
     };
 
+    if let Some(DumpMovePaths::Graphviz) = tcx.sess.opts.debugging_opts.dump_move_paths {
+        mbcx.dump_move_paths_graphviz();
+    }
+
     // Compute and report region errors, if any.
     mbcx.report_region_errors(nll_errors);
 
@@ -435,7 +497,25 @@ fn do_mir_borrowck<'a, 'tcx>(
     if !mbcx.errors_buffer.is_empty() {
         mbcx.errors_buffer.sort_by_key(|diag| diag.sort_span);
 
-        for diag in mbcx.errors_buffer.drain(..) {
+        let downgraded_error_codes =
+            tcx.sess.opts.debugging_opts.treat_borrowck_err_as_warn.as_ref();
+        for mut diag in mbcx.errors_buffer.drain(..) {
+            if let Some(DiagnosticId::Error(code)) = &diag.code {
+                if mbcx.ignored_error_codes.contains(code) {
+                    let note = format!(
+                        "this error was downgraded to a warning by \
+                         `#[rustc_ignore_borrowck_error({})]`",
+                        code
+                    );
+                    diag.level = Level::Warning;
+                    diag.note(&note);
+                } else if downgraded_error_codes.map_or(false, |codes| codes.contains(code)) {
+                    diag.level = Level::Warning;
+                    diag.note(
+                        "this error was downgraded to a warning by `-Z treat-borrowck-err-as-warn`",
+                    );
+                }
+            }
             mbcx.infcx.tcx.sess.diagnostic().emit_diagnostic(&diag);
         }
     }
@@ -444,6 +524,7 @@ fn do_mir_borrowck<'a, 'tcx>(
         concrete_opaque_types: opaque_type_values,
         closure_requirements: opt_closure_req,
         used_mut_upvars: mbcx.used_mut_upvars,
+        move_paths: mbcx.move_data.summary(tcx),
     };
 
     debug!("do_mir_borrowck: result = {:#?}", result);
@@ -483,6 +564,10 @@ fn do_mir_borrowck<'a, 'tcx>(
     // but it is currently inconvenient to track down the `BorrowIndex`
     // at the time we detect and report a reservation error.
     reservation_error_reported: FxHashSet<Place<'tcx>>,
+    /// The borrows which were reported to conflict with an earlier borrow.
+    /// When a later access conflicts with several borrows, these are not
+    /// pointed out again as additional conflicting borrows.
+    conflicting_borrows_reported: FxHashSet<BorrowIndex>,
     /// This fields keeps track of the `Span`s that we have
     /// used to report extra information for `FnSelfUse`, to avoid
     /// unnecessarily verbose errors.
@@ -543,6 +628,13 @@ fn do_mir_borrowck<'a, 'tcx>(
 
     /// Results of Polonius analysis.
     polonius_output: Option<Rc<PoloniusOutput>>,
+
+    /// Codes of the errors that are emitted as warnings for this body, see
+    /// `ignored_error_codes`.
+    ignored_error_codes: FxHashSet<String>,
+
+    /// How each local is mutated, computed on first use by diagnostics, see `mutable_use`.
+    mutable_uses: OnceCell<IndexVec<Local, MutableUse>>,
 }
 
 // Check that:
@@ -916,6 +1008,8 @@ enum LocalMutationIsAllowed {
 enum InitializationRequiringAction {
     Update,
     Borrow,
+    /// Creating a raw pointer with `&raw const` or `&raw mut`.
+    RawBorrow,
     MatchOn,
     Use,
     Assignment,
@@ -933,6 +1027,7 @@ fn as_noun(self) -> &'static str {
         match self {
             InitializationRequiringAction::Update => "update",
             InitializationRequiringAction::Borrow => "borrow",
+            InitializationRequiringAction::RawBorrow => "raw borrow",
             InitializationRequiringAction::MatchOn => "use", // no good noun
             InitializationRequiringAction::Use => "use",
             InitializationRequiringAction::Assignment => "assign",
@@ -944,6 +1039,7 @@ fn as_verb_in_past_tense(self) -> &'static str {
         match self {
             InitializationRequiringAction::Update => "updated",
             InitializationRequiringAction::Borrow => "borrowed",
+            InitializationRequiringAction::RawBorrow => "raw-borrowed",
             InitializationRequiringAction::MatchOn => "matched on",
             InitializationRequiringAction::Use => "used",
             InitializationRequiringAction::Assignment => "assigned",
@@ -957,6 +1053,28 @@ fn body(&self) -> &'cx Body<'tcx> {
         self.body
     }
 
+    /// Writes the move paths of the body and the move-outs of each path to a `.dot` file, with
+    /// the paths labeled the way diagnostics describe them.
+    fn dump_move_paths_graphviz(&self) {
+        let tcx = self.infcx.tcx;
+        let describe_place = |place| self.describe_place(place);
+        let result: io::Result<()> = try {
+            let mut file =
+                pretty::create_dump_file(tcx, "dot", None, "move_paths", &0, self.body.source)?;
+            let mut render_opts = vec![dot::RenderOption::Fontname(
+                tcx.sess.opts.debugging_opts.graphviz_font.clone(),
+            )];
+            if tcx.sess.opts.debugging_opts.graphviz_dark_mode {
+                render_opts.push(dot::RenderOption::DarkTheme);
+            }
+            graphviz::Formatter::new(self.body, self.move_data, &describe_place)
+                .render(&mut file, &render_opts)?;
+        };
+        if let Err(e) = result {
+            tcx.sess.err(&format!("failed to write move path dump: {}", e));
+        }
+    }
+
     /// Checks an access to the given place to see if it is allowed. Examines the set of borrows
     /// that are in scope, as well as which paths have been initialized, to ensure that (a) the
     /// place is initialized and (b) it is not borrowed in some way that would prevent this
@@ -1033,6 +1151,9 @@ fn check_access_for_conflict(
         );
 
         let mut error_reported = false;
+        // Conflicts with borrows are collected so that they can all be
+        // reported in a single diagnostic.
+        let mut conflicting_borrows = vec![];
         let tcx = self.infcx.tcx;
         let body = self.body;
         let borrow_set = self.borrow_set.clone();
@@ -1096,13 +1217,13 @@ fn check_access_for_conflict(
                         ReadKind::Copy => {
                             this.report_use_while_mutably_borrowed(location, place_span, borrow)
                                 .buffer(&mut this.errors_buffer);
+                            Control::Break
                         }
                         ReadKind::Borrow(bk) => {
-                            this.report_conflicting_borrow(location, place_span, bk, borrow)
-                                .buffer(&mut this.errors_buffer);
+                            conflicting_borrows.push((bk, borrow_index));
+                            Control::Continue
                         }
                     }
-                    Control::Break
                 }
 
                 (
@@ -1149,8 +1270,8 @@ fn check_access_for_conflict(
                     error_reported = true;
                     match kind {
                         WriteKind::MutableBorrow(bk) => {
-                            this.report_conflicting_borrow(location, place_span, bk, borrow)
-                                .buffer(&mut this.errors_buffer);
+                            conflicting_borrows.push((bk, borrow_index));
+                            return Control::Continue;
                         }
                         WriteKind::StorageDeadOrDrop => this
                             .report_borrowed_value_does_not_live_long_enough(
@@ -1171,6 +1292,22 @@ fn check_access_for_conflict(
             },
         );
 
+        if let Some((&(bk, first_borrow), other_borrows)) = conflicting_borrows.split_first() {
+            let mut err =
+                self.report_conflicting_borrow(location, place_span, bk, &borrow_set[first_borrow]);
+            self.label_other_conflicting_borrows(
+                &mut err,
+                other_borrows
+                    .iter()
+                    .filter(|(_, borrow)| !self.conflicting_borrows_reported.contains(borrow))
+                    .map(|&(_, borrow)| &borrow_set[borrow]),
+            );
+            err.buffer(&mut self.errors_buffer);
+            if let Some(borrow) = borrow_set.get_index_of(&location) {
+                self.conflicting_borrows_reported.insert(borrow);
+            }
+        }
+
         error_reported
     }
 
@@ -1286,7 +1423,7 @@ fn consume_rvalue(
 
                 self.check_if_path_or_subpath_is_moved(
                     location,
-                    InitializationRequiringAction::Borrow,
+                    InitializationRequiringAction::RawBorrow,
                     (place.as_ref(), span),
                     flow_state,
                 );
@@ -1569,10 +1706,13 @@ fn check_for_local_borrow(&mut self, borrow: &BorrowData<'tcx>, yield_span: Span
         debug!("check_for_local_borrow({:?})", borrow);
 
         if borrow_of_local_data(borrow.borrowed_place) {
-            let err = self.cannot_borrow_across_generator_yield(
-                self.retrieve_borrow_spans(borrow).var_or_use(),
-                yield_span,
-            );
+            let borrow_span = self.retrieve_borrow_spans(borrow).var_or_use();
+            let mut err = self.cannot_borrow_across_generator_yield(borrow_span, yield_span);
+            // Variables captured by the generator are described by their own names,
+            // rather than as fields of the generator.
+            if let Some(place) = self.describe_place(borrow.borrowed_place.as_ref()) {
+                err.span_label(borrow_span, format!("`{}` is borrowed here", place));
+            }
 
             err.buffer(&mut self.errors_buffer);
         }
@@ -10,6 +10,7 @@
     Promoted,
 };
 use rustc_middle::ty::{self, RegionKind, RegionVid};
+use rustc_session::config::OutputType;
 use rustc_span::symbol::sym;
 use std::env;
 use std::fmt::Debug;
@@ -280,6 +281,22 @@ pub(in crate::borrow_check) fn compute_regions<'cx, 'tcx>(
             all_facts.write_to_dir(dir_path, location_table).unwrap();
         }
 
+        if infcx.tcx.sess.opts.output_types.contains_key(&OutputType::BorrowckFacts) {
+            let def_path = infcx.tcx.def_path(def_id);
+            let dir_path = infcx
+                .tcx
+                .output_filenames(())
+                .path(OutputType::BorrowckFacts)
+                .join(def_path.to_filename_friendly_no_crate());
+            if let Err(e) = all_facts.write_to_dir(&dir_path, location_table) {
+                infcx.tcx.sess.err(&format!(
+                    "could not emit borrowck facts to `{}`: {}",
+                    dir_path.display(),
+                    e
+                ));
+            }
+        }
+
         if infcx.tcx.sess.opts.debugging_opts.polonius {
             let algorithm =
                 env::var("POLONIUS_ALGORITHM").unwrap_or_else(|_| String::from("Naive"));
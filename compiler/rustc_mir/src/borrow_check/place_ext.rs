@@ -44,13 +44,10 @@ fn ignore_borrow(
             }
         }
 
-        for (i, elem) in self.projection.iter().enumerate() {
-            let proj_base = &self.projection[..i];
-
+        for (proj_base, elem, base_ty) in self.iter_projections_with_ty(body, tcx) {
             if elem == ProjectionElem::Deref {
-                let ty = Place::ty_from(self.local, proj_base, body, tcx).ty;
-                match ty.kind() {
-                    ty::Ref(_, _, hir::Mutability::Not) if i == 0 => {
+                match base_ty.ty.kind() {
+                    ty::Ref(_, _, hir::Mutability::Not) if proj_base.projection.is_empty() => {
                         // For references to thread-local statics, we do need
                         // to track the borrow.
                         if body.local_decls[self.local].is_ref_to_thread_local() {
@@ -16,6 +16,7 @@
 #[derive(Clone, Debug)]
 pub enum ConstEvalErrKind {
     NeedsRfc(String),
+    UnsupportedIntrinsic { name: Symbol, feature: Option<Symbol> },
     PtrToIntCast,
     ConstAccessesStatic,
     ModifiedGlobal,
@@ -49,6 +50,17 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             NeedsRfc(ref msg) => {
                 write!(f, "\"{}\" needs an rfc before being allowed inside constants", msg)
             }
+            UnsupportedIntrinsic { name, feature } => {
+                write!(f, "calling intrinsic `{}` is not supported in constants", name)?;
+                if let Some(feature) = feature {
+                    write!(
+                        f,
+                        ", even though it is const-unstable under `#![feature({})]`",
+                        feature
+                    )?;
+                }
+                Ok(())
+            }
             PtrToIntCast => {
                 write!(
                     f,
@@ -119,6 +131,17 @@ pub fn report_as_lint(
         message: &str,
         lint_root: hir::HirId,
         span: Option<Span>,
+    ) -> ErrorHandled {
+        self.struct_lint(tcx, message, lint_root, span, |mut lint| lint.emit())
+    }
+
+    pub fn struct_lint(
+        &self,
+        tcx: TyCtxtAt<'tcx>,
+        message: &str,
+        lint_root: hir::HirId,
+        span: Option<Span>,
+        emit: impl FnOnce(DiagnosticBuilder<'_>),
     ) -> ErrorHandled {
         self.struct_generic(
             tcx,
@@ -137,7 +160,7 @@ pub fn report_as_lint(
                         }
                     }
                 }
-                lint.emit();
+                emit(lint);
             },
             Some(lint_root),
         )
@@ -164,6 +187,14 @@ fn struct_generic(
             if let Some(span_msg) = span_msg {
                 err.span_label(self.span, span_msg);
             }
+            if let err_ub!(UncheckedShiftOverflow { intrinsic, ty, shift }) = &self.error {
+                err.note(&format!(
+                    "`{}::<{}>` requires the shift amount to be in the range `0..{}`",
+                    intrinsic,
+                    ty,
+                    shift.size().bits()
+                ));
+            }
             // Add spans for the stacktrace. Don't print a single-line backtrace though.
             if self.stacktrace.len() > 1 {
                 for frame_info in &self.stacktrace {
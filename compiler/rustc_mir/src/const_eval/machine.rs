@@ -1,5 +1,5 @@
 use rustc_middle::mir;
-use rustc_middle::ty::{self, Ty};
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use std::borrow::Borrow;
 use std::collections::hash_map::Entry;
 use std::hash::Hash;
@@ -194,6 +194,17 @@ fn guaranteed_ne(&mut self, a: Scalar, b: Scalar) -> bool {
     }
 }
 
+/// The error for an intrinsic the interpreter does not support. If the intrinsic is
+/// const-unstable, the error names the feature gate, as it is expected to become
+/// const-evaluable eventually.
+fn unsupported_intrinsic(tcx: TyCtxt<'_>, def_id: DefId) -> ConstEvalErrKind {
+    let feature = tcx
+        .lookup_const_stability(def_id)
+        .filter(|stab| stab.level.is_unstable())
+        .map(|stab| stab.feature);
+    ConstEvalErrKind::UnsupportedIntrinsic { name: tcx.item_name(def_id), feature }
+}
+
 impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir, 'tcx> {
     compile_time_machine!(<'mir, 'tcx>);
 
@@ -273,13 +284,7 @@ fn call_intrinsic(
 
         // CTFE-specific intrinsics.
         let (dest, ret) = match ret {
-            None => {
-                return Err(ConstEvalErrKind::NeedsRfc(format!(
-                    "calling intrinsic `{}`",
-                    intrinsic_name
-                ))
-                .into());
-            }
+            None => return Err(unsupported_intrinsic(ecx.tcx.tcx, instance.def_id()).into()),
             Some(p) => p,
         };
         match intrinsic_name {
@@ -309,13 +314,7 @@ fn call_intrinsic(
                 );
                 ecx.write_scalar(Scalar::Ptr(ptr), dest)?;
             }
-            _ => {
-                return Err(ConstEvalErrKind::NeedsRfc(format!(
-                    "calling intrinsic `{}`",
-                    intrinsic_name
-                ))
-                .into());
-            }
+            _ => return Err(unsupported_intrinsic(ecx.tcx.tcx, instance.def_id()).into()),
         }
 
         ecx.go_to_block(ret);
@@ -105,11 +105,10 @@ fn move_path_for(&mut self, place: Place<'tcx>) -> Result<MovePathIndex, MoveErr
         // from `*(u.f: &_)` isn't allowed.
         let mut union_path = None;
 
-        for (i, elem) in place.projection.iter().enumerate() {
-            let proj_base = &place.projection[..i];
-            let body = self.builder.body;
-            let tcx = self.builder.tcx;
-            let place_ty = Place::ty_from(place.local, proj_base, body, tcx).ty;
+        let body = self.builder.body;
+        let tcx = self.builder.tcx;
+        for (i, (_, elem, place_ty)) in place.iter_projections_with_ty(body, tcx).enumerate() {
+            let place_ty = place_ty.ty;
             match place_ty.kind() {
                 ty::Ref(..) | ty::RawPtr(..) => {
                     let proj = &place.projection[..i + 1];
@@ -0,0 +1,126 @@
+//! Renders the move-path forest of a MIR body, together with the move-outs of
+//! each path, as a Graphviz diagram (`-Z dump-move-paths=graphviz`).
+
+use std::io;
+
+use rustc_graphviz as dot;
+use rustc_middle::mir::{Body, PlaceRef};
+
+use super::{MoveData, MoveOutIndex, MovePathIndex};
+use crate::util::graphviz_safe_def_name;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Node {
+    Path(MovePathIndex),
+    Move(MoveOutIndex),
+}
+
+pub struct Formatter<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    move_data: &'a MoveData<'tcx>,
+    describe_place: &'a dyn Fn(PlaceRef<'tcx>) -> Option<String>,
+}
+
+impl Formatter<'a, 'tcx> {
+    /// Creates a formatter for the move paths of `body`. Paths are labeled with the output of
+    /// `describe_place`, falling back to their debug representation when it returns `None`.
+    pub fn new(
+        body: &'a Body<'tcx>,
+        move_data: &'a MoveData<'tcx>,
+        describe_place: &'a dyn Fn(PlaceRef<'tcx>) -> Option<String>,
+    ) -> Self {
+        Formatter { body, move_data, describe_place }
+    }
+
+    pub fn render(
+        &self,
+        mut w: &mut dyn io::Write,
+        options: &[dot::RenderOption],
+    ) -> io::Result<()> {
+        dot::render_opts(self, &mut w, options)
+    }
+}
+
+impl dot::Labeller<'_> for Formatter<'a, 'tcx> {
+    type Node = Node;
+    type Edge = (Node, Node);
+
+    fn graph_id(&self) -> dot::Id<'_> {
+        let name = graphviz_safe_def_name(self.body.source.def_id());
+        dot::Id::new(format!("move_paths_for_def_id_{}", name)).unwrap()
+    }
+
+    fn node_id(&self, n: &Self::Node) -> dot::Id<'_> {
+        match *n {
+            Node::Path(mpi) => dot::Id::new(format!("mp_{}", mpi.index())).unwrap(),
+            Node::Move(moi) => dot::Id::new(format!("mo_{}", moi.index())).unwrap(),
+        }
+    }
+
+    fn node_label(&self, n: &Self::Node) -> dot::LabelText<'_> {
+        match *n {
+            Node::Path(mpi) => {
+                let place = self.move_data.move_paths[mpi].place;
+                let descr = match (self.describe_place)(place.as_ref()) {
+                    Some(descr) => format!("`{}`", descr),
+                    None => format!("{:?}", place),
+                };
+                dot::LabelText::label(format!("{:?}: {}", mpi, descr))
+            }
+            Node::Move(moi) => {
+                let source = self.move_data.moves[moi].source;
+                dot::LabelText::label(format!("{:?} at {:?}", moi, source))
+            }
+        }
+    }
+
+    fn node_shape(&self, n: &Self::Node) -> Option<dot::LabelText<'_>> {
+        match n {
+            Node::Path(_) => Some(dot::LabelText::label("box")),
+            Node::Move(_) => Some(dot::LabelText::label("ellipse")),
+        }
+    }
+
+    fn edge_style(&self, e: &Self::Edge) -> dot::Style {
+        match e.1 {
+            Node::Path(_) => dot::Style::None,
+            Node::Move(_) => dot::Style::Dashed,
+        }
+    }
+}
+
+impl dot::GraphWalk<'a> for Formatter<'a, 'tcx> {
+    type Node = Node;
+    type Edge = (Node, Node);
+
+    fn nodes(&self) -> dot::Nodes<'_, Self::Node> {
+        self.move_data
+            .move_paths
+            .indices()
+            .map(Node::Path)
+            .chain(self.move_data.moves.indices().map(Node::Move))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn edges(&self) -> dot::Edges<'_, Self::Edge> {
+        let mut edges = Vec::new();
+        for (mpi, path) in self.move_data.move_paths.iter_enumerated() {
+            if let Some(parent) = path.parent {
+                edges.push((Node::Path(parent), Node::Path(mpi)));
+            }
+            for &moi in &self.move_data.path_map[mpi] {
+                edges.push((Node::Path(mpi), Node::Move(moi)));
+            }
+        }
+        edges.into()
+    }
+
+    fn source(&self, edge: &Self::Edge) -> Self::Node {
+        edge.0
+    }
+
+    fn target(&self, edge: &Self::Edge) -> Self::Node {
+        edge.1
+    }
+}
@@ -12,6 +12,7 @@
 use self::abs_domain::{AbstractElem, Lift};
 
 mod abs_domain;
+pub mod graphviz;
 
 rustc_index::newtype_index! {
     pub struct MovePathIndex {
@@ -383,6 +384,30 @@ pub fn gather_moves(
         builder::gather_moves(body, tcx, param_env)
     }
 
+    /// Returns the move paths along with where they are moved out of and
+    /// initialized, as exposed by the `mir_move_paths` query. The regions of the
+    /// places are erased, as borrowck gathers them from a body whose regions are
+    /// inference variables.
+    pub fn summary(&self, tcx: TyCtxt<'tcx>) -> MovePathsSummary<'tcx> {
+        let paths = self
+            .move_paths
+            .iter_enumerated()
+            .map(|(mpi, path)| MovePathSummary {
+                place: tcx.erase_regions(path.place),
+                parent: path.parent.map(|parent| MovePathId::from_usize(parent.index())),
+                moves: self.path_map[mpi].iter().map(|&moi| self.moves[moi].source).collect(),
+                inits: self.init_path_map[mpi]
+                    .iter()
+                    .map(|&ii| match self.inits[ii].location {
+                        InitLocation::Argument(local) => InitSite::Argument(local),
+                        InitLocation::Statement(location) => InitSite::Statement(location),
+                    })
+                    .collect(),
+            })
+            .collect();
+        MovePathsSummary { paths }
+    }
+
     /// For the move path `mpi`, returns the root local variable (if any) that starts the path.
     /// (e.g., for a path like `a.b.c` returns `Some(a)`)
     pub fn base_local(&self, mut mpi: MovePathIndex) -> Option<Local> {
@@ -5,6 +5,7 @@
 use std::convert::TryFrom;
 
 use rustc_hir::def_id::DefId;
+use rustc_hir::lang_items::LangItem;
 use rustc_middle::mir::{
     self,
     interpret::{ConstValue, GlobalId, InterpResult, Scalar},
@@ -12,7 +13,7 @@
 };
 use rustc_middle::ty;
 use rustc_middle::ty::subst::SubstsRef;
-use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_middle::ty::{ScalarInt, Ty, TyCtxt};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{Abi, Align, LayoutOf as _, Primitive, Size};
 
@@ -217,6 +218,35 @@ pub fn emulate_intrinsic(
                 };
                 self.binop_with_overflow(bin_op, &lhs, &rhs, dest)?;
             }
+            sym::checked_add
+            | sym::checked_sub
+            | sym::checked_mul
+            | sym::checked_shl
+            | sym::checked_shr => {
+                let l = self.read_immediate(&args[0])?;
+                let r = self.read_immediate(&args[1])?;
+                let bin_op = match intrinsic_name {
+                    sym::checked_add => BinOp::Add,
+                    sym::checked_sub => BinOp::Sub,
+                    sym::checked_mul => BinOp::Mul,
+                    sym::checked_shl => BinOp::Shl,
+                    sym::checked_shr => BinOp::Shr,
+                    _ => bug!("Already checked for int ops"),
+                };
+                let (val, overflowed, _ty) = self.overflowing_binary_op(bin_op, &l, &r)?;
+                let option = dest.layout.ty.ty_adt_def().unwrap();
+                let variant =
+                    |item| option.variant_index_with_id(self.tcx.require_lang_item(item, None));
+                let (some, none) = (variant(LangItem::OptionSome), variant(LangItem::OptionNone));
+                if overflowed {
+                    self.write_discriminant(none, dest)?;
+                } else {
+                    let field = self.place_downcast(dest, some)?;
+                    let field = self.place_field(&field, 0)?;
+                    self.write_scalar(val, &field)?;
+                    self.write_discriminant(some, dest)?;
+                }
+            }
             sym::saturating_add | sym::saturating_sub => {
                 let l = self.read_immediate(&args[0])?;
                 let r = self.read_immediate(&args[1])?;
@@ -295,13 +325,22 @@ pub fn emulate_intrinsic(
                     let layout = self.layout_of(substs.type_at(0))?;
                     let r_val = self.force_bits(r.to_scalar()?, layout.size)?;
                     if let sym::unchecked_shl | sym::unchecked_shr = intrinsic_name {
-                        throw_ub_format!("overflowing shift by {} in `{}`", r_val, intrinsic_name);
+                        throw_ub!(UncheckedShiftOverflow {
+                            intrinsic: intrinsic_name,
+                            ty: layout.ty,
+                            shift: ScalarInt::try_from_uint(r_val, layout.size).unwrap(),
+                        });
                     } else {
                         throw_ub_format!("overflow executing `{}`", intrinsic_name);
                     }
                 }
                 self.write_scalar(val, dest)?;
             }
+            sym::exact_div => {
+                let l = self.read_immediate(&args[0])?;
+                let r = self.read_immediate(&args[1])?;
+                self.exact_div(&l, &r, dest)?;
+            }
             sym::rotate_left | sym::rotate_right => {
                 // rotate_left: (X << (S % BW)) | (X >> ((BW - S) % BW))
                 // rotate_right: (X << ((BW - S) % BW)) | (X >> (S % BW))
@@ -492,7 +531,7 @@ pub fn exact_div(
             if b_scalar == minus1 {
                 throw_ub_format!("exact_div: result of dividing MIN by -1 cannot be represented")
             } else {
-                throw_ub_format!("exact_div: {} cannot be divided by {} without remainder", a, b,)
+                throw_ub_format!("exact_div: {} is not a multiple of {}", a, b)
             }
         }
         // `Rem` says this is all right, so we can let `Div` do its job.
@@ -201,6 +201,7 @@
 use rustc_session::config::EntryFnType;
 use rustc_session::lint::builtin::LARGE_ASSIGNMENTS;
 use rustc_span::source_map::{dummy_spanned, respan, Span, Spanned, DUMMY_SP};
+use rustc_span::symbol::sym;
 use rustc_target::abi::Size;
 use smallvec::SmallVec;
 use std::iter;
@@ -307,6 +308,7 @@ pub fn collect_crate_mono_items(
                     dummy_spanned(root),
                     visited,
                     &mut recursion_depths,
+                    &mut Vec::new(),
                     inlining_map,
                 );
             });
@@ -345,11 +347,15 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionMode) -> Vec<MonoItem<
 
 /// Collect all monomorphized items reachable from `starting_point`, and emit a note diagnostic if a
 /// post-monorphization error is encountered during a collection step.
+///
+/// `instantiation_stack` holds the items whose collection led to `starting_point`, starting from
+/// the root.
 fn collect_items_rec<'tcx>(
     tcx: TyCtxt<'tcx>,
     starting_point: Spanned<MonoItem<'tcx>>,
     visited: MTRef<'_, MTLock<FxHashSet<MonoItem<'tcx>>>>,
     recursion_depths: &mut DefIdMap<usize>,
+    instantiation_stack: &mut Vec<Spanned<MonoItem<'tcx>>>,
     inlining_map: MTRef<'_, MTLock<InliningMap<'tcx>>>,
 ) {
     if !visited.lock_mut().insert(starting_point.node) {
@@ -360,6 +366,7 @@ fn collect_items_rec<'tcx>(
 
     let mut neighbors = Vec::new();
     let recursion_depth_reset;
+    let mut erroneous_generic_const = false;
 
     //
     // Post-monomorphization errors MVP
@@ -412,9 +419,10 @@ fn collect_items_rec<'tcx>(
             recursion_depth_reset =
                 Some(check_recursion_limit(tcx, instance, starting_point.span, recursion_depths));
             check_type_length_limit(tcx, instance);
+            check_clone_shim_expectation(tcx, instance);
 
             rustc_data_structures::stack::ensure_sufficient_stack(|| {
-                collect_neighbours(tcx, instance, &mut neighbors);
+                erroneous_generic_const = collect_neighbours(tcx, instance, &mut neighbors);
             });
         }
         MonoItem::GlobalAsm(item_id) => {
@@ -443,20 +451,45 @@ fn collect_items_rec<'tcx>(
     // involving a dependency, and the lack of context is confusing) in this MVP, we focus on
     // diagnostics on edges crossing a crate boundary: the collected mono items which are not
     // defined in the local crate.
-    if tcx.sess.diagnostic().err_count() > error_count && starting_point.node.krate() != LOCAL_CRATE
+    //
+    // A constant which only fails to evaluate for some generic arguments is just as confusing
+    // in the local crate, so in that case we also show the whole chain of generic instantiations
+    // leading to the erroneous one, down to the concrete arguments chosen by a caller.
+    if erroneous_generic_const
+        || tcx.sess.diagnostic().err_count() > error_count
+            && starting_point.node.krate() != LOCAL_CRATE
     {
         let formatted_item = with_no_trimmed_paths(|| starting_point.node.to_string());
         tcx.sess.span_note_without_error(
             starting_point.span,
             &format!("the above error was encountered while instantiating `{}`", formatted_item),
         );
+        if erroneous_generic_const {
+            let callers = instantiation_stack.iter().rev();
+            for caller in callers.take_while(|caller| is_generic_instantiation(caller.node)) {
+                let formatted_caller = with_no_trimmed_paths(|| caller.node.to_string());
+                tcx.sess.span_note_without_error(
+                    caller.span,
+                    &format!("which is required by the instantiation of `{}`", formatted_caller),
+                );
+            }
+        }
     }
 
     record_accesses(tcx, starting_point.node, neighbors.iter().map(|i| &i.node), inlining_map);
 
+    instantiation_stack.push(starting_point);
     for neighbour in neighbors {
-        collect_items_rec(tcx, neighbour, visited, recursion_depths, inlining_map);
+        collect_items_rec(
+            tcx,
+            neighbour,
+            visited,
+            recursion_depths,
+            instantiation_stack,
+            inlining_map,
+        );
     }
+    instantiation_stack.pop();
 
     if let Some((def_id, depth)) = recursion_depth_reset {
         recursion_depths.insert(def_id, depth);
@@ -465,6 +498,14 @@ fn collect_items_rec<'tcx>(
     debug!("END collect_items_rec({})", starting_point.node);
 }
 
+/// Whether `item` is a function instantiated with some generic arguments.
+fn is_generic_instantiation(item: MonoItem<'_>) -> bool {
+    match item {
+        MonoItem::Fn(instance) => instance.substs.non_erasable_generics().next().is_some(),
+        MonoItem::Static(_) | MonoItem::GlobalAsm(_) => false,
+    }
+}
+
 fn record_accesses<'a, 'tcx: 'a>(
     tcx: TyCtxt<'tcx>,
     caller: MonoItem<'tcx>,
@@ -593,11 +634,82 @@ fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
     }
 }
 
+/// Checks the `clone` of types annotated with `#[rustc_clone_shim_expect(..)]` against the
+/// expected lowering, so that tests can make sure that cloning them stays a plain copy.
+fn check_clone_shim_expectation<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
+    let def_id = instance.def_id();
+    let trait_id = tcx
+        .trait_of_item(def_id)
+        .or_else(|| tcx.impl_of_method(def_id).and_then(|impl_id| tcx.trait_id_of_impl(impl_id)));
+    if trait_id.is_none()
+        || trait_id != tcx.lang_items().clone_trait()
+        || tcx.item_name(def_id) != sym::clone
+    {
+        return;
+    }
+
+    let param_env = ty::ParamEnv::reveal_all();
+    let sig = tcx
+        .normalize_erasing_late_bound_regions(param_env, instance.ty(tcx, param_env).fn_sig(tcx));
+    let self_ty = match sig.inputs()[0].builtin_deref(true) {
+        Some(mt) => mt.ty,
+        None => return,
+    };
+    let adt_def = match self_ty.kind() {
+        ty::Adt(adt_def, _) => adt_def,
+        _ => return,
+    };
+    let attr = match tcx
+        .get_attrs(adt_def.did)
+        .iter()
+        .find(|attr| tcx.sess.check_name(attr, sym::rustc_clone_shim_expect))
+    {
+        Some(attr) => attr,
+        None => return,
+    };
+
+    let body = tcx.instance_mir(instance.def);
+    let terminators = body.basic_blocks().iter().map(|data| &data.terminator().kind);
+    let branches =
+        terminators.clone().any(|kind| matches!(kind, mir::TerminatorKind::SwitchInt { .. }));
+    let calls = terminators.any(|kind| {
+        matches!(
+            kind,
+            mir::TerminatorKind::Call { .. }
+                | mir::TerminatorKind::Drop { .. }
+                | mir::TerminatorKind::DropAndReplace { .. }
+        )
+    });
+
+    for meta_item in attr.meta_item_list().unwrap_or_default() {
+        let violation = match meta_item.name_or_empty() {
+            sym::memcpy => (branches || calls).then_some("is not a memcpy"),
+            sym::no_branches => branches.then_some("branches"),
+            sym::no_calls => calls.then_some("calls other functions"),
+            name => {
+                tcx.sess.span_err(
+                    meta_item.span(),
+                    &format!("unrecognized clone shim expectation `{}`", name),
+                );
+                continue;
+            }
+        };
+        if let Some(violation) = violation {
+            tcx.sess.span_err(
+                meta_item.span(),
+                &format!("`Clone::clone` for `{}` {}", self_ty, violation),
+            );
+        }
+    }
+}
+
 struct MirNeighborCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     body: &'a mir::Body<'tcx>,
     output: &'a mut Vec<Spanned<MonoItem<'tcx>>>,
     instance: Instance<'tcx>,
+    /// Whether a constant depending on the generic arguments of `instance` failed to evaluate.
+    erroneous_generic_const: bool,
 }
 
 impl<'a, 'tcx> MirNeighborCollector<'a, 'tcx> {
@@ -715,7 +827,10 @@ fn visit_constant(&mut self, constant: &mir::Constant<'tcx>, location: Location)
                     match self.tcx.const_eval_resolve(param_env, ct, None) {
                         // The `monomorphize` call should have evaluated that constant already.
                         Ok(val) => val,
-                        Err(ErrorHandled::Reported(ErrorReported) | ErrorHandled::Linted) => return,
+                        Err(ErrorHandled::Reported(ErrorReported) | ErrorHandled::Linted) => {
+                            self.erroneous_generic_const |= constant.literal.needs_subst();
+                            return;
+                        }
                         Err(ErrorHandled::TooGeneric) => span_bug!(
                             self.body.source_info(location).span,
                             "collection encountered polymorphic constant: {:?}",
@@ -723,6 +838,11 @@ fn visit_constant(&mut self, constant: &mir::Constant<'tcx>, location: Location)
                         ),
                     }
                 }
+                // The `monomorphize` call has already failed to evaluate that constant.
+                ty::ConstKind::Error(_) => {
+                    self.erroneous_generic_const |= constant.literal.needs_subst();
+                    return;
+                }
                 _ => return,
             },
         };
@@ -1367,15 +1487,20 @@ fn collect_miri<'tcx>(
 }
 
 /// Scans the MIR in order to find function calls, closures, and drop-glue.
+/// Collects the items used by `instance`, and returns whether one of its constants only failed
+/// to evaluate for the generic arguments of `instance`.
 fn collect_neighbours<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     output: &mut Vec<Spanned<MonoItem<'tcx>>>,
-) {
+) -> bool {
     debug!("collect_neighbours: {:?}", instance.def_id());
     let body = tcx.instance_mir(instance.def);
 
-    MirNeighborCollector { tcx, body: &body, output, instance }.visit_body(&body);
+    let mut collector =
+        MirNeighborCollector { tcx, body: &body, output, instance, erroneous_generic_const: false };
+    collector.visit_body(&body);
+    collector.erroneous_generic_const
 }
 
 fn collect_const_value<'tcx>(
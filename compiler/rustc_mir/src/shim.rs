@@ -15,6 +15,7 @@
 use std::fmt;
 use std::iter;
 
+use crate::transform::pass_order::PassOrderCache;
 use crate::transform::{
     add_call_guards, add_moves_for_packed_drops, no_landing_pads, remove_noop_landing_pads,
     run_passes, simplify,
@@ -75,6 +76,7 @@ fn make_shim<'tcx>(tcx: TyCtxt<'tcx>, instance: ty::InstanceDef<'tcx>) -> Body<'
     };
     debug!("make_shim({:?}) = untransformed {:?}", instance, result);
 
+    static ORDER: PassOrderCache = PassOrderCache::new();
     run_passes(
         tcx,
         &mut result,
@@ -86,6 +88,7 @@ fn make_shim<'tcx>(tcx: TyCtxt<'tcx>, instance: ty::InstanceDef<'tcx>) -> Body<'
             &simplify::SimplifyCfg::new("make_shim"),
             &add_call_guards::CriticalCallEdges,
         ]],
+        &ORDER,
     );
 
     debug!("make_shim({:?}) = {:?}", instance, result);
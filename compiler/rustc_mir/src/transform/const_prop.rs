@@ -5,6 +5,7 @@
 
 use rustc_ast::Mutability;
 use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::DiagnosticBuilder;
 use rustc_hir::def::DefKind;
 use rustc_hir::HirId;
 use rustc_index::bit_set::BitSet;
@@ -23,7 +24,7 @@
     self, ConstInt, ConstKind, Instance, ParamEnv, ScalarInt, Ty, TyCtxt, TypeFoldable,
 };
 use rustc_session::lint;
-use rustc_span::{def_id::DefId, Span};
+use rustc_span::{def_id::DefId, sym, Span};
 use rustc_target::abi::{HasDataLayout, LayoutOf, Size, TargetDataLayout};
 use rustc_target::spec::abi::Abi;
 use rustc_trait_selection::traits;
@@ -331,6 +332,8 @@ struct ConstPropagator<'mir, 'tcx> {
     // Because we have `MutVisitor` we can't obtain the `SourceInfo` from a `Location`. So we store
     // the last known `SourceInfo` here and just keep revisiting it.
     source_info: Option<SourceInfo>,
+    /// Erroneous constants are only reported at their first use in the body.
+    reported_erroneous_consts: FxHashSet<ConstantKind<'tcx>>,
 }
 
 impl<'mir, 'tcx> LayoutOf for ConstPropagator<'mir, 'tcx> {
@@ -413,6 +416,7 @@ fn new(
             //FIXME(wesleywiser) we can't steal this because `Visitor::super_visit_body()` needs it
             local_decls: body.local_decls.clone(),
             source_info: None,
+            reported_erroneous_consts: FxHashSet::default(),
         }
     }
 
@@ -475,6 +479,24 @@ fn eval_constant(&mut self, c: &Constant<'tcx>, source_info: SourceInfo) -> Opti
         match self.ecx.mir_const_to_op(&c.literal, None) {
             Ok(op) => Some(op),
             Err(error) => {
+                if !self.reported_erroneous_consts.insert(c.literal) {
+                    return None;
+                }
+                // The uses are only counted here, as erroneous constants are rare. The body of
+                // the frame is a copy of the body from before it was changed by propagation.
+                let uses = ConstantUseCounter::count(self.ecx.frame().body, c.literal);
+                let def_id = self.ecx.frame().instance.def_id();
+                let descr = self.tcx.def_kind(def_id).descr(def_id);
+                let emit = |mut err: DiagnosticBuilder<'_>| {
+                    if uses > 1 {
+                        err.note(&format!(
+                            "this constant is used {} times in this {}, \
+                             only the first use is reported",
+                            uses, descr,
+                        ));
+                    }
+                    err.emit();
+                };
                 let tcx = self.ecx.tcx.at(c.span);
                 let err = ConstEvalErr::new(&self.ecx, error, Some(c.span));
                 if let Some(lint_root) = self.lint_root(source_info) {
@@ -495,12 +517,18 @@ fn eval_constant(&mut self, c: &Constant<'tcx>, source_info: SourceInfo) -> Opti
                     if lint_only {
                         // Out of backwards compatibility we cannot report hard errors in unused
                         // generic functions using associated constants of the generic parameters.
-                        err.report_as_lint(tcx, "erroneous constant used", lint_root, Some(c.span));
+                        err.struct_lint(
+                            tcx,
+                            "erroneous constant used",
+                            lint_root,
+                            Some(c.span),
+                            emit,
+                        );
                     } else {
-                        err.report_as_error(tcx, "erroneous constant used");
+                        err.struct_error(tcx, "erroneous constant used", emit);
                     }
                 } else {
-                    err.report_as_error(tcx, "erroneous constant used");
+                    err.struct_error(tcx, "erroneous constant used", emit);
                 }
                 None
             }
@@ -623,6 +651,116 @@ fn check_binary_op(
         Some(())
     }
 
+    /// Lints calls to the `unchecked_*` arithmetic intrinsics (and `exact_div`) whose arguments
+    /// are both known and violate the intrinsic's precondition. Such calls are undefined
+    /// behavior, which CTFE would only report once the call is evaluated in a constant.
+    /// Calls to the methods of the integer types wrapping these intrinsics, like
+    /// `u8::unchecked_add`, are checked too.
+    /// Arguments only known through const propagation, like a local initialized with a
+    /// constant, are labeled with their values.
+    fn check_unchecked_intrinsic(
+        &mut self,
+        func: &Operand<'tcx>,
+        args: &[Operand<'tcx>],
+        source_info: SourceInfo,
+    ) -> Option<()> {
+        let def_id = match *func.ty(&self.local_decls, self.tcx).kind() {
+            ty::FnDef(def_id, _) => def_id,
+            _ => return None,
+        };
+        if self.tcx.fn_sig(def_id).abi() != Abi::RustIntrinsic
+            && !self.is_integer_inherent_method(def_id)
+        {
+            return None;
+        }
+        // Associated constants are evaluated by CTFE, which reports these calls itself.
+        if self.tcx.def_kind(self.ecx.frame().body.source.def_id()) == DefKind::AssocConst {
+            return None;
+        }
+        let name = self.tcx.item_name(def_id);
+        let bin_op = match name {
+            sym::unchecked_add => BinOp::Add,
+            sym::unchecked_sub => BinOp::Sub,
+            sym::unchecked_mul => BinOp::Mul,
+            sym::unchecked_div | sym::exact_div => BinOp::Div,
+            sym::unchecked_rem => BinOp::Rem,
+            sym::unchecked_shl => BinOp::Shl,
+            sym::unchecked_shr => BinOp::Shr,
+            _ => return None,
+        };
+        let (l, r) = match args {
+            [l, r] => (l, r),
+            _ => return None,
+        };
+        let l = self.use_ecx(|this| this.ecx.read_immediate(&this.ecx.eval_operand(l, None)?))?;
+        let r = self.use_ecx(|this| this.ecx.read_immediate(&this.ecx.eval_operand(r, None)?))?;
+        if !l.layout.ty.is_integral() || !r.layout.ty.is_integral() {
+            return None;
+        }
+
+        let r_bits = self.use_ecx(|this| this.ecx.force_bits(r.to_scalar()?, r.layout.size))?;
+        let violation = if matches!(bin_op, BinOp::Div | BinOp::Rem) && r_bits == 0 {
+            "divides by zero"
+        } else {
+            let (_res, overflow, _ty) =
+                self.use_ecx(|this| this.ecx.overflowing_binary_op(bin_op, &l, &r))?;
+            if overflow && matches!(bin_op, BinOp::Shl | BinOp::Shr) {
+                "shifts by at least the bit width of the type"
+            } else if overflow {
+                "overflows"
+            } else if name == sym::exact_div {
+                let (rem, _, _) =
+                    self.use_ecx(|this| this.ecx.overflowing_binary_op(BinOp::Rem, &l, &r))?;
+                if self.use_ecx(|this| this.ecx.force_bits(rem, l.layout.size))? == 0 {
+                    return Some(());
+                }
+                "has a remainder"
+            } else {
+                return Some(());
+            }
+        };
+
+        let lint_root = self.lint_root(source_info)?;
+        let (l, r) = (l.to_const_int(), r.to_const_int());
+        // Arguments that aren't constants in the source got their values from const propagation,
+        // so point at them to make it clear where the values come from.
+        let propagated_args: Vec<_> = args
+            .iter()
+            .zip(&[l, r])
+            .filter_map(|(arg, &value)| {
+                let local = arg.place()?.as_local()?;
+                let span = self.local_decls[local].source_info.span;
+                (span != source_info.span && source_info.span.contains(span)).then(|| (span, value))
+            })
+            .collect();
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::UNCHECKED_INTRINSIC_UB,
+            lint_root,
+            source_info.span,
+            |lint| {
+                let mut err =
+                    lint.build(&format!("this call to `{}` has undefined behavior", name));
+                err.span_label(
+                    source_info.span,
+                    format!("`{}` with `{:#?}` and `{:#?}` {}", name, l, r, violation),
+                );
+                for (span, value) in propagated_args {
+                    err.span_label(span, format!("this evaluates to `{:#?}`", value));
+                }
+                err.emit()
+            },
+        );
+        Some(())
+    }
+
+    /// Returns whether `def_id` is an inherent method of an integer type, like `u8::unchecked_add`.
+    fn is_integer_inherent_method(&self, def_id: DefId) -> bool {
+        self.tcx.impl_of_method(def_id).map_or(false, |impl_def_id| {
+            self.tcx.trait_id_of_impl(impl_def_id).is_none()
+                && self.tcx.type_of(impl_def_id).is_integral()
+        })
+    }
+
     fn propagate_operand(&mut self, operand: &mut Operand<'tcx>) {
         match *operand {
             Operand::Copy(l) | Operand::Move(l) => {
@@ -1062,6 +1200,30 @@ fn visit_local(&mut self, &local: &Local, context: PlaceContext, _: Location) {
     }
 }
 
+/// Counts the uses of a constant in the basic blocks of a body.
+struct ConstantUseCounter<'tcx> {
+    literal: ConstantKind<'tcx>,
+    uses: usize,
+}
+
+impl<'tcx> ConstantUseCounter<'tcx> {
+    fn count(body: &Body<'tcx>, literal: ConstantKind<'tcx>) -> usize {
+        let mut counter = ConstantUseCounter { literal, uses: 0 };
+        for (bb, data) in body.basic_blocks().iter_enumerated() {
+            counter.visit_basic_block_data(bb, data);
+        }
+        counter.uses
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ConstantUseCounter<'tcx> {
+    fn visit_constant(&mut self, constant: &Constant<'tcx>, _: Location) {
+        if constant.literal == self.literal {
+            self.uses += 1;
+        }
+    }
+}
+
 impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
     fn tcx(&self) -> TyCtxt<'tcx> {
         self.tcx
@@ -1278,7 +1440,9 @@ fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             //
             // NOTE: because LLVM codegen gives slight performance regressions with it, so this is
             // gated on `mir_opt_level=3`.
-            TerminatorKind::Call { .. } => {}
+            TerminatorKind::Call { ref func, ref args, .. } => {
+                self.check_unchecked_intrinsic(func, args, source_info);
+            }
         }
 
         // We remove all Locals which are restricted in propagation to their containing blocks and
@@ -1,5 +1,6 @@
 //! Performs various peephole optimizations.
 
+use crate::transform::pass_order::PassOrder;
 use crate::transform::MirPass;
 use rustc_hir::Mutability;
 use rustc_middle::mir::{
@@ -11,6 +12,11 @@
 pub struct InstCombine;
 
 impl<'tcx> MirPass<'tcx> for InstCombine {
+    fn order_constraints(&self) -> &'static [PassOrder] {
+        // Cleans up the `Ne(_1, false)` left behind by `MatchBranchSimplification`.
+        &[PassOrder::After("MatchBranchSimplification")]
+    }
+
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         let (basic_blocks, local_decls) = body.basic_blocks_and_local_decls_mut();
         let ctx = InstCombineContext { tcx, local_decls };
@@ -1,4 +1,5 @@
 use crate::{shim, util};
+use pass_order::{PassOrder, PassOrderCache};
 use required_consts::RequiredConstsVisitor;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::steal::Steal;
@@ -10,8 +11,10 @@
 use rustc_middle::mir::{traversal, Body, ConstQualifs, MirPhase, Promoted};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt, TypeFoldable};
-use rustc_span::{Span, Symbol};
+use rustc_session::config::MirPassOrder;
+use rustc_span::{sym, Span, Symbol};
 use std::borrow::Cow;
+use std::io::Write;
 
 pub mod add_call_guards;
 pub mod add_moves_for_packed_drops;
@@ -40,6 +43,7 @@
 pub mod multiple_return_terminators;
 pub mod no_landing_pads;
 pub mod nrvo;
+pub mod pass_order;
 pub mod promote_consts;
 pub mod remove_noop_landing_pads;
 pub mod remove_storage_markers;
@@ -62,6 +66,7 @@ pub(crate) fn provide(providers: &mut Providers) {
     self::check_packed_ref::provide(providers);
     *providers = Providers {
         mir_keys,
+        skipped_mir_passes,
         mir_const,
         mir_const_qualif: |tcx, def_id| {
             let def_id = def_id.expect_local();
@@ -157,14 +162,97 @@ fn name(&self) -> Cow<'_, str> {
         default_name::<Self>()
     }
 
+    /// Constraints on when this pass has to run relative to the other passes of
+    /// the same phase. See the `pass_order` module for details.
+    fn order_constraints(&self) -> &'static [PassOrder] {
+        &[]
+    }
+
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>);
 }
 
+/// The names of all the passes `run_passes` runs, which `#[rustc_mir(skip_pass = "...")]`
+/// attributes are checked against. Passes with a label are named with their label.
+const PASS_NAMES: &[&str] = &[
+    "AddMovesForPackedDrops",
+    "AddRetag",
+    "CheckConstItemMutation",
+    "CheckPackedRef",
+    "CleanupNonCodegenStatements",
+    "ConstDebugInfo",
+    "ConstGoto",
+    "ConstProp",
+    "CriticalCallEdges",
+    "Deaggregator",
+    "DeduplicateBlocks",
+    "DestinationPropagation",
+    "EarlyOtherwiseBranch",
+    "ElaborateDrops",
+    "FunctionItemReferences",
+    "Inline",
+    "InstCombine",
+    "InstrumentCoverage",
+    "LowerIntrinsics",
+    "MatchBranchSimplification",
+    "MultipleReturnTerminators",
+    "NoLandingPads",
+    "PreCodegen",
+    "PromoteTemps",
+    "RemoveNoopLandingPads",
+    "RemoveStorageMarkers",
+    "RemoveUnneededDrops",
+    "RemoveZsts",
+    "RenameReturnPlace",
+    "SanityCheck",
+    "SimplifyArmIdentity",
+    "SimplifyBranchSame",
+    "SimplifyBranches-after-const-prop",
+    "SimplifyBranches-final",
+    "SimplifyBranches-initial",
+    "SimplifyCfg-after-uninhabited-enum-branching",
+    "SimplifyCfg-early-opt",
+    "SimplifyCfg-elaborate-drops",
+    "SimplifyCfg-final",
+    "SimplifyCfg-initial",
+    "SimplifyCfg-make_shim",
+    "SimplifyCfg-promote-consts",
+    "SimplifyComparisonIntegral",
+    "SimplifyLocals",
+    "StateTransform",
+    "UninhabitedEnumBranching",
+    "UnreachablePropagation",
+];
+
+/// Returns the names of the passes which `#[rustc_mir(skip_pass = "...")]` attributes keep
+/// from running on the MIR of `def_id`, so that miscompilations can be bisected one
+/// function at a time. Nothing stops passes the later phases rely on from being skipped.
+fn skipped_mir_passes(tcx: TyCtxt<'_>, def_id: LocalDefId) -> &'_ [Symbol] {
+    let names = tcx
+        .get_attrs(def_id.to_def_id())
+        .iter()
+        .filter(|attr| tcx.sess.check_name(attr, sym::rustc_mir))
+        .flat_map(|attr| attr.meta_item_list().unwrap_or_default())
+        .filter(|item| item.has_name(sym::skip_pass))
+        .filter_map(|item| match item.value_str() {
+            None => {
+                tcx.sess.span_err(item.span(), "`skip_pass` requires the name of a pass");
+                None
+            }
+            Some(name) if !PASS_NAMES.contains(&&*name.as_str()) => {
+                tcx.sess.span_err(item.span(), &format!("unknown MIR pass `{}`", name));
+                None
+            }
+            name => name,
+        });
+    tcx.arena.alloc_from_iter(names)
+}
+
 pub fn run_passes(
     tcx: TyCtxt<'tcx>,
     body: &mut Body<'tcx>,
     mir_phase: MirPhase,
     passes: &[&[&dyn MirPass<'tcx>]],
+    order: &PassOrderCache,
 ) {
     let phase_index = mir_phase.phase_index();
     let validate = tcx.sess.opts.debugging_opts.validate_mir;
@@ -178,6 +266,27 @@ pub fn run_passes(
             .run_pass(tcx, body);
     }
 
+    let skipped = match body.source.def_id().as_local() {
+        Some(def_id) => tcx.skipped_mir_passes(def_id),
+        None => &[],
+    };
+    let passes: Vec<_> = order
+        .get(passes)
+        .iter()
+        .map(|&(list, i)| passes[list][i])
+        .inspect(|pass| {
+            debug_assert!(
+                PASS_NAMES.contains(&&*pass.name()),
+                "`{}` is missing from `PASS_NAMES`",
+                pass.name()
+            )
+        })
+        .filter(|pass| !skipped.iter().any(|name| name.as_str() == pass.name()))
+        .collect();
+    if tcx.sess.opts.debugging_opts.mir_pass_order == Some(MirPassOrder::Dump) {
+        dump_pass_order(tcx, body, phase_index, &passes);
+    }
+
     let mut index = 0;
     let mut run_pass = |pass: &dyn MirPass<'tcx>| {
         let run_hooks = |body: &_, index, is_after| {
@@ -204,10 +313,8 @@ pub fn run_passes(
         index += 1;
     };
 
-    for pass_group in passes {
-        for pass in *pass_group {
-            run_pass(*pass);
-        }
+    for pass in passes {
+        run_pass(pass);
     }
 
     body.phase = mir_phase;
@@ -218,6 +325,27 @@ pub fn run_passes(
     }
 }
 
+/// Writes the order in which `passes` run on `body` to a file in `-Z dump-mir-dir`.
+fn dump_pass_order(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    phase_index: usize,
+    passes: &[&dyn MirPass<'tcx>],
+) {
+    let mut file = util::pretty::create_dump_file(
+        tcx,
+        "txt",
+        Some(&format_args!("{:03}", phase_index)),
+        "pass_order",
+        &0,
+        body.source,
+    )
+    .expect("Unexpected error creating MIR pass order file");
+    for pass in passes {
+        writeln!(file, "{}", pass.name()).expect("Unexpected IO error dumping MIR pass order");
+    }
+}
+
 fn mir_const_qualif(tcx: TyCtxt<'_>, def: ty::WithOptConstParam<LocalDefId>) -> ConstQualifs {
     let const_kind = tcx.hir().body_const_context(def.did);
 
@@ -267,6 +395,7 @@ fn mir_const<'tcx>(
 
     util::dump_mir(tcx, None, "mir_map", &0, &body, |_, _| Ok(()));
 
+    static ORDER: PassOrderCache = PassOrderCache::new();
     run_passes(
         tcx,
         &mut body,
@@ -280,6 +409,7 @@ fn mir_const<'tcx>(
             &simplify::SimplifyCfg::new("initial"),
             &rustc_peek::SanityCheck,
         ]],
+        &ORDER,
     );
     tcx.alloc_steal_mir(body)
 }
@@ -314,10 +444,15 @@ fn mir_promoted(
         &simplify::SimplifyCfg::new("promote-consts"),
     ];
 
-    let opt_coverage: &[&dyn MirPass<'tcx>] =
-        if tcx.sess.instrument_coverage() { &[&coverage::InstrumentCoverage] } else { &[] };
+    static ORDER: PassOrderCache = PassOrderCache::new();
+    static ORDER_WITH_COVERAGE: PassOrderCache = PassOrderCache::new();
+    let (opt_coverage, order): (&[&dyn MirPass<'tcx>], _) = if tcx.sess.instrument_coverage() {
+        (&[&coverage::InstrumentCoverage], &ORDER_WITH_COVERAGE)
+    } else {
+        (&[], &ORDER)
+    };
 
-    run_passes(tcx, &mut body, MirPhase::ConstPromotion, &[promote, opt_coverage]);
+    run_passes(tcx, &mut body, MirPhase::ConstPromotion, &[promote, opt_coverage], order);
 
     let promoted = promote_pass.promoted_fragments.into_inner();
     (tcx.alloc_steal_mir(body), tcx.alloc_steal_promoted(promoted))
@@ -384,6 +519,7 @@ fn inner_mir_for_ctfe(tcx: TyCtxt<'_>, def: ty::WithOptConstParam<LocalDefId>) -
                 &const_prop::ConstProp,
             ];
 
+            static ORDER: PassOrderCache = PassOrderCache::new();
             #[rustfmt::skip]
             run_passes(
                 tcx,
@@ -392,6 +528,7 @@ fn inner_mir_for_ctfe(tcx: TyCtxt<'_>, def: ty::WithOptConstParam<LocalDefId>) -
                 &[
                     optimizations,
                 ],
+                &ORDER,
             );
         }
     }
@@ -469,7 +606,8 @@ fn run_post_borrowck_cleanup_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tc
         &deaggregator::Deaggregator,
     ];
 
-    run_passes(tcx, body, MirPhase::DropLowering, &[post_borrowck_cleanup]);
+    static ORDER: PassOrderCache = PassOrderCache::new();
+    run_passes(tcx, body, MirPhase::DropLowering, &[post_borrowck_cleanup], &ORDER);
 }
 
 fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
@@ -529,6 +667,12 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &dump_mir::Marker("PreCodegen"),
     ];
 
+    // Each of the pass lists above resolves its order once.
+    static GENERATOR_ORDER: PassOrderCache = PassOrderCache::new();
+    static NO_OPT_GENERATOR_ORDER: PassOrderCache = PassOrderCache::new();
+    static OPTIMIZATION_ORDER: PassOrderCache = PassOrderCache::new();
+    static NO_OPT_OPTIMIZATION_ORDER: PassOrderCache = PassOrderCache::new();
+
     // End of pass declarations, now actually run the passes.
     // Generator Lowering
     #[rustfmt::skip]
@@ -543,6 +687,7 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
                 no_optimizations_with_generators
             }
         ],
+        if mir_opt_level > 0 { &GENERATOR_ORDER } else { &NO_OPT_GENERATOR_ORDER },
     );
 
     // Main optimization passes
@@ -555,6 +700,7 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             if mir_opt_level > 0 { optimizations } else { no_optimizations },
             pre_codegen_cleanup,
         ],
+        if mir_opt_level > 0 { &OPTIMIZATION_ORDER } else { &NO_OPT_OPTIMIZATION_ORDER },
     );
 }
 
@@ -0,0 +1,127 @@
+//! Ordering constraints between MIR passes.
+//!
+//! A pass can require to run before or after another pass of the same phase by
+//! returning constraints from `MirPass::order_constraints`. `run_passes` then
+//! reorders the passes it was given so that all of these constraints hold,
+//! while staying as close as possible to the order in which they were listed.
+//!
+//! The order only depends on the passes themselves, so it is resolved once per
+//! pass list and kept in a `PassOrderCache`.
+//!
+//! Use `-Z mir-pass-order=dump` to write the resolved order of each phase to
+//! `-Z dump-mir-dir`.
+
+use super::MirPass;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::lazy::SyncOnceCell;
+
+#[cfg(test)]
+mod tests;
+
+/// Where a pass has to run relative to another pass, which is identified by
+/// its name. Passes with a label, such as `SimplifyCfg-final`, can also be
+/// referred to by their unlabeled name, e.g. `SimplifyCfg`, which matches all
+/// of them.
+///
+/// Constraints which refer to passes that are not part of the same phase are
+/// ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassOrder {
+    /// The pass has to run after every run of the given pass.
+    After(&'static str),
+    /// The pass has to run before every run of the given pass.
+    Before(&'static str),
+}
+
+fn matches_name(pass: &dyn MirPass<'_>, name: &str) -> bool {
+    let pass_name = pass.name();
+    pass_name == name || pass_name.strip_prefix(name).map_or(false, |label| label.starts_with('-'))
+}
+
+/// The resolved order of one of the pass lists given to `run_passes`, shared by
+/// all the bodies that list runs on.
+///
+/// Each cache must only ever be used with the same list of passes.
+pub struct PassOrderCache(SyncOnceCell<Vec<(usize, usize)>>);
+
+impl PassOrderCache {
+    pub const fn new() -> Self {
+        PassOrderCache(SyncOnceCell::new())
+    }
+
+    /// Returns where to find the passes of `passes` in the order in which they
+    /// have to run, as the index of their list and their index in that list.
+    /// The order is resolved the first time this cache is used.
+    crate fn get(&self, passes: &[&[&dyn MirPass<'_>]]) -> &[(usize, usize)] {
+        let order = self.0.get_or_init(|| {
+            let positions: Vec<_> = passes
+                .iter()
+                .enumerate()
+                .flat_map(|(list, list_passes)| (0..list_passes.len()).map(move |i| (list, i)))
+                .collect();
+            resolve_pass_order(&passes.concat()).into_iter().map(|i| positions[i]).collect()
+        });
+        debug_assert_eq!(
+            order.len(),
+            passes.iter().map(|list| list.len()).sum::<usize>(),
+            "`PassOrderCache` used for different lists of passes"
+        );
+        order
+    }
+}
+
+/// Sorts `passes` such that all of their ordering constraints are satisfied,
+/// returning their indices in the order in which they have to run.
+/// Whenever several passes could run next, the one listed first is picked, so
+/// passes without any constraints keep the order in which they were listed.
+///
+/// Panics if the constraints are cyclic, since that is a bug in the pass
+/// declarations.
+crate fn resolve_pass_order(passes: &[&dyn MirPass<'_>]) -> Vec<usize> {
+    // `successors[i]` are the passes which have to run after `passes[i]`.
+    let mut successors = vec![vec![]; passes.len()];
+    let mut predecessor_count = vec![0; passes.len()];
+    for (i, pass) in passes.iter().enumerate() {
+        for constraint in pass.order_constraints() {
+            let name = match *constraint {
+                PassOrder::After(name) | PassOrder::Before(name) => name,
+            };
+            for (j, other) in passes.iter().enumerate() {
+                if i == j || !matches_name(*other, name) {
+                    continue;
+                }
+                let (first, second) = match constraint {
+                    PassOrder::After(_) => (j, i),
+                    PassOrder::Before(_) => (i, j),
+                };
+                successors[first].push(second);
+                predecessor_count[second] += 1;
+            }
+        }
+    }
+
+    // Kahn's algorithm, always picking the earliest listed pass that is ready.
+    let mut ready: BinaryHeap<_> =
+        (0..passes.len()).filter(|&i| predecessor_count[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(passes.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &j in &successors[i] {
+            predecessor_count[j] -= 1;
+            if predecessor_count[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    if order.len() != passes.len() {
+        let cycle: Vec<_> = (0..passes.len())
+            .filter(|&i| predecessor_count[i] != 0)
+            .map(|i| passes[i].name().into_owned())
+            .collect();
+        bug!("cyclic ordering constraints between the MIR passes {}", cycle.join(", "));
+    }
+
+    order
+}
@@ -0,0 +1,66 @@
+use super::*;
+use rustc_middle::mir::Body;
+use rustc_middle::ty::TyCtxt;
+use std::borrow::Cow;
+
+struct Pass(&'static str, &'static [PassOrder]);
+
+impl<'tcx> MirPass<'tcx> for Pass {
+    fn name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.0)
+    }
+
+    fn order_constraints(&self) -> &'static [PassOrder] {
+        self.1
+    }
+
+    fn run_pass(&self, _: TyCtxt<'tcx>, _: &mut Body<'tcx>) {
+        unreachable!()
+    }
+}
+
+fn resolve(passes: &[Pass]) -> Vec<String> {
+    let passes: Vec<&dyn MirPass<'_>> = passes.iter().map(|pass| pass as _).collect();
+    resolve_pass_order(&passes).into_iter().map(|i| passes[i].name().into_owned()).collect()
+}
+
+#[test]
+fn unconstrained_order_is_kept() {
+    let passes = [Pass("A", &[]), Pass("B", &[]), Pass("C", &[])];
+    assert_eq!(resolve(&passes), ["A", "B", "C"]);
+}
+
+#[test]
+fn after_and_before() {
+    let passes = [
+        Pass("A", &[PassOrder::After("C")]),
+        Pass("B", &[]),
+        Pass("C", &[]),
+        Pass("D", &[PassOrder::Before("B")]),
+    ];
+    assert_eq!(resolve(&passes), ["C", "A", "D", "B"]);
+}
+
+#[test]
+fn labeled_passes() {
+    let passes = [
+        Pass("Lint", &[PassOrder::After("Simplify")]),
+        Pass("Simplify-initial", &[]),
+        Pass("SimplifyLocals", &[]),
+        Pass("Simplify-final", &[]),
+    ];
+    assert_eq!(resolve(&passes), ["Simplify-initial", "SimplifyLocals", "Simplify-final", "Lint"]);
+}
+
+#[test]
+fn missing_passes_are_ignored() {
+    let passes = [Pass("A", &[PassOrder::After("Z")]), Pass("B", &[PassOrder::Before("Y")])];
+    assert_eq!(resolve(&passes), ["A", "B"]);
+}
+
+#[test]
+#[should_panic]
+fn cycle() {
+    let passes = [Pass("A", &[PassOrder::After("B")]), Pass("B", &[PassOrder::After("A")])];
+    resolve(&passes);
+}
@@ -6,14 +6,16 @@
 
 use super::MirPass;
 use rustc_index::bit_set::BitSet;
+use rustc_index::vec::Idx;
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::tcx::PlaceTy;
 use rustc_middle::mir::traversal;
 use rustc_middle::mir::visit::{PlaceContext, Visitor};
 use rustc_middle::mir::{
-    AggregateKind, BasicBlock, Body, BorrowKind, Local, Location, MirPhase, Operand, PlaceElem,
-    PlaceRef, ProjectionElem, Rvalue, SourceScope, Statement, StatementKind, Terminator,
-    TerminatorKind,
+    AggregateKind, BasicBlock, Body, BorrowKind, Local, Location, MirPhase, Operand, Place,
+    PlaceElem, PlaceRef, ProjectionElem, Rvalue, SourceScope, Statement, StatementKind,
+    Terminator, TerminatorKind,
 };
 use rustc_middle::ty::fold::BottomUpFolder;
 use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt, TypeFoldable};
@@ -48,7 +50,7 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             .iterate_to_fixpoint()
             .into_results_cursor(body);
 
-        TypeChecker {
+        let mut checker = TypeChecker {
             when: &self.when,
             body,
             tcx,
@@ -57,8 +59,17 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             reachable_blocks: traversal::reachable_as_bitset(body),
             storage_liveness,
             place_cache: Vec::new(),
+        };
+
+        // Computing the type of a place with ill-typed projections ICEs right away, without
+        // mentioning the pass which built it, so those are checked before anything else.
+        let mut projection_checker = ProjectionChecker { checker: &checker, valid: true };
+        projection_checker.visit_body(body);
+        if !projection_checker.valid {
+            return;
         }
-        .visit_body(body);
+
+        checker.visit_body(body);
     }
 }
 
@@ -180,6 +191,124 @@ fn mir_assign_valid_types(&self, src: Ty<'tcx>, dest: Ty<'tcx>) -> bool {
         // differences. So we compare ignoring lifetimes.
         equal_up_to_regions(self.tcx, param_env, src, dest)
     }
+
+    /// Checks the projections of `place` one after the other, stopping at the first one which
+    /// cannot be applied to the type it projects out of. Returns whether all of them are valid.
+    fn check_place_projections(&self, place: PlaceRef<'tcx>, location: Location) -> bool {
+        let local_decl = match self.body.local_decls.get(place.local) {
+            Some(local_decl) => local_decl,
+            None => {
+                self.fail(
+                    location,
+                    format!(
+                        "local {:?} has no corresponding declaration in `body.local_decls`",
+                        place.local
+                    ),
+                );
+                return false;
+            }
+        };
+
+        let mut place_ty = PlaceTy::from_ty(local_decl.ty);
+        for (i, &elem) in place.projection.iter().enumerate() {
+            if let Err(msg) = self.check_projection_elem(place_ty, elem) {
+                let base = PlaceRef { local: place.local, projection: &place.projection[..i] };
+                self.fail(
+                    location,
+                    format!("invalid projection {:?} of {:?}: {}", elem, base, msg),
+                );
+                return false;
+            }
+            place_ty = place_ty.projection_ty(self.tcx, elem);
+        }
+        true
+    }
+
+    fn check_projection_elem(
+        &self,
+        place_ty: PlaceTy<'tcx>,
+        elem: PlaceElem<'tcx>,
+    ) -> Result<(), String> {
+        let ty = place_ty.ty;
+        match elem {
+            ProjectionElem::Deref => {
+                if ty.builtin_deref(true).is_none() {
+                    return Err(format!("dereference of non-pointer type {}", ty));
+                }
+            }
+            ProjectionElem::Field(field, field_ty) => {
+                let expected_ty = match *ty.kind() {
+                    ty::Adt(adt_def, substs) => {
+                        let variant = match place_ty.variant_index {
+                            Some(variant_index) => &adt_def.variants[variant_index],
+                            None if adt_def.is_enum() => {
+                                return Err(format!("field of enum {} without a downcast", ty));
+                            }
+                            None => adt_def.non_enum_variant(),
+                        };
+                        variant
+                            .fields
+                            .get(field.index())
+                            .map(|field_def| field_def.ty(self.tcx, substs))
+                    }
+                    ty::Tuple(tys) => tys.get(field.index()).map(|ty| ty.expect_ty()),
+                    ty::Closure(_, substs) => {
+                        if field.index() >= substs.as_closure().upvar_tys().count() {
+                            return Err(format!("field {:?} out of range for {}", field, ty));
+                        }
+                        return Ok(());
+                    }
+                    // The fields of a generator depend on its layout.
+                    ty::Generator(..) => return Ok(()),
+                    _ => return Err(format!("field of non-aggregate type {}", ty)),
+                };
+                match expected_ty {
+                    Some(expected_ty) if !self.mir_assign_valid_types(expected_ty, field_ty) => {
+                        return Err(format!(
+                            "field {:?} has type {}, but the projection expects {}",
+                            field, expected_ty, field_ty
+                        ));
+                    }
+                    Some(_) => {}
+                    None => return Err(format!("field {:?} out of range for {}", field, ty)),
+                }
+            }
+            ProjectionElem::Index(_) | ProjectionElem::ConstantIndex { .. } => {
+                if ty.builtin_index().is_none() {
+                    return Err(format!("index into non-array type {}", ty));
+                }
+            }
+            ProjectionElem::Subslice { .. } => {
+                if !matches!(ty.kind(), ty::Array(..) | ty::Slice(..)) {
+                    return Err(format!("subslice of non-array type {}", ty));
+                }
+            }
+            ProjectionElem::Downcast(_, variant_index) => match ty.kind() {
+                ty::Adt(adt_def, _) if adt_def.is_enum() => {
+                    if variant_index.index() >= adt_def.variants.len() {
+                        return Err(format!("variant {:?} out of range for {}", variant_index, ty));
+                    }
+                }
+                ty::Generator(..) => {}
+                _ => return Err(format!("downcast of non-enum type {}", ty)),
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Checks that each projection of a place is applied to a type it can project out of.
+struct ProjectionChecker<'a, 'b, 'tcx> {
+    checker: &'a TypeChecker<'b, 'tcx>,
+    valid: bool,
+}
+
+impl<'a, 'b, 'tcx> Visitor<'tcx> for ProjectionChecker<'a, 'b, 'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, _context: PlaceContext, location: Location) {
+        if !self.checker.check_place_projections(place.as_ref(), location) {
+            self.valid = false;
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for TypeChecker<'a, 'tcx> {
@@ -1,5 +1,6 @@
 use rustc_errors::{struct_span_err, DiagnosticBuilder, DiagnosticId};
 use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::symbol::Symbol;
 use rustc_span::{MultiSpan, Span};
 
 impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
@@ -48,12 +49,12 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         new_loan_span: Span,
         desc: &str,
         opt_via: &str,
+        new_method: Option<Symbol>,
         old_loan_span: Span,
         old_opt_via: &str,
+        old_method: Option<Symbol>,
         old_load_end_span: Option<Span>,
     ) -> DiagnosticBuilder<'cx> {
-        let via =
-            |msg: &str| if msg.is_empty() { "".to_string() } else { format!(" (via {})", msg) };
         let mut err = struct_span_err!(
             self,
             new_loan_span,
@@ -80,11 +81,11 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         } else {
             err.span_label(
                 old_loan_span,
-                format!("first mutable borrow occurs here{}", via(old_opt_via)),
+                format!("first mutable borrow {}{}", occurs(old_method), via(old_opt_via)),
             );
             err.span_label(
                 new_loan_span,
-                format!("second mutable borrow occurs here{}", via(opt_via)),
+                format!("second mutable borrow {}{}", occurs(new_method), via(opt_via)),
             );
             if let Some(old_load_end_span) = old_load_end_span {
                 err.span_label(old_load_end_span, "first borrow ends here");
@@ -137,16 +138,17 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             self,
             new_loan_span,
             E0500,
-            "closure requires unique access to {} but {} is already borrowed{}",
+            "closure requires unique access to {}{} but {} is already borrowed{}",
             desc_new,
+            via(opt_via),
             noun_old,
-            old_opt_via,
+            via(old_opt_via),
         );
         err.span_label(
             new_loan_span,
-            format!("{} construction occurs here{}", container_name, opt_via),
+            format!("{} construction occurs here{}", container_name, via(opt_via)),
         );
-        err.span_label(old_loan_span, format!("borrow occurs here{}", old_opt_via));
+        err.span_label(old_loan_span, format!("borrow occurs here{}", via(old_opt_via)));
         if let Some(previous_end_span) = previous_end_span {
             err.span_label(previous_end_span, "borrow ends here");
         }
@@ -160,6 +162,7 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         desc_new: &str,
         opt_via: &str,
         kind_new: &str,
+        new_method: Option<Symbol>,
         old_loan_span: Span,
         old_opt_via: &str,
         previous_end_span: Option<Span>,
@@ -172,16 +175,16 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             "cannot borrow {}{} as {} because previous closure \
              requires unique access",
             desc_new,
-            opt_via,
+            via(opt_via),
             kind_new,
         );
         err.span_label(
             new_loan_span,
-            format!("{}borrow occurs here{}", second_borrow_desc, opt_via),
+            format!("{}borrow {}{}", second_borrow_desc, occurs(new_method), via(opt_via)),
         );
         err.span_label(
             old_loan_span,
-            format!("{} construction occurs here{}", container_name, old_opt_via),
+            format!("{} construction occurs here{}", container_name, via(old_opt_via)),
         );
         if let Some(previous_end_span) = previous_end_span {
             err.span_label(previous_end_span, "borrow from closure ends here");
@@ -195,14 +198,14 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         desc_new: &str,
         msg_new: &str,
         kind_new: &str,
+        new_method: Option<Symbol>,
         old_span: Span,
         noun_old: &str,
         kind_old: &str,
         msg_old: &str,
+        old_method: Option<Symbol>,
         old_load_end_span: Option<Span>,
     ) -> DiagnosticBuilder<'cx> {
-        let via =
-            |msg: &str| if msg.is_empty() { "".to_string() } else { format!(" (via {})", msg) };
         let mut err = struct_span_err!(
             self,
             span,
@@ -218,8 +221,7 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
 
         if msg_new == "" {
             // If `msg_new` is empty, then this isn't a borrow of a union field.
-            err.span_label(span, format!("{} borrow occurs here", kind_new));
-            err.span_label(old_span, format!("{} borrow occurs here", kind_old));
+            err.span_label(span, format!("{} borrow {}", kind_new, occurs(new_method)));
         } else {
             // If `msg_new` isn't empty, then this a borrow of a union field.
             err.span_label(
@@ -229,8 +231,11 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
                     kind_new, msg_new, msg_old,
                 ),
             );
-            err.span_label(old_span, format!("{} borrow occurs here{}", kind_old, via(msg_old)));
         }
+        err.span_label(
+            old_span,
+            format!("{} borrow {}{}", kind_old, occurs(old_method), via(msg_old)),
+        );
 
         if let Some(old_load_end_span) = old_load_end_span {
             err.span_label(old_load_end_span, format!("{} borrow ends here", kind_old));
@@ -341,6 +346,22 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         )
     }
 
+    crate fn cannot_call_fn_once_closure_again(
+        &self,
+        call_span: Span,
+        closure_path: Option<String>,
+    ) -> DiagnosticBuilder<'cx> {
+        let closure_path = closure_path.map(|cp| format!(" `{}`", cp)).unwrap_or_default();
+
+        struct_span_err!(
+            self,
+            call_span,
+            E0382,
+            "closure{} cannot be called more than once",
+            closure_path,
+        )
+    }
+
     crate fn cannot_borrow_path_as_mutable_because(
         &self,
         span: Span,
@@ -485,3 +506,17 @@ fn struct_span_err_with_code<S: Into<MultiSpan>>(
         escapes_from,
     )
 }
+
+/// Formats the ` (via ...)` suffix naming the place a borrow goes through, if there is one.
+fn via(msg: &str) -> String {
+    if msg.is_empty() { "".to_string() } else { format!(" (via {})", msg) }
+}
+
+/// Where a borrow occurs, naming the method it was created for if it was an autoref,
+/// as in `v[0] = 1` calling `index_mut`.
+fn occurs(method: Option<Symbol>) -> String {
+    match method {
+        Some(method) => format!("occurs due to call to `{}`", method),
+        None => "occurs here".to_string(),
+    }
+}
@@ -10,7 +10,9 @@
 mod generic_graph;
 pub(crate) mod generic_graphviz;
 mod graphviz;
+pub(crate) mod mutable_uses;
 pub(crate) mod pretty;
+pub(crate) mod spans;
 pub(crate) mod spanview;
 
 pub use self::aggregate::expand_aggregate;
@@ -0,0 +1,66 @@
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::{Body, Local, Location, Place};
+
+/// How a local is mutated by a body, used to suggest making bindings and references
+/// mutable only where it is needed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+crate struct MutableUse {
+    crate writes: Writes,
+}
+
+/// The assignments of a local.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+crate enum Writes {
+    /// The local is never assigned, like an argument which is only read.
+    None,
+    /// The local is assigned as a whole by a single statement, usually its initialization.
+    /// The statement may still run more than once, in a loop.
+    Once(Location),
+    /// The local is assigned by several statements, or a part of it is assigned.
+    Several,
+}
+
+crate trait FindMutableUses {
+    /// Tells how each local of the body is mutated.
+    fn find_mutable_uses(&self) -> IndexVec<Local, MutableUse>;
+}
+
+impl<'tcx> FindMutableUses for Body<'tcx> {
+    fn find_mutable_uses(&self) -> IndexVec<Local, MutableUse> {
+        let unused = MutableUse { writes: Writes::None };
+        let mut visitor =
+            MutableUsesVisitor { uses: IndexVec::from_elem(unused, &self.local_decls) };
+        visitor.visit_body(self);
+        visitor.uses
+    }
+}
+
+struct MutableUsesVisitor {
+    uses: IndexVec<Local, MutableUse>,
+}
+
+impl<'tcx> Visitor<'tcx> for MutableUsesVisitor {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        let mutating_use = match context {
+            PlaceContext::MutatingUse(mutating_use) => mutating_use,
+            PlaceContext::NonMutatingUse(_) | PlaceContext::NonUse(_) => return,
+        };
+        let use_ = &mut self.uses[place.local];
+        match mutating_use {
+            // Every local is dropped, whether it is mutable or not.
+            MutatingUseContext::Drop | MutatingUseContext::Retag => {}
+            // Mutating the data behind the local, or borrowing the local mutably, doesn't
+            // assign it.
+            _ if place.is_indirect() => {}
+            MutatingUseContext::Borrow | MutatingUseContext::AddressOf => {}
+            _ if place.projection.is_empty() && context.is_place_assignment() => {
+                use_.writes = match use_.writes {
+                    Writes::None => Writes::Once(location),
+                    Writes::Once(_) | Writes::Several => Writes::Several,
+                };
+            }
+            _ => use_.writes = Writes::Several,
+        }
+    }
+}
@@ -0,0 +1,712 @@
+//! Finding the source spans that explain a use of a place in MIR.
+//!
+//! Uses of a place are not always where they appear to be in the MIR: a variable captured by a
+//! closure is used where the closure is created, a binding borrowed for a match guard is used by
+//! the guard, and a value passed as `self` is used by the method call. The functions in this
+//! module find the spans a diagnostic should point at instead, by matching MIR statements back to
+//! the HIR expressions they were built from:
+//!
+//! - [`move_spans`] for a move or copy of a place,
+//! - [`borrow_spans`] for a borrow of a place,
+//! - [`closure_capture_span`] for the capture of a place by a particular closure.
+//!
+//! They only need the MIR body and the `TyCtxt`, so they can be used outside of borrowck, e.g. by
+//! liveness diagnostics about captured variables.
+
+use rustc_errors::DiagnosticBuilder;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::lang_items::LangItemGroup;
+use rustc_hir::GeneratorKind;
+use rustc_middle::hir::place::{
+    Place as HirPlace, PlaceBase as HirPlaceBase, ProjectionKind as HirProjectionKind,
+};
+use rustc_middle::mir::{
+    AggregateKind, Body, BorrowKind, FakeReadCause, LocalKind, Location, Operand, Place,
+    PlaceRef, ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
+};
+use rustc_middle::ty::{self, DefIdTree, Instance, Ty, TyCtxt};
+use rustc_span::{
+    hygiene::{DesugaringKind, ForLoopLoc},
+    symbol::{sym, Ident, Symbol},
+    Span,
+};
+
+/// The span(s) associated to a use of a place.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+crate enum UseSpans<'tcx> {
+    /// The access is caused by capturing a variable for a closure.
+    ClosureUse {
+        /// This is true if the captured variable was from a generator.
+        generator_kind: Option<GeneratorKind>,
+        /// The span of the args of the closure, including the `move` keyword if
+        /// it's present.
+        args_span: Span,
+        /// The span of the use resulting in capture kind
+        /// Check `ty::CaptureInfo` for more details
+        capture_kind_span: Span,
+        /// The span of the use resulting in the captured path
+        /// Check `ty::CaptureInfo` for more details
+        path_span: Span,
+        /// The captured path, e.g. `self.config.name`, if only part of the
+        /// variable is captured.
+        captured_path: Option<Symbol>,
+    },
+    /// The access is caused by capturing a variable for a closure which is
+    /// passed to an iterator adapter, e.g. `iter.map(|x| ...)`.
+    IteratorChainUse {
+        /// The name of the adapter the closure is passed to, e.g. `map`.
+        adapter: Symbol,
+        /// The span of the name of the adapter in the method call.
+        adapter_span: Span,
+        /// The span of the args of the closure, including the `move` keyword if
+        /// it's present.
+        args_span: Span,
+        /// The span of the use resulting in capture kind
+        capture_kind_span: Span,
+        /// The span of the use resulting in the captured path
+        path_span: Span,
+        /// The captured path, if only part of the variable is captured.
+        captured_path: Option<Symbol>,
+    },
+    /// The access is caused by using a variable as the receiver of a method
+    /// that takes 'self'
+    FnSelfUse {
+        /// The span of the variable being moved
+        var_span: Span,
+        /// The span of the method call on the variable
+        fn_call_span: Span,
+        /// The definition span of the method being called
+        fn_span: Span,
+        kind: FnSelfUseKind<'tcx>,
+    },
+    /// This access is caused by a `match` or `if let` pattern.
+    PatUse(Span),
+    /// The access is caused by borrowing a binding of a match arm for the
+    /// evaluation of its guard.
+    GuardUse {
+        /// The span of the binding, which is the one in the matching
+        /// alternative for or-patterns.
+        binding_span: Span,
+        /// The span of the guard the binding is borrowed for.
+        guard_span: Span,
+    },
+    /// This access has a single span associated to it: common case.
+    OtherUse(Span),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+crate enum FnSelfUseKind<'tcx> {
+    /// A normal method call of the form `receiver.foo(a, b, c)`
+    Normal {
+        self_arg: Ident,
+        implicit_into_iter: bool,
+        /// Whether the self type of the method call has an `.as_ref()` method.
+        /// Used for better diagnostics.
+        is_option_or_result: bool,
+    },
+    /// A call to `FnOnce::call_once`, desugared from `my_closure(a, b, c)`
+    FnOnceCall,
+    /// A call to an operator trait, desuraged from operator syntax (e.g. `a << b`)
+    Operator { self_arg: Ident },
+    /// A call to `Try::branch`, desugared from the `?` operator (e.g. `foo?`)
+    TryOperator {
+        /// Whether the operand is an `Option`, which can be borrowed with `.as_ref()`
+        /// before applying `?`. Doing so with a `Result` would also borrow the error,
+        /// which then can't be converted by `?` anymore.
+        is_option: bool,
+    },
+    DerefCoercion {
+        /// The `Span` of the `Target` associated type
+        /// in the `Deref` impl we are using.
+        deref_target: Span,
+        /// The type `T::Deref` we are dereferencing to
+        deref_target_ty: Ty<'tcx>,
+    },
+}
+
+impl UseSpans<'_> {
+    crate fn args_or_use(self) -> Span {
+        match self {
+            UseSpans::ClosureUse { args_span: span, .. }
+            | UseSpans::IteratorChainUse { args_span: span, .. }
+            | UseSpans::PatUse(span)
+            | UseSpans::GuardUse { binding_span: span, .. }
+            | UseSpans::OtherUse(span) => span,
+            UseSpans::FnSelfUse {
+                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
+            } => fn_call_span,
+            UseSpans::FnSelfUse { var_span, .. } => var_span,
+        }
+    }
+
+    /// Returns the span of `self`, in the case of a `ClosureUse` returns the `path_span`
+    crate fn var_or_use_path_span(self) -> Span {
+        match self {
+            UseSpans::ClosureUse { path_span: span, .. }
+            | UseSpans::IteratorChainUse { path_span: span, .. }
+            | UseSpans::PatUse(span)
+            | UseSpans::GuardUse { binding_span: span, .. }
+            | UseSpans::OtherUse(span) => span,
+            UseSpans::FnSelfUse {
+                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
+            } => fn_call_span,
+            UseSpans::FnSelfUse { var_span, .. } => var_span,
+        }
+    }
+
+    /// Returns the span of `self`, in the case of a `ClosureUse` returns the `capture_kind_span`
+    crate fn var_or_use(self) -> Span {
+        match self {
+            UseSpans::ClosureUse { capture_kind_span: span, .. }
+            | UseSpans::IteratorChainUse { capture_kind_span: span, .. }
+            | UseSpans::PatUse(span)
+            | UseSpans::GuardUse { binding_span: span, .. }
+            | UseSpans::OtherUse(span) => span,
+            UseSpans::FnSelfUse {
+                fn_call_span, kind: FnSelfUseKind::DerefCoercion { .. }, ..
+            } => fn_call_span,
+            UseSpans::FnSelfUse { var_span, .. } => var_span,
+        }
+    }
+
+    crate fn generator_kind(self) -> Option<GeneratorKind> {
+        match self {
+            UseSpans::ClosureUse { generator_kind, .. } => generator_kind,
+            _ => None,
+        }
+    }
+
+    // Add a span label to the arguments of the closure, if it exists.
+    crate fn args_span_label(
+        self,
+        err: &mut DiagnosticBuilder<'_>,
+        message: impl Into<String>,
+    ) {
+        if let UseSpans::ClosureUse { args_span, .. }
+        | UseSpans::IteratorChainUse { args_span, .. } = self
+        {
+            err.span_label(args_span, message);
+        }
+    }
+
+    // Add a span label to the use of the captured variable, if it exists.
+    // only adds label to the `path_span`
+    crate fn var_span_label_path_only(
+        self,
+        err: &mut DiagnosticBuilder<'_>,
+        message: impl Into<String>,
+    ) {
+        if let UseSpans::ClosureUse { path_span, .. }
+        | UseSpans::IteratorChainUse { path_span, .. } = self
+        {
+            err.span_label(path_span, message);
+        }
+    }
+
+    // Add a span label to the use of the captured variable, if it exists.
+    crate fn var_span_label(
+        self,
+        err: &mut DiagnosticBuilder<'_>,
+        message: impl Into<String>,
+        kind_desc: impl Into<String>,
+    ) {
+        if let UseSpans::GuardUse { guard_span, .. } = self {
+            err.span_label(guard_span, message);
+        } else if let UseSpans::ClosureUse { capture_kind_span, path_span, .. }
+        | UseSpans::IteratorChainUse { capture_kind_span, path_span, .. } = self
+        {
+            if capture_kind_span == path_span {
+                err.span_label(capture_kind_span, message);
+            } else {
+                let capture_kind_label =
+                    format!("capture is {} because of use here", kind_desc.into());
+                let path_label = message;
+                err.span_label(capture_kind_span, capture_kind_label);
+                err.span_label(path_span, path_label);
+            }
+        }
+    }
+
+    /// Returns `false` if this place is not used in a closure.
+    crate fn for_closure(&self) -> bool {
+        match *self {
+            UseSpans::ClosureUse { generator_kind, .. } => generator_kind.is_none(),
+            UseSpans::IteratorChainUse { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `false` if this place is not used in a generator.
+    crate fn for_generator(&self) -> bool {
+        match *self {
+            UseSpans::ClosureUse { generator_kind, .. } => generator_kind.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Describe the span associated with a use of a place.
+    crate fn describe(&self) -> String {
+        match *self {
+            UseSpans::ClosureUse { generator_kind, .. } => {
+                if generator_kind.is_some() {
+                    " in generator".to_string()
+                } else {
+                    " in closure".to_string()
+                }
+            }
+            UseSpans::IteratorChainUse { .. } => " in closure".to_string(),
+            UseSpans::GuardUse { .. } => " in match guard".to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Like `describe`, but also names the captured path if the closure only
+    /// captures part of a variable, e.g. " of `self.config.name` in closure".
+    crate fn describe_captured(&self) -> String {
+        match *self {
+            UseSpans::ClosureUse { captured_path: Some(path), .. }
+            | UseSpans::IteratorChainUse { captured_path: Some(path), .. } => {
+                format!(" of `{}`{}", path, self.describe())
+            }
+            _ => self.describe(),
+        }
+    }
+
+    crate fn or_else<F>(self, if_other: F) -> Self
+    where
+        F: FnOnce() -> Self,
+    {
+        match self {
+            closure @ (UseSpans::ClosureUse { .. } | UseSpans::IteratorChainUse { .. }) => closure,
+            guard @ UseSpans::GuardUse { .. } => guard,
+            UseSpans::PatUse(_) | UseSpans::OtherUse(_) => if_other(),
+            fn_self @ UseSpans::FnSelfUse { .. } => fn_self,
+        }
+    }
+}
+
+/// Finds the spans associated to a move or copy of move_place at location.
+crate fn move_spans<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    moved_place: PlaceRef<'tcx>, // Could also be an upvar.
+    location: Location,
+) -> UseSpans<'tcx> {
+    use UseSpans::*;
+
+    let stmt = match body[location.block].statements.get(location.statement_index) {
+        Some(stmt) => stmt,
+        None => return OtherUse(body.source_info(location).span),
+    };
+
+    debug!("move_spans: moved_place={:?} location={:?} stmt={:?}", moved_place, location, stmt);
+    if let StatementKind::Assign(box (_, Rvalue::Aggregate(ref kind, ref places))) = stmt.kind {
+        match kind {
+            box AggregateKind::Closure(def_id, _) | box AggregateKind::Generator(def_id, _, _) => {
+                debug!("move_spans: def_id={:?} places={:?}", def_id, places);
+                if let Some((
+                    args_span,
+                    generator_kind,
+                    capture_kind_span,
+                    path_span,
+                    captured_path,
+                )) = closure_capture_span(tcx, *def_id, moved_place, places)
+                {
+                    if let Some((adapter, adapter_span)) = iterator_adapter(tcx, *def_id) {
+                        return IteratorChainUse {
+                            adapter,
+                            adapter_span,
+                            args_span,
+                            capture_kind_span,
+                            path_span,
+                            captured_path,
+                        };
+                    }
+                    return ClosureUse {
+                        generator_kind,
+                        args_span,
+                        capture_kind_span,
+                        path_span,
+                        captured_path,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // StatementKind::FakeRead only contains a def_id if they are introduced as a result
+    // of pattern matching within a closure.
+    if let StatementKind::FakeRead(box (cause, ref place)) = stmt.kind {
+        match cause {
+            FakeReadCause::ForMatchedPlace(Some(closure_def_id))
+            | FakeReadCause::ForLet(Some(closure_def_id)) => {
+                debug!("move_spans: def_id={:?} place={:?}", closure_def_id, place);
+                let places = &[Operand::Move(*place)];
+                if let Some((
+                    args_span,
+                    generator_kind,
+                    capture_kind_span,
+                    path_span,
+                    captured_path,
+                )) = closure_capture_span(tcx, closure_def_id, moved_place, places)
+                {
+                    return ClosureUse {
+                        generator_kind,
+                        args_span,
+                        capture_kind_span,
+                        path_span,
+                        captured_path,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let normal_ret =
+        if moved_place.projection.iter().any(|p| matches!(p, ProjectionElem::Downcast(..))) {
+            PatUse(stmt.source_info.span)
+        } else {
+            OtherUse(stmt.source_info.span)
+        };
+
+    // We are trying to find MIR of the form:
+    // ```
+    // _temp = _moved_val;
+    // ...
+    // FnSelfCall(_temp, ...)
+    // ```
+    //
+    // where `_moved_val` is the place we generated the move error for,
+    // `_temp` is some other local, and `FnSelfCall` is a function
+    // that has a `self` parameter.
+
+    let target_temp = match stmt.kind {
+        StatementKind::Assign(box (temp, _)) if temp.as_local().is_some() => {
+            temp.as_local().unwrap()
+        }
+        _ => return normal_ret,
+    };
+
+    debug!("move_spans: target_temp = {:?}", target_temp);
+
+    if let Some(Terminator { kind: TerminatorKind::Call { fn_span, from_hir_call, .. }, .. }) =
+        &body[location.block].terminator
+    {
+        let (method_did, method_substs) = if let Some(info) =
+            crate::util::find_self_call(tcx, body, target_temp, location.block)
+        {
+            info
+        } else {
+            return normal_ret;
+        };
+
+        let parent = tcx.parent(method_did);
+        let is_fn_once = parent == tcx.lang_items().fn_once_trait();
+        let is_operator = !from_hir_call
+            && parent.map_or(false, |p| tcx.lang_items().group(LangItemGroup::Op).contains(&p));
+        let is_deref = !from_hir_call && tcx.is_diagnostic_item(sym::deref_method, method_did);
+        let fn_call_span = *fn_span;
+        let is_try = Some(method_did) == tcx.lang_items().branch_fn()
+            && matches!(fn_call_span.desugaring_kind(), Some(DesugaringKind::QuestionMark));
+
+        let self_arg = tcx.fn_arg_names(method_did)[0];
+
+        debug!(
+            "terminator = {:?} from_hir_call={:?}",
+            body[location.block].terminator, from_hir_call
+        );
+
+        // Check for a 'special' use of 'self' -
+        // an FnOnce call, an operator (e.g. `<<`), the `?` operator,
+        // or a deref coercion.
+        let kind = if is_try {
+            let is_option = match method_substs.type_at(0).kind() {
+                ty::Adt(def, _) => tcx.is_diagnostic_item(sym::option_type, def.did),
+                _ => false,
+            };
+            Some(FnSelfUseKind::TryOperator { is_option })
+        } else if is_fn_once {
+            Some(FnSelfUseKind::FnOnceCall)
+        } else if is_operator {
+            Some(FnSelfUseKind::Operator { self_arg })
+        } else if is_deref {
+            let deref_target =
+                tcx.get_diagnostic_item(sym::deref_target).and_then(|deref_target| {
+                    Instance::resolve(tcx, param_env, deref_target, method_substs).transpose()
+                });
+            if let Some(Ok(instance)) = deref_target {
+                let deref_target_ty = instance.ty(tcx, param_env);
+                Some(FnSelfUseKind::DerefCoercion {
+                    deref_target: tcx.def_span(instance.def_id()),
+                    deref_target_ty,
+                })
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let kind = kind.unwrap_or_else(|| {
+            // This isn't a 'special' use of `self`
+            debug!("move_spans: method_did={:?}, fn_call_span={:?}", method_did, fn_call_span);
+            let implicit_into_iter = matches!(
+                fn_call_span.desugaring_kind(),
+                Some(DesugaringKind::ForLoop(ForLoopLoc::IntoIter))
+            );
+            let parent_self_ty = parent
+                .filter(|did| tcx.def_kind(*did) == rustc_hir::def::DefKind::Impl)
+                .and_then(|did| match tcx.type_of(did).kind() {
+                    ty::Adt(def, ..) => Some(def.did),
+                    _ => None,
+                });
+            let is_option_or_result = parent_self_ty.map_or(false, |def_id| {
+                tcx.is_diagnostic_item(sym::option_type, def_id)
+                    || tcx.is_diagnostic_item(sym::result_type, def_id)
+            });
+            FnSelfUseKind::Normal { self_arg, implicit_into_iter, is_option_or_result }
+        });
+
+        return FnSelfUse {
+            var_span: stmt.source_info.span,
+            fn_call_span,
+            fn_span: tcx.sess.source_map().guess_head_span(tcx.def_span(method_did)),
+            kind,
+        };
+    }
+    normal_ret
+}
+
+/// Finds the span of arguments of a closure (within `maybe_closure_span`)
+/// and its usage of the local assigned at `location`.
+/// This is done by searching in statements succeeding `location`
+/// and originating from `maybe_closure_span`.
+crate fn borrow_spans<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    use_span: Span,
+    location: Location,
+) -> UseSpans<'tcx> {
+    use UseSpans::*;
+    debug!("borrow_spans: use_span={:?} location={:?}", use_span, location);
+
+    let (target, rvalue) = match body[location.block].statements.get(location.statement_index) {
+        Some(&Statement { kind: StatementKind::Assign(box (ref place, ref rvalue)), .. }) => {
+            if let Some(local) = place.as_local() {
+                (local, rvalue)
+            } else {
+                return OtherUse(use_span);
+            }
+        }
+        _ => return OtherUse(use_span),
+    };
+
+    // Fake borrows only exist to keep match guards from changing the
+    // scrutinee while the patterns of the match are tested.
+    if let Rvalue::Ref(_, BorrowKind::Shallow, _) = rvalue {
+        return PatUse(use_span);
+    }
+
+    if body.local_decls[target].is_ref_for_guard() {
+        // The binding is borrowed by the guard of its arm. The span of the
+        // borrow is the one of the binding in the alternative that matched,
+        // but the local is declared for the first alternative only.
+        return match match_guard_span(tcx, body, use_span) {
+            Some(guard_span) => GuardUse { binding_span: use_span, guard_span },
+            None => OtherUse(use_span),
+        };
+    }
+
+    if body.local_kind(target) != LocalKind::Temp {
+        // operands are always temporaries.
+        return OtherUse(use_span);
+    }
+
+    for stmt in &body[location.block].statements[location.statement_index + 1..] {
+        if let StatementKind::Assign(box (_, Rvalue::Aggregate(ref kind, ref places))) = stmt.kind {
+            let (def_id, is_generator) = match kind {
+                box AggregateKind::Closure(def_id, _) => (def_id, false),
+                box AggregateKind::Generator(def_id, _, _) => (def_id, true),
+                _ => continue,
+            };
+
+            debug!(
+                "borrow_spans: def_id={:?} is_generator={:?} places={:?}",
+                def_id, is_generator, places
+            );
+            if let Some((args_span, generator_kind, capture_kind_span, path_span, captured_path)) =
+                closure_capture_span(tcx, *def_id, Place::from(target).as_ref(), places)
+            {
+                return ClosureUse {
+                    generator_kind,
+                    args_span,
+                    capture_kind_span,
+                    path_span,
+                    captured_path,
+                };
+            } else {
+                return OtherUse(use_span);
+            }
+        }
+
+        if use_span != stmt.source_info.span {
+            break;
+        }
+    }
+
+    OtherUse(use_span)
+}
+
+/// Finds the guard of the match arm that binds a variable at `binding_span`.
+/// Guards spanning several lines are not returned, as pointing at all of
+/// them would bury the rest of the error.
+fn match_guard_span(tcx: TyCtxt<'_>, body: &Body<'_>, binding_span: Span) -> Option<Span> {
+    let hir_id = tcx.hir().local_def_id_to_hir_id(body.source.def_id().expect_local());
+    let body_id = tcx.hir().maybe_body_owned_by(hir_id)?;
+
+    let mut finder = MatchGuardFinder { binding_span, found: None };
+    finder.visit_body(tcx.hir().body(body_id));
+    let guard_span = finder.found?;
+    if tcx.sess.source_map().is_multiline(guard_span) {
+        return None;
+    }
+    Some(guard_span)
+}
+
+/// Finds the spans of a captured place within a closure or generator.
+/// The first span is the location of the use resulting in the capture kind of the capture
+/// The second span is the location the use resulting in the captured path of the capture
+/// Also returns the captured path if only part of the variable is captured.
+crate fn closure_capture_span<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    target_place: PlaceRef<'tcx>,
+    places: &[Operand<'tcx>],
+) -> Option<(Span, Option<GeneratorKind>, Span, Span, Option<Symbol>)> {
+    debug!(
+        "closure_capture_span: def_id={:?} target_place={:?} places={:?}",
+        def_id, target_place, places
+    );
+    let local_did = def_id.as_local()?;
+    let hir_id = tcx.hir().local_def_id_to_hir_id(local_did);
+    let expr = &tcx.hir().expect_expr(hir_id).kind;
+    debug!("closure_capture_span: hir_id={:?} expr={:?}", hir_id, expr);
+    if let hir::ExprKind::Closure(.., body_id, args_span, _) = expr {
+        for (captured_place, place) in
+            tcx.typeck(def_id.expect_local()).closure_min_captures_flattened(def_id).zip(places)
+        {
+            match place {
+                Operand::Copy(place) | Operand::Move(place) if target_place == place.as_ref() => {
+                    debug!("closure_capture_span: found captured local {:?}", place);
+                    let body = tcx.hir().body(*body_id);
+                    let generator_kind = body.generator_kind();
+
+                    // Without a path, the capture is described by the name of its root variable.
+                    let captured_path = if captured_place.place.projections.is_empty() {
+                        None
+                    } else {
+                        describe_captured_place(tcx, &captured_place.place)
+                            .map(|path| Symbol::intern(&path))
+                    };
+
+                    return Some((
+                        *args_span,
+                        generator_kind,
+                        captured_place.get_capture_kind_span(tcx),
+                        captured_place.get_path_span(tcx),
+                        captured_path,
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// If the closure `def_id` is passed directly to a method of `Iterator`,
+/// e.g. `iter.map(|x| ...)`, returns the name of that method and the span
+/// of the name in the call.
+fn iterator_adapter(tcx: TyCtxt<'_>, def_id: DefId) -> Option<(Symbol, Span)> {
+    let local_did = def_id.as_local()?;
+    let hir_id = tcx.hir().local_def_id_to_hir_id(local_did);
+    let parent = tcx.hir().get_parent_node(hir_id);
+    let call = match tcx.hir().find(parent) {
+        Some(hir::Node::Expr(call)) => call,
+        _ => return None,
+    };
+    match call.kind {
+        hir::ExprKind::MethodCall(segment, _, args, _)
+            if args[1..].iter().any(|arg| arg.hir_id == hir_id) =>
+        {
+            let method_did = tcx.typeck(local_did).type_dependent_def_id(call.hir_id)?;
+            let trait_did = tcx.trait_of_item(method_did)?;
+            if tcx.is_diagnostic_item(sym::Iterator, trait_did) {
+                Some((segment.ident.name, segment.ident.span))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Describes a captured place the way it is written in the closure body,
+/// e.g. `self.config.name`. Dereferences which are only needed to reach a
+/// field are implicit in the source, so they are left out. Returns `None` for places
+/// which cannot be written that way.
+fn describe_captured_place<'tcx>(tcx: TyCtxt<'tcx>, place: &HirPlace<'tcx>) -> Option<String> {
+    let mut path = match place.base {
+        HirPlaceBase::Upvar(upvar_id) => tcx.hir().name(upvar_id.var_path.hir_id).to_string(),
+        _ => return None,
+    };
+    let mut derefs = 0;
+    for (i, proj) in place.projections.iter().enumerate() {
+        match proj.kind {
+            HirProjectionKind::Deref => derefs += 1,
+            HirProjectionKind::Field(idx, variant) => {
+                derefs = 0;
+                match place.ty_before_projection(i).kind() {
+                    ty::Adt(def, ..) => {
+                        let field = &def.variants[variant].fields[idx as usize];
+                        path = format!("{}.{}", path, field.ident);
+                    }
+                    ty::Tuple(_) => path = format!("{}.{}", path, idx),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(format!("{}{}", "*".repeat(derefs), path))
+}
+
+/// Finds the guard of the innermost match arm whose pattern contains `binding_span`.
+struct MatchGuardFinder {
+    binding_span: Span,
+    found: Option<Span>,
+}
+
+impl<'v> Visitor<'v> for MatchGuardFinder {
+    type Map = intravisit::ErasedMap<'v>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_arm(&mut self, arm: &'v hir::Arm<'v>) {
+        if arm.pat.span.contains(self.binding_span) {
+            self.found = match arm.guard {
+                Some(hir::Guard::If(expr)) => Some(expr.span),
+                Some(hir::Guard::IfLet(pat, expr)) => Some(pat.span.to(expr.span)),
+                None => None,
+            };
+        }
+        intravisit::walk_arm(self, arm);
+    }
+}
@@ -623,6 +623,29 @@ fn check_doc_inline(
         }
     }
 
+    /// Checks that `#[doc(since = "...")]` is given a version number. Returns `true` if valid.
+    fn check_doc_since(&self, meta: &NestedMetaItem) -> bool {
+        let since = meta.value_str().map(|s| s.to_string()).unwrap_or_else(String::new);
+        if since.is_empty() {
+            self.doc_attr_str_error(meta, "since");
+            return false;
+        }
+        let is_version_part =
+            |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !since.split('.').all(is_version_part) {
+            self.tcx
+                .sess
+                .struct_span_err(
+                    meta.name_value_literal_span().unwrap_or_else(|| meta.span()),
+                    &format!("`{}` is not a valid version", since),
+                )
+                .note("expected a version number like `1.2.0`")
+                .emit();
+            return false;
+        }
+        true
+    }
+
     /// Checks that an attribute is *not* used at the crate level. Returns `true` if valid.
     fn check_attr_not_crate_level(
         &self,
@@ -722,6 +745,13 @@ fn check_doc_attrs(
                             is_valid = false
                         }
 
+                        sym::since
+                            if !self.check_attr_not_crate_level(&meta, hir_id, "since")
+                                || !self.check_doc_since(&meta) =>
+                        {
+                            is_valid = false
+                        }
+
                         sym::html_favicon_url
                         | sym::html_logo_url
                         | sym::html_playground_url
@@ -767,6 +797,7 @@ fn check_doc_attrs(
                         | sym::passes
                         | sym::plugins
                         | sym::primitive
+                        | sym::since
                         | sym::test => {}
 
                         _ => {
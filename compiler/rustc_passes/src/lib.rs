@@ -32,6 +32,7 @@
 mod lib_features;
 mod liveness;
 pub mod loops;
+pub mod move_paths_test;
 mod naked_functions;
 mod reachable;
 mod region;
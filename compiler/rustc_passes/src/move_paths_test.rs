@@ -0,0 +1,58 @@
+use rustc_hir as hir;
+use rustc_hir::itemlikevisit::ItemLikeVisitor;
+use rustc_hir::ItemKind;
+use rustc_middle::mir::InitSite;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::sym;
+
+pub fn test_move_paths(tcx: TyCtxt<'_>) {
+    if tcx.features().rustc_attrs {
+        // if the `rustc_attrs` feature is not enabled, don't bother testing move paths
+        tcx.hir().krate().visit_all_item_likes(&mut MovePathsTest { tcx });
+    }
+}
+
+struct MovePathsTest<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl ItemLikeVisitor<'tcx> for MovePathsTest<'tcx> {
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        if let ItemKind::Fn(..) = item.kind {
+            if self.tcx.has_attr(item.def_id.to_def_id(), sym::rustc_dump_move_paths) {
+                self.dump_move_paths_of(item);
+            }
+        }
+    }
+
+    fn visit_trait_item(&mut self, _: &'tcx hir::TraitItem<'tcx>) {}
+    fn visit_impl_item(&mut self, _: &'tcx hir::ImplItem<'tcx>) {}
+    fn visit_foreign_item(&mut self, _: &'tcx hir::ForeignItem<'tcx>) {}
+}
+
+impl MovePathsTest<'tcx> {
+    fn dump_move_paths_of(&self, item: &hir::Item<'tcx>) {
+        let tcx = self.tcx;
+        let span = tcx.sess.source_map().guess_head_span(item.span);
+        let mut err = tcx.sess.struct_span_err(span, "move paths");
+        for (mpi, path) in tcx.mir_move_paths(item.def_id).paths.iter_enumerated() {
+            let mut note = format!("{:?}: `{:?}`", mpi, path.place);
+            if let Some(parent) = path.parent {
+                note.push_str(&format!(", child of {:?}", parent));
+            }
+            for init in &path.inits {
+                match init {
+                    InitSite::Argument(_) => note.push_str(", initialized as an argument"),
+                    InitSite::Statement(location) => {
+                        note.push_str(&format!(", initialized at {:?}", location))
+                    }
+                }
+            }
+            for location in &path.moves {
+                note.push_str(&format!(", moved out at {:?}", location));
+            }
+            err.note(&note);
+        }
+        err.emit();
+    }
+}
@@ -45,9 +45,34 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct SavedLocalInfo {
+    pub name: Option<String>,
+    pub ty: String,
+    pub size: u64,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct SuspendPointInfo {
+    pub location: String,
+    pub size: u64,
+    /// The locals held across this suspension point.
+    pub locals: Vec<SavedLocalInfo>,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct GeneratorSizeInfo {
+    pub description: String,
+    pub kind: String,
+    pub align: u64,
+    pub overall_size: u64,
+    pub suspend_points: Vec<SuspendPointInfo>,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    generator_sizes: Lock<FxHashSet<GeneratorSizeInfo>>,
 }
 
 impl CodeStats {
@@ -77,6 +102,48 @@ pub fn record_type_size<S: ToString>(
         self.type_sizes.borrow_mut().insert(info);
     }
 
+    pub fn record_generator_size(&self, mut info: GeneratorSizeInfo) {
+        // Show the largest locals of each suspension point first.
+        for suspend_point in &mut info.suspend_points {
+            suspend_point.locals.sort_by(|local1, local2| local2.size.cmp(&local1.size));
+        }
+        self.generator_sizes.borrow_mut().insert(info);
+    }
+
+    pub fn print_generator_sizes(&self) {
+        let generator_sizes = self.generator_sizes.borrow();
+        let mut sorted: Vec<_> = generator_sizes.iter().collect();
+
+        // Same order as the type sizes: large-to-small, then by description.
+        sorted.sort_by(|info1, info2| match info2.overall_size.cmp(&info1.overall_size) {
+            Ordering::Equal => info1.description.cmp(&info2.description),
+            other => other,
+        });
+
+        for info in &sorted {
+            println!(
+                "print-generator-size {} `{}`: {} bytes, alignment: {} bytes",
+                info.kind, info.description, info.overall_size, info.align
+            );
+            for suspend_point in &info.suspend_points {
+                println!(
+                    "print-generator-size     suspend point at {}: {} bytes",
+                    suspend_point.location, suspend_point.size
+                );
+                for local in &suspend_point.locals {
+                    let name = match &local.name {
+                        Some(name) => format!("local `{}`", name),
+                        None => "temporary".to_owned(),
+                    };
+                    println!(
+                        "print-generator-size         {} of type `{}`: {} bytes",
+                        name, local.ty, local.size
+                    );
+                }
+            }
+        }
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
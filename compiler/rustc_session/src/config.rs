@@ -125,6 +125,23 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The different settings that the `-Z mir-pass-order` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MirPassOrder {
+    /// `-Z mir-pass-order=dump`: write the order in which the MIR passes of
+    /// each phase run, after resolving their ordering constraints, to a `.txt`
+    /// file in `-Z dump-mir-dir`.
+    Dump,
+}
+
+/// The different settings that the `-Z dump-move-paths` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DumpMovePaths {
+    /// `-Z dump-move-paths=graphviz`: write the move paths and move-outs of
+    /// each borrow-checked MIR body to a `.dot` file in `-Z dump-mir-dir`.
+    Graphviz,
+}
+
 /// The different settings that the `-Z instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-Z instrument-coverage`
@@ -214,6 +231,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    BorrowckFacts,
 }
 
 impl_stable_hash_via_hash!(OutputType);
@@ -221,7 +239,10 @@ pub enum OutputType {
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::BorrowckFacts => true,
             OutputType::Bitcode
             | OutputType::Assembly
             | OutputType::LlvmAssembly
@@ -240,6 +261,7 @@ fn shorthand(&self) -> &'static str {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::BorrowckFacts => "borrowck-facts",
         }
     }
 
@@ -253,13 +275,14 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "borrowck-facts" => OutputType::BorrowckFacts,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -268,6 +291,7 @@ fn shorthands_display() -> String {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::BorrowckFacts.shorthand(),
         )
     }
 
@@ -280,6 +304,7 @@ pub fn extension(&self) -> &'static str {
             OutputType::Object => "o",
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
+            OutputType::BorrowckFacts => "facts",
             OutputType::Exe => "",
         }
     }
@@ -365,7 +390,7 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata | OutputType::DepInfo | OutputType::BorrowckFacts => false,
         })
     }
 
@@ -378,7 +403,8 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
+            | OutputType::DepInfo
+            | OutputType::BorrowckFacts => false,
             OutputType::Exe => true,
         })
     }
@@ -1052,7 +1078,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "emit",
             "Comma separated list of types of output for \
              the compiler to emit",
-            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir]",
+            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir|borrowck-facts]",
         ),
         opt::multi_s(
             "",
@@ -2423,7 +2449,7 @@ pub fn needs_analysis(&self) -> bool {
         SymbolManglingVersion, TrimmedDefPaths,
     };
     use crate::lint;
-    use crate::options::WasiExecModel;
+    use crate::options::{DeriveCmpStrategy, WasiExecModel};
     use crate::utils::{NativeLib, NativeLibKind};
     use rustc_feature::UnstableFeatures;
     use rustc_span::edition::Edition;
@@ -2470,6 +2496,7 @@ fn hash(&self, hasher: &mut DefaultHasher, error_format: ErrorOutputType) {
         PathBuf,
         lint::Level,
         WasiExecModel,
+        DeriveCmpStrategy,
         u32,
         RelocModel,
         CodeModel,
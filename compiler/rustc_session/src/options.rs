@@ -349,6 +349,8 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_mir_pass_order: &str = "`dump`";
+    pub const parse_dump_move_paths: &str = "`graphviz`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -368,6 +370,7 @@ mod desc {
     pub const parse_tls_model: &str = "one of supported TLS models (`rustc --print tls-models`)";
     pub const parse_target_feature: &str = parse_string;
     pub const parse_wasi_exec_model: &str = "either `command` or `reactor`";
+    pub const parse_derive_cmp_strategy: &str = "either `match` or `chain`";
     pub const parse_split_debuginfo: &str =
         "one of supported split-debuginfo modes (`off`, `packed`, or `unpacked`)";
 }
@@ -686,6 +689,28 @@ mod parse {
         true
     }
 
+    crate fn parse_mir_pass_order(
+        slot: &mut Option<MirPassOrder>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = Some(match v {
+            Some("dump") => MirPassOrder::Dump,
+            _ => return false,
+        });
+        true
+    }
+
+    crate fn parse_dump_move_paths(
+        slot: &mut Option<DumpMovePaths>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = Some(match v {
+            Some("graphviz") => DumpMovePaths::Graphviz,
+            _ => return false,
+        });
+        true
+    }
+
     crate fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -857,6 +882,18 @@ mod parse {
         true
     }
 
+    crate fn parse_derive_cmp_strategy(
+        slot: &mut DeriveCmpStrategy,
+        v: Option<&str>,
+    ) -> bool {
+        match v {
+            Some("match") => *slot = DeriveCmpStrategy::Match,
+            Some("chain") => *slot = DeriveCmpStrategy::Chain,
+            _ => return false,
+        }
+        true
+    }
+
     crate fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) -> bool {
         match v.and_then(|s| SplitDebuginfo::from_str(s).ok()) {
             Some(e) => *slot = Some(e),
@@ -1020,6 +1057,10 @@ mod parse {
     dep_tasks: bool = (false, parse_bool, [UNTRACKED],
         "print tasks that execute and the color their dep node gets (requires debug build) \
         (default: no)"),
+    derive_cmp_strategy: DeriveCmpStrategy = (DeriveCmpStrategy::Match,
+        parse_derive_cmp_strategy, [TRACKED],
+        "how `#[derive(PartialOrd, Ord)]` combines the comparisons of the fields: \
+        `match` (nested matches, default) or `chain` (`and_then`/`then_with` chains)"),
     dont_buffer_diagnostics: bool = (false, parse_bool, [UNTRACKED],
         "emit diagnostics rather than buffering (breaks NLL error downgrading, sorting) \
         (default: no)"),
@@ -1052,6 +1093,9 @@ mod parse {
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    dump_move_paths: Option<DumpMovePaths> = (None, parse_dump_move_paths, [UNTRACKED],
+        "dump the move paths and move-outs of each borrow-checked MIR body into \
+        `-Z dump-mir-dir` (`graphviz`)"),
     emit_future_incompat_report: bool = (false, parse_bool, [UNTRACKED],
         "emits a future-incompatibility report for lints (RFC 2834)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
@@ -1127,6 +1171,9 @@ mod parse {
         (default: no)"),
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mir_pass_order: Option<MirPassOrder> = (None, parse_mir_pass_order, [UNTRACKED],
+        "write the order in which MIR passes run, after resolving their ordering constraints, \
+        to `-Z dump-mir-dir` (`dump`)"),
     mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "emit noalias metadata for mutable references (default: yes for LLVM >= 12, otherwise no)"),
     new_llvm_pass_manager: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1179,12 +1226,18 @@ mod parse {
         See #77382 and #74551."),
     print_fuel: Option<String> = (None, parse_opt_string, [TRACKED],
         "make rustc print the total optimization fuel used by a crate"),
+    print_generator_sizes: bool = (false, parse_bool, [UNTRACKED],
+        "print the size of each generator and the locals it holds across each suspension \
+        point (default: no)"),
     print_link_args: bool = (false, parse_bool, [UNTRACKED],
         "print the arguments passed to the linker (default: no)"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "print the LLVM optimization passes being run (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_theme_css_ast: bool = (false, parse_bool, [UNTRACKED],
+        "make rustdoc's `--check-theme` print the parsed rules of the baselines and of each \
+        checked theme (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
@@ -1275,6 +1328,9 @@ mod parse {
         "for every macro invocation, print its name and arguments (default: no)"),
     trap_unreachable: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "generate trap instructions for unreachable intrinsics (default: use target setting, usually yes)"),
+    treat_borrowck_err_as_warn: Option<Vec<String>> = (None, parse_opt_comma_list, [TRACKED],
+        "emit the borrowck errors with these codes (comma separated) as warnings, to help \
+        migrating code to the MIR borrow checker"),
     treat_err_as_bug: Option<NonZeroUsize> = (None, parse_treat_err_as_bug, [TRACKED],
         "treat error number `val` that occurs as bug"),
     trim_diagnostic_paths: bool = (true, parse_bool, [UNTRACKED],
@@ -1321,3 +1377,13 @@ pub enum WasiExecModel {
     Command,
     Reactor,
 }
+
+/// The code generated by `#[derive(PartialOrd, Ord)]` to compare the fields one after another.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum DeriveCmpStrategy {
+    /// A `match` on the comparison of each field, nested in the `Equal` arm of the previous one.
+    Match,
+    /// A chain of `Option::and_then` (for `PartialOrd`) or `Ordering::then_with` (for `Ord`)
+    /// calls, with each closure comparing the next field.
+    Chain,
+}
@@ -1,6 +1,7 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
 pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{GeneratorSizeInfo, SavedLocalInfo, SuspendPointInfo};
 use crate::config::{self, CrateType, OutputType, PrintRequest, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint::{self, LintId};
@@ -503,6 +504,7 @@ pub fn delay_span_bug<S: Into<MultiSpan>>(&self, sp: S, msg: &str) {
     /// it's likely a bug.
     pub fn delay_good_path_bug(&self, msg: &str) {
         if self.opts.debugging_opts.print_type_sizes
+            || self.opts.debugging_opts.print_generator_sizes
             || self.opts.debugging_opts.query_dep_graph
             || self.opts.debugging_opts.dump_mir.is_some()
             || self.opts.debugging_opts.unpretty.is_some()
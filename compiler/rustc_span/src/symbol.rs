@@ -173,6 +173,7 @@
         Left,
         LinkedList,
         LintPass,
+        LocalKey,
         None,
         Ok,
         Option,
@@ -208,6 +209,7 @@
         RustcEncodable,
         Send,
         Some,
+        String,
         StructuralEq,
         StructuralPartialEq,
         Sync,
@@ -334,6 +336,7 @@
         breakpoint,
         bridge,
         bswap,
+        by_discriminant,
         c_str,
         c_unwind,
         c_variadic,
@@ -359,6 +362,11 @@
         cfg_target_vendor,
         cfg_version,
         char,
+        checked_add,
+        checked_mul,
+        checked_shl,
+        checked_shr,
+        checked_sub,
         client,
         clippy,
         clone,
@@ -472,6 +480,8 @@
         deref_mut,
         deref_target,
         derive,
+        derive_relaxed_bounds,
+        derive_skip_fields,
         destructuring_assignment,
         diagnostic,
         direct,
@@ -487,6 +497,7 @@
         doc_keyword,
         doc_masked,
         doc_notable_trait,
+        doc_since,
         doc_spotlight,
         doctest,
         document_private_items,
@@ -606,6 +617,7 @@
         generic_associated_types,
         generic_param_attrs,
         get_context,
+        get_unchecked_mut,
         global_allocator,
         global_asm,
         globs,
@@ -662,6 +674,7 @@
         intra_doc_pointers,
         intrinsics,
         irrefutable_let_patterns,
+        is_eq,
         isa_attribute,
         isize,
         issue,
@@ -724,6 +737,7 @@
         managed_boxes,
         manually_drop,
         map,
+        map_unchecked_mut,
         marker,
         marker_trait_attr,
         masked,
@@ -738,6 +752,7 @@
         mem_uninitialized,
         mem_zeroed,
         member_constraints,
+        memcpy,
         memory,
         message,
         meta,
@@ -791,7 +806,9 @@
         next,
         nll,
         no,
+        no_branches,
         no_builtins,
+        no_calls,
         no_core,
         no_coverage,
         no_crate_inject,
@@ -840,6 +857,7 @@
         options,
         or,
         or_patterns,
+        ord,
         other,
         out,
         overlapping_marker_traits,
@@ -863,6 +881,7 @@
         parent_trait,
         partial_cmp,
         partial_ord,
+        partial_ord_by_discriminant,
         passes,
         pat,
         pat_param,
@@ -1004,9 +1023,11 @@
         rustc_builtin_macro,
         rustc_capture_analysis,
         rustc_clean,
+        rustc_clone_shim_expect,
         rustc_const_stable,
         rustc_const_unstable,
         rustc_conversion_suggestion,
+        rustc_copy_clone_shim,
         rustc_def_path,
         rustc_deprecated,
         rustc_diagnostic_item,
@@ -1014,12 +1035,14 @@
         rustc_dirty,
         rustc_dummy,
         rustc_dump_env_program_clauses,
+        rustc_dump_move_paths,
         rustc_dump_program_clauses,
         rustc_dump_user_substs,
         rustc_error,
         rustc_evaluate_where_clauses,
         rustc_expected_cgu_reuse,
         rustc_if_this_changed,
+        rustc_ignore_borrowck_error,
         rustc_inherit_overflow_checks,
         rustc_insignificant_dtor,
         rustc_layout,
@@ -1144,6 +1167,7 @@
         size_of_val,
         sized,
         skip,
+        skip_pass,
         slice,
         slice_alloc,
         slice_patterns,
@@ -1283,6 +1307,7 @@
         unsafe_block_in_unsafe_fn,
         unsafe_cell,
         unsafe_no_drop_flag,
+        unsafe_pinned,
         unsize,
         unsized_fn_params,
         unsized_locals,
@@ -1330,6 +1355,7 @@
         width,
         windows,
         windows_subsystem,
+        with,
         wrapping_add,
         wrapping_mul,
         wrapping_sub,
@@ -1863,6 +1863,45 @@ fn note_obligation_cause_for_async_await(
             }
         }
 
+        if is_send {
+            // If the future isn't `Send` because of a type parameter of the function creating
+            // it, that parameter can be required to be `Send`. When the obligation is on the
+            // parameter itself, this was already suggested along with the error.
+            if let ty::Param(param_ty) = target_ty.kind() {
+                let generics = hir.find(obligation.cause.body_id).and_then(|node| node.generics());
+                if let (Some(generics), false) = (generics, trait_ref.self_ty() == target_ty) {
+                    let constraint =
+                        with_no_trimmed_paths(|| trait_ref.print_only_trait_path().to_string());
+                    suggest_constraining_type_param(
+                        self.tcx,
+                        generics,
+                        err,
+                        &param_ty.name.as_str(),
+                        &constraint,
+                        Some(trait_ref.def_id),
+                    );
+                }
+            }
+
+            // Executors usually pair their `spawn` function with a `spawn_local` one, which
+            // runs the future on the current thread and so doesn't need it to be `Send`.
+            if let Some(
+                ObligationCauseCode::ItemObligation(item_def_id)
+                | ObligationCauseCode::BindingObligation(item_def_id, _),
+            ) = next_code
+            {
+                let item_name = self.tcx.item_name(*item_def_id);
+                let item_name = item_name.as_str();
+                if item_name.starts_with("spawn") && !item_name.ends_with("local") {
+                    err.help(&format!(
+                        "if the future doesn't need to run on another thread, consider a local \
+                         executor API like `spawn_local` instead of `{}`",
+                        item_name
+                    ));
+                }
+            }
+        }
+
         // Add a note for the item obligation that remains - normally a note pointing to the
         // bound that introduced the obligation (e.g. `T: Send`).
         debug!("note_obligation_cause_for_async_await: next_code={:?}", next_code);
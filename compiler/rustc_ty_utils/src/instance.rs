@@ -215,6 +215,27 @@ fn resolve_associated_item<'tcx>(
                 }
             }
 
+            // Derived `Clone` impls of generic types that also derive `Copy` are marked with
+            // `#[rustc_copy_clone_shim]`. Once the type is fully known to be `Copy`, the
+            // field-by-field clone is equivalent to a copy, so use the clone shim instead.
+            if param_env.reveal() == Reveal::All
+                && trait_item.kind == ty::AssocKind::Fn
+                && trait_item.ident.name == sym::clone
+                && Some(trait_id) == tcx.lang_items().clone_trait()
+                && tcx.has_attr(impl_data.impl_def_id, sym::rustc_copy_clone_shim)
+            {
+                let self_ty = trait_ref.self_ty();
+                if !self_ty.needs_subst()
+                    && self_ty.is_copy_modulo_regions(tcx.at(DUMMY_SP), param_env)
+                {
+                    debug!(" => copy clone shim for derived impl {:?}", impl_data.impl_def_id);
+                    return Ok(Some(Instance {
+                        def: ty::InstanceDef::CloneShim(def_id, self_ty),
+                        substs: rcvr_substs,
+                    }));
+                }
+            }
+
             Some(ty::Instance::new(leaf_def.item.def_id, substs))
         }
         traits::ImplSource::Generator(generator_data) => Some(Instance {
@@ -67,6 +67,11 @@ pub fn intrinsic_operation_unsafety(intrinsic: Symbol) -> hir::Unsafety {
         | sym::wrapping_add
         | sym::wrapping_sub
         | sym::wrapping_mul
+        | sym::checked_add
+        | sym::checked_sub
+        | sym::checked_mul
+        | sym::checked_shl
+        | sym::checked_shr
         | sym::saturating_add
         | sym::saturating_sub
         | sym::rotate_left
@@ -299,6 +304,20 @@ pub fn check_intrinsic_type(tcx: TyCtxt<'_>, it: &hir::ForeignItem<'_>) {
             sym::wrapping_add | sym::wrapping_sub | sym::wrapping_mul => {
                 (1, vec![param(0), param(0)], param(0))
             }
+            sym::checked_add
+            | sym::checked_sub
+            | sym::checked_mul
+            | sym::checked_shl
+            | sym::checked_shr => match tcx.mk_diagnostic_item(param(0), sym::option_type) {
+                Some(option_ty) => {
+                    let rhs_ty = match intrinsic_name {
+                        sym::checked_shl | sym::checked_shr => tcx.types.u32,
+                        _ => param(0),
+                    };
+                    (1, vec![param(0), rhs_ty], option_ty)
+                }
+                None => bug!("`Option` diagnostic item needed for checked arithmetic intrinsics"),
+            },
             sym::saturating_add | sym::saturating_sub => (1, vec![param(0), param(0)], param(0)),
             sym::fadd_fast | sym::fsub_fast | sym::fmul_fast | sym::fdiv_fast | sym::frem_fast => {
                 (1, vec![param(0), param(0)], param(0))
@@ -432,6 +432,7 @@ fn drop(&mut self) {
         }
     }
 
+    #[cfg_attr(not(bootstrap), allow(inconsistent_ord_impls))]
     impl<T: PartialOrd> PartialOrd for PanicOrd<T> {
         fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
             if self.1 || other.1 {
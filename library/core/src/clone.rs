@@ -135,7 +135,7 @@ fn clone_from(&mut self, source: &Self) {
 /// Derive macro generating an impl of the trait `Clone`.
 #[rustc_builtin_macro]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
-#[allow_internal_unstable(core_intrinsics, derive_clone_copy)]
+#[allow_internal_unstable(core_intrinsics, derive_clone_copy, rustc_attrs)]
 pub macro Clone($item:item) {
     /* compiler built-in */
 }
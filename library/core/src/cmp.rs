@@ -703,6 +703,7 @@ fn clone_from(&mut self, other: &Self) {
 #[doc(alias = "<=")]
 #[doc(alias = ">=")]
 #[stable(feature = "rust1", since = "1.0.0")]
+#[rustc_diagnostic_item = "Ord"]
 pub trait Ord: Eq + PartialOrd<Self> {
     /// This method returns an [`Ordering`] between `self` and `other`.
     ///
@@ -796,7 +797,8 @@ fn clamp(self, min: Self, max: Self) -> Self
 }
 
 /// Derive macro generating an impl of the trait `Ord`.
-#[rustc_builtin_macro]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(Ord, attributes(ord)))]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics)]
 pub macro Ord($item:item) {
@@ -1041,7 +1043,8 @@ fn ge(&self, other: &Rhs) -> bool {
 }
 
 /// Derive macro generating an impl of the trait `PartialOrd`.
-#[rustc_builtin_macro]
+#[cfg_attr(bootstrap, rustc_builtin_macro)]
+#[cfg_attr(not(bootstrap), rustc_builtin_macro(PartialOrd, attributes(partial_ord)))]
 #[stable(feature = "builtin_macro_prelude", since = "1.38.0")]
 #[allow_internal_unstable(core_intrinsics)]
 pub macro PartialOrd($item:item) {
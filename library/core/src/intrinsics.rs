@@ -1590,6 +1590,7 @@ pub unsafe fn drop_in_place<T: ?Sized>(to_drop: *mut T) {
     /// `x % y != 0` or `y == 0` or `x == T::MIN && y == -1`
     ///
     /// This intrinsic does not have a stable counterpart.
+    #[rustc_const_unstable(feature = "const_int_unchecked_arith", issue = "none")]
     pub fn exact_div<T: Copy>(x: T, y: T) -> T;
 
     /// Performs an unchecked division, resulting in undefined behavior
@@ -1700,6 +1701,49 @@ pub unsafe fn drop_in_place<T: ?Sized>(to_drop: *mut T) {
     #[rustc_const_stable(feature = "const_int_saturating", since = "1.40.0")]
     pub fn saturating_sub<T: Copy>(a: T, b: T) -> T;
 
+    /// Computes `a + b`, returning `None` if overflow occurred.
+    ///
+    /// The stabilized versions of this intrinsic are available on the integer
+    /// primitives via the `checked_add` method. For example,
+    /// [`u32::checked_add`]
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_checked_arith", issue = "none")]
+    pub fn checked_add<T: Copy>(a: T, b: T) -> Option<T>;
+    /// Computes `a - b`, returning `None` if overflow occurred.
+    ///
+    /// The stabilized versions of this intrinsic are available on the integer
+    /// primitives via the `checked_sub` method. For example,
+    /// [`u32::checked_sub`]
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_checked_arith", issue = "none")]
+    pub fn checked_sub<T: Copy>(a: T, b: T) -> Option<T>;
+    /// Computes `a * b`, returning `None` if overflow occurred.
+    ///
+    /// The stabilized versions of this intrinsic are available on the integer
+    /// primitives via the `checked_mul` method. For example,
+    /// [`u32::checked_mul`]
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_checked_arith", issue = "none")]
+    pub fn checked_mul<T: Copy>(a: T, b: T) -> Option<T>;
+    /// Computes `x << y`, returning `None` if `y >= N`, where N is the
+    /// width of T in bits.
+    ///
+    /// The stabilized versions of this intrinsic are available on the integer
+    /// primitives via the `checked_shl` method. For example,
+    /// [`u32::checked_shl`]
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_checked_arith", issue = "none")]
+    pub fn checked_shl<T: Copy>(x: T, y: u32) -> Option<T>;
+    /// Computes `x >> y`, returning `None` if `y >= N`, where N is the
+    /// width of T in bits.
+    ///
+    /// The stabilized versions of this intrinsic are available on the integer
+    /// primitives via the `checked_shr` method. For example,
+    /// [`u32::checked_shr`]
+    #[cfg(not(bootstrap))]
+    #[rustc_const_unstable(feature = "const_checked_arith", issue = "none")]
+    pub fn checked_shr<T: Copy>(x: T, y: u32) -> Option<T>;
+
     /// Returns the value of the discriminant for the variant in 'v';
     /// if `T` has no discriminant, returns `0`.
     ///
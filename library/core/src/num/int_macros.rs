@@ -400,10 +400,18 @@ pub const fn to_le(self) -> Self {
         #[rustc_const_stable(feature = "const_checked_int_methods", since = "1.47.0")]
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
+        #[cfg_attr(not(bootstrap), rustc_allow_const_fn_unstable(const_checked_arith))]
         #[inline]
         pub const fn checked_add(self, rhs: Self) -> Option<Self> {
-            let (a, b) = self.overflowing_add(rhs);
-            if unlikely!(b) {None} else {Some(a)}
+            #[cfg(not(bootstrap))]
+            {
+                intrinsics::checked_add(self, rhs)
+            }
+            #[cfg(bootstrap)]
+            {
+                let (a, b) = self.overflowing_add(rhs);
+                if unlikely!(b) {None} else {Some(a)}
+            }
         }
 
         /// Unchecked integer addition. Computes `self + rhs`, assuming overflow
@@ -446,10 +454,18 @@ pub const fn checked_add(self, rhs: Self) -> Option<Self> {
         #[rustc_const_stable(feature = "const_checked_int_methods", since = "1.47.0")]
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
+        #[cfg_attr(not(bootstrap), rustc_allow_const_fn_unstable(const_checked_arith))]
         #[inline]
         pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
-            let (a, b) = self.overflowing_sub(rhs);
-            if unlikely!(b) {None} else {Some(a)}
+            #[cfg(not(bootstrap))]
+            {
+                intrinsics::checked_sub(self, rhs)
+            }
+            #[cfg(bootstrap)]
+            {
+                let (a, b) = self.overflowing_sub(rhs);
+                if unlikely!(b) {None} else {Some(a)}
+            }
         }
 
         /// Unchecked integer subtraction. Computes `self - rhs`, assuming overflow
@@ -492,10 +508,18 @@ pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
         #[rustc_const_stable(feature = "const_checked_int_methods", since = "1.47.0")]
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
+        #[cfg_attr(not(bootstrap), rustc_allow_const_fn_unstable(const_checked_arith))]
         #[inline]
         pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
-            let (a, b) = self.overflowing_mul(rhs);
-            if unlikely!(b) {None} else {Some(a)}
+            #[cfg(not(bootstrap))]
+            {
+                intrinsics::checked_mul(self, rhs)
+            }
+            #[cfg(bootstrap)]
+            {
+                let (a, b) = self.overflowing_mul(rhs);
+                if unlikely!(b) {None} else {Some(a)}
+            }
         }
 
         /// Unchecked integer multiplication. Computes `self * rhs`, assuming overflow
@@ -660,10 +684,18 @@ pub const fn checked_neg(self) -> Option<Self> {
         #[rustc_const_stable(feature = "const_checked_int_methods", since = "1.47.0")]
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
+        #[cfg_attr(not(bootstrap), rustc_allow_const_fn_unstable(const_checked_arith))]
         #[inline]
         pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
-            let (a, b) = self.overflowing_shl(rhs);
-            if unlikely!(b) {None} else {Some(a)}
+            #[cfg(not(bootstrap))]
+            {
+                intrinsics::checked_shl(self, rhs)
+            }
+            #[cfg(bootstrap)]
+            {
+                let (a, b) = self.overflowing_shl(rhs);
+                if unlikely!(b) {None} else {Some(a)}
+            }
         }
 
         /// Unchecked shift left. Computes `self << rhs`, assuming that
@@ -706,10 +738,18 @@ pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
         #[rustc_const_stable(feature = "const_checked_int_methods", since = "1.47.0")]
         #[must_use = "this returns the result of the operation, \
                       without modifying the original"]
+        #[cfg_attr(not(bootstrap), rustc_allow_const_fn_unstable(const_checked_arith))]
         #[inline]
         pub const fn checked_shr(self, rhs: u32) -> Option<Self> {
-            let (a, b) = self.overflowing_shr(rhs);
-            if unlikely!(b) {None} else {Some(a)}
+            #[cfg(not(bootstrap))]
+            {
+                intrinsics::checked_shr(self, rhs)
+            }
+            #[cfg(bootstrap)]
+            {
+                let (a, b) = self.overflowing_shr(rhs);
+                if unlikely!(b) {None} else {Some(a)}
+            }
         }
 
         /// Unchecked shift right. Computes `self >> rhs`, assuming that
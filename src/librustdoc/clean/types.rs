@@ -604,10 +604,15 @@ pub fn from_def_id_and_attrs_and_parts(
         })
     }
 
+    /// Returns the version this item has been stable since, either from its `#[stable]`
+    /// attribute or, for crates without stability attributes, from `#[doc(since = "...")]`.
     crate fn stable_since(&self, tcx: TyCtxt<'_>) -> Option<SymbolStr> {
-        match self.stability(tcx)?.level {
-            StabilityLevel::Stable { since, .. } => Some(since.as_str()),
-            StabilityLevel::Unstable { .. } => None,
+        match self.stability(tcx) {
+            Some(stability) => match stability.level {
+                StabilityLevel::Stable { since, .. } => Some(since.as_str()),
+                StabilityLevel::Unstable { .. } => None,
+            },
+            None => self.attrs.doc_since().map(|since| since.as_str()),
         }
     }
 
@@ -1084,6 +1089,14 @@ fn update_need_backline(doc_strings: &mut Vec<DocFragment>) {
         if self.doc_strings.is_empty() { None } else { Some(self.doc_strings.iter().collect()) }
     }
 
+    /// Returns the value of the `#[doc(since = "...")]` attribute, if any.
+    crate fn doc_since(&self) -> Option<Symbol> {
+        self.other_attrs
+            .lists(sym::doc)
+            .find(|a| a.has_name(sym::since))
+            .and_then(|a| a.value_str())
+    }
+
     crate fn get_doc_aliases(&self) -> Box<[String]> {
         let mut aliases = FxHashSet::default();
 
@@ -2,10 +2,12 @@
 use std::convert::TryFrom;
 use std::ffi::OsStr;
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::ptr;
 use std::str::FromStr;
 
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
 use rustc_session::config::{
     self, parse_crate_types_from_list, parse_externs, parse_target_triple, CrateType,
 };
@@ -15,6 +17,7 @@
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
 use rustc_span::edition::Edition;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
 use rustc_target::spec::TargetTriple;
 
 use crate::core::new_handler;
@@ -22,7 +25,6 @@
 use crate::html;
 use crate::html::markdown::IdMap;
 use crate::html::render::StylePath;
-use crate::html::static_files;
 use crate::opts;
 use crate::passes::{self, Condition, DefaultPassOption};
 use crate::theme;
@@ -226,6 +228,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     /// List of themes to extend the docs with. Original argument name is included to assist in
     /// displaying errors if it fails a theme check.
     crate themes: Vec<StylePath>,
+    /// The results of checking `themes` against the baselines, for the JSON backend.
+    crate theme_checks: Vec<theme::ThemeCheck>,
     /// If present, CSS file that contains rules to add to the default CSS.
     crate extension_css: Option<PathBuf>,
     /// A map of crate names to the URL to use instead of querying the crate's `html_root_url`.
@@ -386,26 +390,144 @@ fn println_condition(condition: Condition) {
             );
         }
 
+        // The baselines are only loaded when there are themes to compare against them.
+        let baselines = if matches.opt_present("check-theme") || matches.opt_present("theme") {
+            match theme::Baseline::load_all(&matches.opt_strs("theme-baseline"), &diag) {
+                Some(baselines) => baselines,
+                None => return Err(1),
+            }
+        } else {
+            if matches.opt_present("theme-baseline") {
+                diag.warn("`--theme-baseline` has no effect without `--theme` or `--check-theme`");
+            }
+            Vec::new()
+        };
+
         let to_check = matches.opt_strs("check-theme");
         if !to_check.is_empty() {
-            let paths = theme::load_css_paths(static_files::themes::LIGHT.as_bytes());
+            let source_map = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let diag = new_handler(error_format, Some(source_map.clone()), &debugging_opts);
             let mut errors = 0;
 
+            // Checks a single theme file, printing its result on a line prefixed with `indent`.
+            // Returns whether the theme passed.
+            let print_ast = debugging_opts.print_theme_css_ast;
+            let strict = matches.opt_present("check-theme-strict");
+            let check_theme = |theme_file: &Path, indent: &str| -> bool {
+                if print_ast {
+                    if let Some(paths) = theme::load_theme(&theme_file, &source_map, &diag) {
+                        println!("{}Rules of \"{}\":", indent, theme_file.display());
+                        let mut out = String::new();
+                        paths.print_tree(&mut out, 1);
+                        print!("{}", out);
+                    }
+                }
+                print!("{}- Checking \"{}\"...", indent, theme_file.display());
+                let mut success = true;
+                let mut differences = Vec::new();
+                for baseline in &baselines {
+                    let (ok, ret) =
+                        theme::test_theme_against(&theme_file, &baseline.paths, &source_map, &diag);
+                    if !ok {
+                        success = false;
+                        break;
+                    }
+                    differences.extend(ret.into_iter().map(|difference| (baseline, difference)));
+                }
+                // Rules unknown to the baselines are only warned about, and do not make the
+                // theme fail.
+                let mut unknown_rules = Vec::new();
+                if strict && success {
+                    if let Some(paths) = theme::load_theme(&theme_file, &source_map, &diag) {
+                        let against =
+                            baselines.iter().map(|baseline| &baseline.paths).collect::<Vec<_>>();
+                        unknown_rules = theme::get_unknown_rules(&against, &paths);
+                    }
+                }
+                if differences.is_empty() && success {
+                    println!(" OK");
+                    for rule in &unknown_rules {
+                        rule.report(&diag);
+                    }
+                    return true;
+                }
+                println!(" FAILED");
+                for (baseline, difference) in &differences {
+                    difference.report(&diag, theme_file, baseline);
+                }
+                for rule in &unknown_rules {
+                    rule.report(&diag);
+                }
+                // The baselines with rules missing from the theme.
+                let missing_rules = baselines
+                    .iter()
+                    .filter(|&baseline| {
+                        differences.iter().any(|&(b, ref d)| {
+                            ptr::eq(b, baseline)
+                                && matches!(d, theme::Difference::MissingRule { .. })
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                if !missing_rules.is_empty() && matches.opt_present("write-theme-patch") {
+                    if let Some(patch_file) =
+                        theme::write_theme_patch(&theme_file, &missing_rules, &diag)
+                    {
+                        println!(
+                            "{}  Missing rules written to \"{}\"",
+                            indent,
+                            patch_file.display()
+                        );
+                    }
+                }
+                false
+            };
+
             println!("rustdoc: [check-theme] Starting tests! (Ignoring all other arguments)");
-            for theme_file in to_check.iter() {
-                print!(" - Checking \"{}\"...", theme_file);
-                let (success, differences) = theme::test_theme_against(theme_file, &paths, &diag);
-                if !differences.is_empty() || !success {
-                    println!(" FAILED");
+            if print_ast {
+                for baseline in &baselines {
+                    println!("rustdoc: [check-theme] Rules of {}:", baseline.descr());
+                    let mut out = String::new();
+                    baseline.paths.print_tree(&mut out, 1);
+                    print!("{}", out);
+                }
+            }
+            let (mut checked, mut failed) = (0, 0);
+            for theme_arg in to_check.iter() {
+                let theme_arg = Path::new(theme_arg);
+                if !theme_arg.is_dir() {
+                    checked += 1;
+                    if !check_theme(theme_arg, " ") {
+                        failed += 1;
+                    }
+                    continue;
+                }
+                // Every `.css` file of a directory is checked, in its own section.
+                let theme_files = match theme::theme_files_in(theme_arg, &diag) {
+                    Some(theme_files) => theme_files,
+                    None => {
+                        errors += 1;
+                        continue;
+                    }
+                };
+                println!(" - Directory \"{}\":", theme_arg.display());
+                if theme_files.is_empty() {
+                    println!("   No themes found");
+                    diag.struct_err(&format!("no themes found in \"{}\"", theme_arg.display()))
+                        .emit();
                     errors += 1;
-                    if !differences.is_empty() {
-                        println!("{}", differences.join("\n"));
+                    continue;
+                }
+                for theme_file in &theme_files {
+                    checked += 1;
+                    if !check_theme(theme_file, "   ") {
+                        failed += 1;
                     }
-                } else {
-                    println!(" OK");
                 }
             }
-            if errors != 0 {
+            if failed != 0 {
+                println!("rustdoc: [check-theme] {} of {} themes failed", failed, checked);
+            }
+            if errors + failed != 0 {
                 return Err(1);
             }
             return Err(0);
@@ -484,8 +606,12 @@ fn println_condition(condition: Condition) {
         }
 
         let mut themes = Vec::new();
+        let mut theme_checks = Vec::new();
         if matches.opt_present("theme") {
-            let paths = theme::load_css_paths(static_files::themes::LIGHT.as_bytes());
+            let source_map = SourceMap::new(FilePathMapping::empty());
+            // Themes are not rendered by the JSON backend, which only records whether they
+            // could be loaded.
+            let output_json = matches.opt_str("output-format").as_deref() == Some("json");
 
             for (theme_file, theme_s) in
                 matches.opt_strs("theme").iter().map(|s| (PathBuf::from(&s), s.to_owned()))
@@ -502,22 +628,47 @@ fn println_condition(condition: Condition) {
                         .emit();
                     return Err(1);
                 }
-                let (success, ret) = theme::test_theme_against(&theme_file, &paths, &diag);
-                if !success {
-                    diag.struct_err(&format!("error loading theme file: \"{}\"", theme_s)).emit();
-                    return Err(1);
-                } else if !ret.is_empty() {
-                    diag.struct_warn(&format!(
-                        "theme file \"{}\" is missing CSS rules from the default theme",
-                        theme_s
-                    ))
-                    .warn("the theme may appear incorrect when loaded")
-                    .help(&format!(
-                        "to see what rules are missing, call `rustdoc  --check-theme \"{}\"`",
-                        theme_s
-                    ))
-                    .emit();
+                let mut check = theme::ThemeCheck {
+                    path: theme_file.clone(),
+                    parsed: true,
+                    differences: Vec::new(),
+                };
+                for baseline in &baselines {
+                    let (success, ret) =
+                        theme::test_theme_against(&theme_file, &baseline.paths, &source_map, &diag);
+                    if !success && output_json {
+                        check.parsed = false;
+                        check.differences.clear();
+                        break;
+                    } else if !success {
+                        diag.struct_err(&format!("error loading theme file: \"{}\"", theme_s))
+                            .emit();
+                        return Err(1);
+                    } else if !ret.is_empty() {
+                        let check_args = match baseline.file {
+                            Some(ref file) => format!(
+                                " -Z unstable-options --theme-baseline \"{}\"",
+                                file.display()
+                            ),
+                            None => String::new(),
+                        };
+                        diag.struct_warn(&format!(
+                            "theme file \"{}\" does not match {}",
+                            theme_s,
+                            baseline.descr()
+                        ))
+                        .warn("the theme may appear incorrect when loaded")
+                        .help(&format!(
+                            "to see the differences, call `rustdoc  --check-theme \"{}\"{}`",
+                            theme_s, check_args
+                        ))
+                        .emit();
+                    }
+                    check.differences.extend(
+                        ret.into_iter().map(|difference| (baseline.file.clone(), difference)),
+                    );
                 }
+                theme_checks.push(check);
                 themes.push(StylePath { path: theme_file, disabled: true });
             }
         }
@@ -672,6 +823,7 @@ fn println_condition(condition: Condition) {
                 playground_url,
                 sort_modules_alphabetically,
                 themes,
+                theme_checks,
                 extension_css,
                 extern_html_root_urls,
                 default_settings,
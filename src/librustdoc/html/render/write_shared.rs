@@ -17,6 +17,7 @@
 use crate::docfs::PathError;
 use crate::error::Error;
 use crate::html::{layout, static_files};
+use crate::theme;
 
 static FILES_UNVERSIONED: Lazy<FxHashMap<&str, &[u8]>> = Lazy::new(|| {
     map! {
@@ -192,7 +193,14 @@ pub(super) fn write_shared(
             _ => {
                 // Handle added third-party themes
                 let filename = format!("{}.{}", theme, extension);
-                write_crate(&filename, &|| Ok(try_err!(fs::read(&entry.path), &entry.path)))?;
+                write_crate(&filename, &|| {
+                    let content = try_err!(fs::read(&entry.path), &entry.path);
+                    // A theme extending a built-in theme only contains the rules it changes.
+                    match theme::extended_theme(&content).and_then(theme::builtin_theme) {
+                        Some(base) => Ok([base.as_bytes(), &b"\n"[..], &content[..]].concat()),
+                        None => Ok(content),
+                    }
+                })?;
             }
         };
 
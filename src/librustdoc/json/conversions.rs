@@ -18,11 +18,13 @@
 use crate::clean::{self, FakeDefId};
 use crate::formats::item_type::ItemType;
 use crate::json::JsonRenderer;
+use crate::theme;
 use std::collections::HashSet;
 
 impl JsonRenderer<'_> {
     pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
         let deprecation = item.deprecation(self.tcx);
+        let since = item.stable_since(self.tcx).map(|since| since.to_string());
         let links = self
             .cache
             .intra_doc_links
@@ -55,6 +57,7 @@ pub(super) fn convert_item(&self, item: clean::Item) -> Option<Item> {
             docs,
             attrs,
             deprecation: deprecation.map(from_deprecation),
+            since,
             inner,
             links,
         })
@@ -170,6 +173,34 @@ fn from_tcx(kind: clean::TypeBindingKind, tcx: TyCtxt<'_>) -> Self {
     }
 }
 
+crate fn from_theme_check(check: theme::ThemeCheck) -> ThemeCheck {
+    let theme::ThemeCheck { path, parsed, differences } = check;
+    let differences = differences
+        .into_iter()
+        .map(|(baseline, difference)| ThemeDifference {
+            baseline,
+            kind: match difference {
+                theme::Difference::MissingRule { name, .. } => {
+                    ThemeDifferenceKind::MissingRule { name }
+                }
+                theme::Difference::MismatchedProperty {
+                    name,
+                    expected,
+                    default_value,
+                    found,
+                    ..
+                } => ThemeDifferenceKind::MismatchedProperty {
+                    name,
+                    expected: expected.descr().to_owned(),
+                    found: found.descr().to_owned(),
+                    default_value,
+                },
+            },
+        })
+        .collect();
+    ThemeCheck { path, parsed, differences }
+}
+
 crate fn from_def_id(did: FakeDefId) -> Id {
     match did {
         FakeDefId::Real(did) => Id(format!("{}:{}", did.krate.as_u32(), u32::from(did.index))),
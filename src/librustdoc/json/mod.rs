@@ -25,7 +25,7 @@
 use crate::formats::cache::Cache;
 use crate::formats::FormatRenderer;
 use crate::html::render::cache::ExternalLocation;
-use crate::json::conversions::{from_def_id, IntoWithTcx};
+use crate::json::conversions::{from_def_id, from_theme_check, IntoWithTcx};
 
 #[derive(Clone)]
 crate struct JsonRenderer<'tcx> {
@@ -36,6 +36,8 @@
     /// The directory where the blob will be written to.
     out_path: PathBuf,
     cache: Rc<Cache>,
+    /// The results of checking the themes given with `--theme`.
+    theme_checks: Vec<types::ThemeCheck>,
 }
 
 impl JsonRenderer<'tcx> {
@@ -115,6 +117,7 @@ fn get_trait_items(&mut self) -> Vec<(types::Id, types::Item)> {
                             links: Default::default(),
                             attrs: Default::default(),
                             deprecation: Default::default(),
+                            since: Default::default(),
                         },
                     ))
                 } else {
@@ -145,6 +148,7 @@ fn init(
                 index: Rc::new(RefCell::new(FxHashMap::default())),
                 out_path: options.output,
                 cache: Rc::new(cache),
+                theme_checks: options.theme_checks.into_iter().map(from_theme_check).collect(),
             },
             krate,
         ))
@@ -234,7 +238,8 @@ fn after_krate(&mut self) -> Result<(), Error> {
                     )
                 })
                 .collect(),
-            format_version: 5,
+            theme_checks: self.theme_checks.clone(),
+            format_version: 7,
         };
         let mut p = self.out_path.clone();
         p.push(output.index.get(&output.root).unwrap().name.clone().unwrap());
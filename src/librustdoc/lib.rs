@@ -454,7 +454,36 @@ fn opts() -> Vec<RustcOptGroup> {
             )
         }),
         stable("check-theme", |o| {
-            o.optmulti("", "check-theme", "check if given theme is valid", "FILES")
+            o.optmulti(
+                "",
+                "check-theme",
+                "check if given theme is valid, or all the themes of a directory",
+                "FILES",
+            )
+        }),
+        unstable("theme-baseline", |o| {
+            o.optmulti(
+                "",
+                "theme-baseline",
+                "compare themes given with --theme or --check-theme against these themes \
+                 instead of the default theme",
+                "FILES",
+            )
+        }),
+        unstable("check-theme-strict", |o| {
+            o.optflag(
+                "",
+                "check-theme-strict",
+                "with --check-theme, also warn about the rules of a theme which are missing from \
+                 the baseline themes",
+            )
+        }),
+        unstable("write-theme-patch", |o| {
+            o.optflag(
+                "",
+                "write-theme-patch",
+                "with --check-theme, write the missing rules of each theme to THEME.patch.css",
+            )
         }),
         unstable("resource-suffix", |o| {
             o.optopt(
@@ -637,8 +666,11 @@ fn main_args(at_args: &[String]) -> MainResult {
     };
 
     // Note that we discard any distinction between different non-zero exit
-    // codes from `from_matches` here.
-    let options = match config::Options::from_matches(&matches) {
+    // codes from `from_matches` here. It needs the session globals to report
+    // errors pointing into theme files.
+    let options = match rustc_span::with_default_session_globals(|| {
+        config::Options::from_matches(&matches)
+    }) {
         Ok(opts) => opts,
         Err(code) => return if code == 0 { Ok(()) } else { Err(ErrorReported) },
     };
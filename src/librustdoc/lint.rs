@@ -126,6 +126,17 @@ macro_rules! declare_rustdoc_lint {
     "detects publicly-exported items without code samples in their documentation"
 }
 
+declare_rustdoc_lint! {
+    /// The `missing_doc_since` lint detects publicly-exported items without
+    /// a `#[doc(since = "...")]` annotation. This is a `rustdoc` only lint,
+    /// see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#missing_doc_since
+    MISSING_DOC_SINCE,
+    Allow,
+    "detects publicly-exported items without a `#[doc(since)]` annotation"
+}
+
 declare_rustdoc_lint! {
     /// The `private_doc_tests` lint detects code samples in docs of private
     /// items not documented by `rustdoc`. This is a `rustdoc` only lint, see
@@ -174,6 +185,7 @@ macro_rules! declare_rustdoc_lint {
         BROKEN_INTRA_DOC_LINKS,
         PRIVATE_INTRA_DOC_LINKS,
         MISSING_DOC_CODE_EXAMPLES,
+        MISSING_DOC_SINCE,
         PRIVATE_DOC_TESTS,
         INVALID_CODEBLOCK_ATTRIBUTES,
         INVALID_RUST_CODEBLOCKS,
@@ -0,0 +1,64 @@
+//! Detects publicly-exported items which are missing a `#[doc(since = "...")]` annotation.
+
+use super::Pass;
+use crate::clean::*;
+use crate::core::DocContext;
+use crate::fold::DocFolder;
+
+crate const CHECK_DOC_SINCE: Pass = Pass {
+    name: "check-doc-since",
+    run: check_doc_since,
+    description: "detects public items without a `#[doc(since = \"...\")]` annotation",
+};
+
+struct DocSinceLinter<'a, 'tcx> {
+    cx: &'a mut DocContext<'tcx>,
+}
+
+crate fn check_doc_since(krate: Crate, cx: &mut DocContext<'_>) -> Crate {
+    DocSinceLinter { cx }.fold_crate(krate)
+}
+
+fn should_have_doc_since(item: &Item) -> bool {
+    !item.is_crate()
+        && !matches!(
+            *item.kind,
+            StructFieldItem(_)
+                | VariantItem(_)
+                | ImplItem(_)
+                | ExternCrateItem { .. }
+                | ImportItem(_)
+                | PrimitiveItem(_)
+                | KeywordItem(_)
+                | StrippedItem(_)
+        )
+}
+
+impl<'a, 'tcx> DocFolder for DocSinceLinter<'a, 'tcx> {
+    fn fold_item(&mut self, item: Item) -> Option<Item> {
+        // Items of trait implementations are as old as the implementation itself.
+        if let ImplItem(Impl { trait_: Some(_), .. }) = *item.kind {
+            return Some(item);
+        }
+        let hir_id = match DocContext::as_local_hir_id(self.cx.tcx, item.def_id) {
+            Some(hir_id) => hir_id,
+            None => {
+                // If non-local, no need to check anything.
+                return Some(self.fold_item_recur(item));
+            }
+        };
+        if should_have_doc_since(&item)
+            && self.cx.cache.access_levels.is_public(item.def_id.expect_real())
+            && item.stable_since(self.cx.tcx).is_none()
+        {
+            let sp = item.attr_span(self.cx.tcx);
+            self.cx.tcx.struct_span_lint_hir(crate::lint::MISSING_DOC_SINCE, hir_id, sp, |lint| {
+                lint.build("missing `#[doc(since = \"...\")]` annotation on this item")
+                    .help("add `#[doc(since = \"...\")]` with the version this item was added in")
+                    .emit()
+            });
+        }
+
+        Some(self.fold_item_recur(item))
+    }
+}
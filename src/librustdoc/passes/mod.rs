@@ -48,6 +48,9 @@
 mod html_tags;
 crate use self::html_tags::CHECK_INVALID_HTML_TAGS;
 
+mod check_doc_since;
+crate use self::check_doc_since::CHECK_DOC_SINCE;
+
 /// A single pass over the cleaned documentation.
 ///
 /// Runs in the compiler context, so it has access to types and traits and the like.
@@ -91,6 +94,7 @@
     CALCULATE_DOC_COVERAGE,
     CHECK_INVALID_HTML_TAGS,
     CHECK_BARE_URLS,
+    CHECK_DOC_SINCE,
 ];
 
 /// The list of passes run by default.
@@ -106,6 +110,7 @@
     ConditionalPass::always(CHECK_INVALID_HTML_TAGS),
     ConditionalPass::always(PROPAGATE_DOC_CFG),
     ConditionalPass::always(CHECK_BARE_URLS),
+    ConditionalPass::always(CHECK_DOC_SINCE),
 ];
 
 /// The list of default passes run when `--doc-coverage` is passed to rustdoc.
@@ -1,9 +1,15 @@
 use rustc_data_structures::fx::FxHashSet;
+use std::cmp;
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use rustc_errors::Handler;
+use rustc_span::lev_distance::lev_distance;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{BytePos, Span, DUMMY_SP};
+
+use crate::html::static_files;
 
 #[cfg(test)]
 mod tests;
@@ -11,7 +17,13 @@
 #[derive(Debug, Clone, Eq)]
 crate struct CssPath {
     crate name: String,
+    /// The span of the selector of the rule in the CSS file it was loaded from.
+    /// It is not taken into account when comparing paths.
+    crate span: Span,
     crate children: FxHashSet<CssPath>,
+    /// The declarations of a rule, without the rules nested in it. They are not taken into
+    /// account when comparing paths.
+    crate declarations: String,
 }
 
 // This PartialEq implementation IS NOT COMMUTATIVE!!!
@@ -43,8 +55,53 @@ fn hash<H: Hasher>(&self, state: &mut H) {
 }
 
 impl CssPath {
-    fn new(name: String) -> CssPath {
-        CssPath { name, children: FxHashSet::default() }
+    fn new(name: String, span: Span) -> CssPath {
+        CssPath { name, span, children: FxHashSet::default(), declarations: String::new() }
+    }
+
+    /// Serializes the children of `self` back to CSS, sorted by selector so that
+    /// the output is stable.
+    crate fn children_to_css(&self, out: &mut String, indent: usize) {
+        let mut children = self.children.iter().collect::<Vec<_>>();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        for child in children {
+            child.to_css(out, indent);
+        }
+    }
+
+    fn to_css(&self, out: &mut String, indent: usize) {
+        let pad = "    ".repeat(indent);
+        // The font is only part of the name of `@font-face` rules to tell them apart.
+        let name = if at_keyword(&self.name) == "@font-face" { "@font-face" } else { &self.name };
+        out.push_str(&format!("{}{} {{\n", pad, name));
+        for declaration in self.declarations.split(';').map(str::trim) {
+            if !declaration.is_empty() {
+                out.push_str(&format!("{}    {};\n", pad, declaration));
+            }
+        }
+        self.children_to_css(out, indent + 1);
+        out.push_str(&format!("{}}}\n", pad));
+    }
+
+    /// Pretty-prints the tree of rules below `self`, one selector per line with its
+    /// declarations, for `-Z print-theme-css-ast`.
+    crate fn print_tree(&self, out: &mut String, indent: usize) {
+        let mut children = self.children.iter().collect::<Vec<_>>();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        for child in children {
+            out.push_str(&format!("{}{}", "    ".repeat(indent), child.name));
+            let declarations = child
+                .declarations
+                .split(';')
+                .map(str::trim)
+                .filter(|declaration| !declaration.is_empty())
+                .collect::<Vec<_>>();
+            if !declarations.is_empty() {
+                out.push_str(&format!(" {{ {} }}", declarations.join("; ")));
+            }
+            out.push('\n');
+            child.print_tree(out, indent + 1);
+        }
     }
 }
 
@@ -167,11 +224,16 @@ fn get_previous_positions(events: &[Events], mut pos: usize) -> Vec<usize> {
 }
 
 fn build_rule(v: &[u8], positions: &[usize]) -> String {
+    let rule = positions
+        .chunks(2)
+        .map(|x| ::std::str::from_utf8(&v[x[0]..x[1]]).unwrap_or(""))
+        .collect::<String>();
     minifier::css::minify(
-        &positions
-            .chunks(2)
-            .map(|x| ::std::str::from_utf8(&v[x[0]..x[1]]).unwrap_or(""))
-            .collect::<String>()
+        // The selector of a nested rule follows the declarations of its parent.
+        &rule
+            .rsplit(';')
+            .next()
+            .unwrap_or("")
             .trim()
             .replace("\n", " ")
             .replace("/", "")
@@ -186,42 +248,554 @@ fn build_rule(v: &[u8], positions: &[usize]) -> String {
     .unwrap_or_else(|_| String::new())
 }
 
-fn inner(v: &[u8], events: &[Events], pos: &mut usize) -> FxHashSet<CssPath> {
+/// Returns the span of the selector of a rule, given the positions returned by
+/// `get_previous_positions`, without the surrounding whitespace and braces.
+fn build_selector_span(v: &[u8], positions: &[usize], start_pos: BytePos) -> Span {
+    let is_blank = |c: u8| c.is_ascii_whitespace() || c == b'{' || c == b'}';
+    let (mut lo, mut hi) = (positions[0], positions[positions.len() - 1]);
+    if let Some(semicolon) = v[lo..hi].iter().rposition(|&c| c == b';') {
+        lo += semicolon + 1;
+    }
+    while lo < hi && is_blank(v[lo]) {
+        lo += 1;
+    }
+    while hi > lo && is_blank(v[hi - 1]) {
+        hi -= 1;
+    }
+    Span::with_root_ctxt(start_pos + BytePos(lo as u32), start_pos + BytePos(hi as u32))
+}
+
+/// Returns the declarations between `start` and `end`, without the comments
+/// they may contain nor the at-rule statements like `@layer a;` given among them.
+fn build_declarations(v: &[u8], events: &[Events], start: usize, end: usize) -> String {
+    let declarations = strip_comments(v, events, start, end);
+    if !declarations.contains('@') {
+        return declarations;
+    }
+    declarations
+        .split(';')
+        .filter(|declaration| !declaration.trim_start().starts_with('@'))
+        .collect::<Vec<_>>()
+        .join(";")
+        .trim()
+        .to_owned()
+}
+
+/// Returns the text between `start` and `end` without comments, with its whitespace
+/// collapsed.
+fn strip_comments(v: &[u8], events: &[Events], start: usize, end: usize) -> String {
+    let mut declarations = String::new();
+    let mut from = start;
+    let mut in_comment = false;
+    for event in events.iter().filter(|e| (start..end).contains(&e.get_pos())) {
+        match *event {
+            Events::StartLineComment(p) | Events::StartComment(p) if !in_comment => {
+                declarations.push_str(&String::from_utf8_lossy(&v[from..p]));
+                in_comment = true;
+            }
+            Events::EndComment(p) if in_comment => {
+                from = p;
+                in_comment = false;
+            }
+            _ => {}
+        }
+    }
+    if !in_comment {
+        declarations.push_str(&String::from_utf8_lossy(&v[from..end]));
+    }
+    declarations.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns the layers declared by the `@layer` statements found before the rule or the
+/// end of block at the end of `positions`, as returned by `get_previous_positions`.
+/// `@layer a, b.c;` declares the same layers as `@layer a {} @layer b.c {}`.
+fn build_layer_statements(v: &[u8], positions: &[usize]) -> Vec<CssPath> {
+    let text =
+        positions.chunks(2).map(|x| String::from_utf8_lossy(&v[x[0]..x[1]])).collect::<String>();
+    let mut statements = text.split(';').collect::<Vec<_>>();
+    // The text after the last statement is the selector of the following rule, if any.
+    statements.pop();
+    statements
+        .into_iter()
+        .filter_map(|statement| statement.trim().strip_prefix("@layer"))
+        .flat_map(|names| names.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| CssPath::new(format!("@layer {}", name), DUMMY_SP))
+        .collect()
+}
+
+/// Returns the declarations of the block between `start` and `end`, leaving out the
+/// rules nested in it.
+fn build_block_declarations(v: &[u8], events: &[Events], start: usize, end: usize) -> String {
+    let mut declarations = Vec::new();
+    let mut from = start;
+    let mut depth = 0;
+    // `start` is the position of the event opening the block itself.
+    for event in events.iter().filter(|e| (start + 1..=end).contains(&e.get_pos())) {
+        match *event {
+            Events::InBlock(p) => {
+                if depth == 0 {
+                    // The selector of the nested rule starts after the last declaration.
+                    let selector = v[from..p]
+                        .iter()
+                        .rposition(|&c| c == b';')
+                        .map_or(from, |semicolon| from + semicolon + 1);
+                    declarations.push(build_declarations(v, events, from, selector));
+                }
+                depth += 1;
+            }
+            Events::OutBlock(p) => {
+                depth -= 1;
+                if depth == 0 {
+                    from = p;
+                }
+            }
+            _ => {}
+        }
+    }
+    declarations.push(build_declarations(v, events, from, end));
+    declarations.retain(|declaration| !declaration.is_empty());
+    declarations.join(" ")
+}
+
+/// Splits a selector list on the commas which are not in parentheses or brackets, like
+/// the one of `:is(a, b)`.
+fn split_selector_list(selector: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&selector[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&selector[start..]);
+    parts
+}
+
+/// Returns the selector a style rule nested in another style rule is stored under in the
+/// children of its parent. As in CSS Nesting, a selector without `&` is relative to the
+/// parent, so a leading `&` followed by a combinator is left out: `& .b` is stored as `.b`
+/// and `& > .b` as `> .b`. Other references to the parent, like `&:hover`, are kept.
+fn resolve_nested_selector(selector: &str) -> String {
+    if !selector.contains('&') {
+        return selector.to_owned();
+    }
+    split_selector_list(selector)
+        .into_iter()
+        .map(|part| {
+            let part = part.trim();
+            match part.strip_prefix('&') {
+                Some(rest)
+                    if !rest.contains('&')
+                        && rest.starts_with(|c: char| {
+                            c.is_whitespace() || matches!(c, '>' | '+' | '~')
+                        }) =>
+                {
+                    rest.trim_start()
+                }
+                _ => part,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Whether the rule is an at-rule like `@media`, whose nested rules are not relative
+/// to it.
+fn is_at_rule(path: &CssPath) -> bool {
+    path.name.starts_with('@')
+}
+
+/// Returns the at-keyword of the name of an at-rule, like `@media`.
+fn at_keyword(name: &str) -> &str {
+    let rest = match name.strip_prefix('@') {
+        Some(rest) => rest,
+        None => return "",
+    };
+    let end = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '-').unwrap_or(rest.len());
+    &name[..end + 1]
+}
+
+/// Returns the name an `@font-face` rule is stored under. A theme usually gives several
+/// of them, one for each font, so the font they describe is part of the name.
+fn font_face_name(declarations: &str) -> String {
+    let descriptors = ["font-family", "font-style", "font-weight"]
+        .iter()
+        .filter_map(|descriptor| {
+            let (_, value) = declarations
+                .split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .find(|(name, _)| name.trim() == *descriptor)?;
+            Some(value.trim().trim_matches(|c| c == '"' || c == '\''))
+        })
+        .collect::<Vec<_>>();
+    if descriptors.is_empty() {
+        "@font-face".to_owned()
+    } else {
+        format!("@font-face ({})", descriptors.join(", "))
+    }
+}
+
+/// Splits the rule of a nested layer like `@layer a.b` into the rules of each layer, as
+/// if it was written `@layer a { @layer b {} }`.
+fn nest_layer(mut path: CssPath) -> CssPath {
+    let layer = path.name.strip_prefix("@layer ");
+    let (outer, name) = match layer.and_then(|layer| layer.rsplit_once('.')) {
+        Some((outer, name)) => (outer.to_owned(), name.to_owned()),
+        None => return path,
+    };
+    let mut parent = CssPath::new(format!("@layer {}", outer), path.span);
+    path.name = format!("@layer {}", name);
+    parent.children.insert(path);
+    nest_layer(parent)
+}
+
+fn inner(v: &[u8], events: &[Events], pos: &mut usize, start_pos: BytePos) -> FxHashSet<CssPath> {
     let mut paths = Vec::with_capacity(50);
 
     while *pos < events.len() {
+        // The end of the enclosing block is left to the caller.
         if let Some(Events::OutBlock(_)) = get_useful_next(events, pos) {
-            *pos += 1;
+            paths.extend(build_layer_statements(v, &get_previous_positions(events, *pos)));
             break;
         }
-        if let Some(Events::InBlock(_)) = get_useful_next(events, pos) {
-            paths.push(CssPath::new(build_rule(v, &get_previous_positions(events, *pos))));
+        let mut block_start = None;
+        if let Some(Events::InBlock(start)) = get_useful_next(events, pos) {
+            let positions = get_previous_positions(events, *pos);
+            paths.extend(build_layer_statements(v, &positions));
+            paths.push(CssPath::new(
+                build_rule(v, &positions),
+                build_selector_span(v, &positions, start_pos),
+            ));
+            block_start = Some(start);
             *pos += 1;
         }
+        let mut has_nested_blocks = false;
         while let Some(Events::InBlock(_)) = get_useful_next(events, pos) {
+            has_nested_blocks = true;
+            let entries = inner(v, events, pos, start_pos);
             if let Some(ref mut path) = paths.last_mut() {
-                for entry in inner(v, events, pos).iter() {
-                    path.children.insert(entry.clone());
+                for mut entry in entries {
+                    if !is_at_rule(path) && !is_at_rule(&entry) {
+                        entry.name = resolve_nested_selector(&entry.name);
+                    }
+                    path.children.insert(entry);
                 }
             }
         }
-        if let Some(Events::OutBlock(_)) = get_useful_next(events, pos) {
+        if let Some(Events::OutBlock(end)) = get_useful_next(events, pos) {
+            if let (Some(start), Some(path)) = (block_start, paths.last_mut()) {
+                // `end` is right after the closing brace.
+                path.declarations = build_block_declarations(v, events, start, end - 1);
+                if at_keyword(&path.name) == "@font-face" {
+                    path.name = font_face_name(&path.declarations);
+                } else if is_at_rule(path) && !has_nested_blocks {
+                    // Otherwise, the statements were read with the nested rules.
+                    let positions = get_previous_positions(events, *pos);
+                    path.children.extend(build_layer_statements(v, &positions));
+                }
+            }
             *pos += 1;
         }
     }
-    paths.iter().cloned().collect()
+    if *pos >= events.len() {
+        // Statements may follow the last block of the file.
+        let last = events.last().map_or(0, Events::get_pos);
+        paths.extend(build_layer_statements(v, &[last, v.len()]));
+    }
+    merge_layers(paths).into_iter().collect()
 }
 
-crate fn load_css_paths(v: &[u8]) -> CssPath {
+/// Merges the `@layer` rules with the same name, like a statement declaring a layer and
+/// the block giving its rules, as they all add to the same layer.
+fn merge_layers(paths: Vec<CssPath>) -> Vec<CssPath> {
+    let mut merged: Vec<CssPath> = Vec::with_capacity(paths.len());
+    for path in paths.into_iter().map(nest_layer) {
+        let is_same_layer =
+            |other: &&mut CssPath| at_keyword(&other.name) == "@layer" && other.name == path.name;
+        match merged.iter_mut().find(is_same_layer) {
+            Some(other) => {
+                // A layer can be declared by a statement before the block giving its rules.
+                if other.span.is_dummy() {
+                    other.span = path.span;
+                }
+                // Layers are nested in one another, so their children are merged too.
+                let children = other.children.drain().chain(path.children).collect();
+                other.children = merge_layers(children).into_iter().collect();
+            }
+            None => merged.push(path),
+        }
+    }
+    merged
+}
+
+/// Parses the rules of a CSS file. `start_pos` is the position of the file in the
+/// source map, and is used to compute the spans of the rules.
+///
+/// If the file extends a built-in theme, the rules of that theme which the file does
+/// not define itself are added too.
+crate fn load_css_paths(v: &[u8], start_pos: BytePos) -> CssPath {
     let events = load_css_events(v);
     let mut pos = 0;
 
-    let mut parent = CssPath::new("parent".to_owned());
-    parent.children = inner(v, &events, &mut pos);
+    let mut parent = CssPath::new("parent".to_owned(), DUMMY_SP);
+    parent.children = inner(v, &events, &mut pos, start_pos);
+    if let Some(base) = extended_theme(v).and_then(builtin_theme) {
+        inherit_rules(&mut parent, &load_css_paths(base.as_bytes(), BytePos(0)));
+    }
     parent
 }
 
-crate fn get_differences(against: &CssPath, other: &CssPath, v: &mut Vec<String>) {
+/// Returns the name of the theme a theme file extends with a leading
+/// `/* extends: <theme> */` comment, if any.
+crate fn extended_theme(v: &[u8]) -> Option<&str> {
+    let comment = std::str::from_utf8(v).ok()?.trim_start().strip_prefix("/*")?;
+    let comment = &comment[..comment.find("*/")?];
+    Some(comment.trim().strip_prefix("extends:")?.trim())
+}
+
+/// Returns the CSS of the built-in theme called `name`.
+crate fn builtin_theme(name: &str) -> Option<&'static str> {
+    match name {
+        "light" => Some(static_files::themes::LIGHT),
+        "dark" => Some(static_files::themes::DARK),
+        "ayu" => Some(static_files::themes::AYU),
+        _ => None,
+    }
+}
+
+/// Adds the rules of `base` which `path` does not define to `path`. A rule with
+/// declarations replaces the rule of `base` as a whole.
+fn inherit_rules(path: &mut CssPath, base: &CssPath) {
+    let mut children = path.children.drain().collect::<Vec<_>>();
+    for base_child in &base.children {
+        match children.iter_mut().find(|child| child.name == base_child.name) {
+            Some(child) if child.declarations.is_empty() => inherit_rules(child, base_child),
+            Some(_) => {}
+            None => children.push(without_spans(base_child)),
+        }
+    }
+    path.children = children.into_iter().collect();
+}
+
+/// Inherited rules do not come from the file being loaded, so they must not point into it.
+fn without_spans(path: &CssPath) -> CssPath {
+    CssPath {
+        name: path.name.clone(),
+        span: DUMMY_SP,
+        children: path.children.iter().map(without_spans).collect(),
+        declarations: path.declarations.clone(),
+    }
+}
+
+/// The kind of value of a declaration, used to check that a theme sets its custom
+/// properties to the same kind of values as the default theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+crate enum ValueKind {
+    Color,
+    Length,
+    Url,
+    /// Anything else, including values like `var(--x)` or `calc(...)` whose kind
+    /// cannot be told without evaluating them. They are never reported.
+    Other,
+}
+
+impl ValueKind {
+    crate fn descr(self) -> &'static str {
+        match self {
+            ValueKind::Color => "color",
+            ValueKind::Length => "length",
+            ValueKind::Url => "URL",
+            ValueKind::Other => "value",
+        }
+    }
+}
+
+const LENGTH_UNITS: &[&str] =
+    &["px", "em", "rem", "ex", "ch", "vw", "vh", "vmin", "vmax", "cm", "mm", "in", "pt", "pc", "%"];
+
+const COLOR_KEYWORDS: &[&str] = &[
+    "transparent",
+    "currentcolor",
+    "black",
+    "white",
+    "gray",
+    "grey",
+    "silver",
+    "red",
+    "maroon",
+    "orange",
+    "yellow",
+    "olive",
+    "lime",
+    "green",
+    "aqua",
+    "cyan",
+    "teal",
+    "blue",
+    "navy",
+    "fuchsia",
+    "magenta",
+    "purple",
+];
+
+/// Tells what kind of value a declaration has, from its syntax.
+fn classify_value(value: &str) -> ValueKind {
+    let value = value.trim_end_matches("!important").trim().to_ascii_lowercase();
+    if value.starts_with("url(") {
+        ValueKind::Url
+    } else if ["rgb(", "rgba(", "hsl(", "hsla("].iter().any(|f| value.starts_with(f))
+        || COLOR_KEYWORDS.contains(&&*value)
+    {
+        ValueKind::Color
+    } else if value.strip_prefix('#').map_or(false, |hex| {
+        [3, 4, 6, 8].contains(&hex.len()) && hex.bytes().all(|c| c.is_ascii_hexdigit())
+    }) {
+        ValueKind::Color
+    } else if value == "0"
+        || LENGTH_UNITS.iter().any(|unit| {
+            value.strip_suffix(unit).map_or(false, |number| {
+                !number.is_empty() && number.trim_start_matches('-').parse::<f64>().is_ok()
+            })
+        })
+    {
+        ValueKind::Length
+    } else {
+        ValueKind::Other
+    }
+}
+
+/// Returns the custom properties (`--name: value`) set by the declarations of a rule.
+fn custom_properties(declarations: &str) -> impl Iterator<Item = (&str, &str)> {
+    declarations.split(';').filter_map(|declaration| {
+        let (name, value) = declaration.split_once(':')?;
+        let name = name.trim();
+        if name.starts_with("--") { Some((name, value.trim())) } else { None }
+    })
+}
+
+/// A theme which other themes are compared against.
+crate struct Baseline {
+    /// The file the baseline was loaded from, or `None` for the built-in light theme.
+    crate file: Option<PathBuf>,
+    crate paths: CssPath,
+}
+
+impl Baseline {
+    /// The built-in light theme, which themes are compared against by default.
+    crate fn default_theme() -> Baseline {
+        Baseline {
+            file: None,
+            paths: load_css_paths(static_files::themes::LIGHT.as_bytes(), BytePos(0)),
+        }
+    }
+
+    /// Loads a baseline given with `--theme-baseline`.
+    crate fn load(file: &Path, diag: &Handler) -> Option<Baseline> {
+        let data = match fs::read(file) {
+            Ok(data) => data,
+            Err(e) => {
+                diag.struct_err(&format!("failed to read \"{}\": {}", file.display(), e)).emit();
+                return None;
+            }
+        };
+        Some(Baseline { file: Some(file.to_owned()), paths: load_css_paths(&data, BytePos(0)) })
+    }
+
+    /// Loads the baselines given with `--theme-baseline`, or the default theme if there
+    /// are none.
+    crate fn load_all(files: &[String], diag: &Handler) -> Option<Vec<Baseline>> {
+        if files.is_empty() {
+            return Some(vec![Baseline::default_theme()]);
+        }
+        files.iter().map(|file| Baseline::load(Path::new(file), diag)).collect()
+    }
+
+    /// Describes the baseline in diagnostics.
+    crate fn descr(&self) -> String {
+        match self.file {
+            Some(ref file) => format!("the baseline theme \"{}\"", file.display()),
+            None => "the default theme".to_owned(),
+        }
+    }
+}
+
+/// A difference between a baseline theme and another theme.
+#[derive(Clone, Debug, PartialEq)]
+crate enum Difference {
+    /// A rule of the baseline theme which is missing from the other theme.
+    MissingRule {
+        name: String,
+        /// The rule of the other theme the missing rule should be nested in, if any.
+        parent: Option<Span>,
+    },
+    /// A custom property which the other theme sets to another kind of value than the
+    /// baseline theme does.
+    MismatchedProperty {
+        name: String,
+        expected: ValueKind,
+        /// The value set by the baseline theme.
+        default_value: String,
+        found: ValueKind,
+        /// The rule of the other theme setting the property, if it comes from its file.
+        rule: Option<Span>,
+    },
+}
+
+impl Difference {
+    crate fn report(&self, diag: &Handler, theme_file: &Path, baseline: &Baseline) {
+        let (mut err, span, label, note) = match self {
+            Difference::MissingRule { name, parent } => {
+                let mut err = diag.struct_err(&format!("missing \"{}\" rule", name));
+                if baseline.file.is_some() {
+                    err.note(&format!("the rule is defined by {}", baseline.descr()));
+                }
+                (err, parent, "the rule should be nested in this rule", "the rule is missing from")
+            }
+            Difference::MismatchedProperty { name, expected, default_value, found, rule } => {
+                let mut err = diag.struct_err(&format!(
+                    "custom property \"{}\" is set to a {} instead of a {}",
+                    name,
+                    found.descr(),
+                    expected.descr()
+                ));
+                err.help(&format!("{} sets it to `{}`", baseline.descr(), default_value));
+                (err, rule, "the property is set in this rule", "the property is set in")
+            }
+        };
+        match *span {
+            Some(span) => {
+                err.set_span(span);
+                err.span_label(span, label);
+            }
+            None => {
+                err.note(&format!("{} \"{}\"", note, theme_file.display()));
+            }
+        }
+        err.emit();
+    }
+}
+
+/// The result of checking a theme given with `--theme` against the baselines, which is
+/// included in the output of the JSON backend.
+#[derive(Clone, Debug)]
+crate struct ThemeCheck {
+    crate path: PathBuf,
+    /// Whether the theme could be loaded. A theme which could not has no differences.
+    crate parsed: bool,
+    /// The differences of the theme, along with the file of the baseline they are with, or
+    /// `None` for the default theme.
+    crate differences: Vec<(Option<PathBuf>, Difference)>,
+}
+
+crate fn get_differences(against: &CssPath, other: &CssPath, v: &mut Vec<Difference>) {
     if against.name == other.name {
         for child in &against.children {
             let mut found = false;
@@ -235,33 +809,241 @@ fn inner(v: &[u8], events: &[Events], pos: &mut usize) -> FxHashSet<CssPath> {
                     } else {
                         found_working = true;
                     }
+                    check_custom_properties(child, other_child, v);
                     found = true;
                     break;
                 }
             }
             if !found {
-                v.push(format!("  Missing \"{}\" rule", child.name));
+                v.push(Difference::MissingRule {
+                    name: child.name.clone(),
+                    parent: if other.span.is_dummy() { None } else { Some(other.span) },
+                });
             } else if !found_working {
-                v.extend(tmp.iter().cloned());
+                v.extend(tmp);
             }
         }
     }
 }
 
-crate fn test_theme_against<P: AsRef<Path>>(
+/// A rule of a theme which none of the baseline themes has, reported with
+/// `--check-theme-strict` since it is likely a typo.
+#[derive(Debug, PartialEq)]
+crate struct UnknownRule {
+    crate name: String,
+    crate span: Span,
+    /// The rule of the baselines with the closest name, if it is close enough to be the
+    /// intended one.
+    crate similar: Option<String>,
+}
+
+impl UnknownRule {
+    crate fn report(&self, diag: &Handler) {
+        let mut err =
+            diag.struct_span_warn(self.span, &format!("unknown selector \"{}\"", self.name));
+        err.span_label(self.span, "no baseline theme has a rule with this selector");
+        if let Some(ref similar) = self.similar {
+            err.help(&format!("a rule with a similar selector exists: \"{}\"", similar));
+        }
+        err.emit();
+    }
+}
+
+/// Returns the rules of `other` which none of the `against` themes has, in the order they
+/// appear in the theme file. Rules inherited from an extended theme are not reported.
+crate fn get_unknown_rules(against: &[&CssPath], other: &CssPath) -> Vec<UnknownRule> {
+    let mut v = Vec::new();
+    collect_unknown_rules(against, other, &mut v);
+    v.sort_by_key(|rule| rule.span.lo());
+    v
+}
+
+fn collect_unknown_rules(against: &[&CssPath], other: &CssPath, v: &mut Vec<UnknownRule>) {
+    for child in &other.children {
+        let known = against
+            .iter()
+            .flat_map(|path| path.children.iter().filter(|c| c.name == child.name))
+            .collect::<Vec<_>>();
+        if !known.is_empty() {
+            collect_unknown_rules(&known, child, v);
+        } else if !child.span.is_dummy() {
+            v.push(UnknownRule {
+                name: child.name.clone(),
+                span: child.span,
+                similar: similar_rule(against, &child.name),
+            });
+        }
+    }
+}
+
+/// Returns the name of the rule of `against` closest to `name`, if few enough edits separate
+/// them for `name` to be a typo of it.
+fn similar_rule(against: &[&CssPath], name: &str) -> Option<String> {
+    let max_dist = cmp::max(name.len(), 3) / 3;
+    against
+        .iter()
+        .flat_map(|path| &path.children)
+        .map(|c| (lev_distance(name, &c.name), &c.name))
+        .filter(|&(dist, _)| dist <= max_dist)
+        .min()
+        .map(|(_, similar)| similar.clone())
+}
+
+/// Reports the custom properties which `other` sets to another kind of value than `against`.
+fn check_custom_properties(against: &CssPath, other: &CssPath, v: &mut Vec<Difference>) {
+    for (name, default_value) in custom_properties(&against.declarations) {
+        let expected = classify_value(default_value);
+        if expected == ValueKind::Other {
+            continue;
+        }
+        for (_, value) in custom_properties(&other.declarations).filter(|&(n, _)| n == name) {
+            let found = classify_value(value);
+            if found != ValueKind::Other && found != expected {
+                v.push(Difference::MismatchedProperty {
+                    name: name.to_owned(),
+                    expected,
+                    default_value: default_value.to_owned(),
+                    found,
+                    rule: if other.span.is_dummy() { None } else { Some(other.span) },
+                });
+            }
+        }
+    }
+}
+
+/// Returns the rules of `against` which are missing from `other`, along with their
+/// declarations. If only some of the nested rules of a rule are missing, the rule is
+/// kept with just those.
+crate fn get_missing_rules(against: &CssPath, other: &CssPath) -> FxHashSet<CssPath> {
+    let mut missing = FxHashSet::default();
+    if against.name == other.name {
+        for child in &against.children {
+            match other.children.iter().find(|other_child| other_child.name == child.name) {
+                None => {
+                    missing.insert(child.clone());
+                }
+                Some(other_child) if child != other_child => {
+                    let mut partial = CssPath::new(child.name.clone(), child.span);
+                    partial.children = get_missing_rules(child, other_child);
+                    missing.insert(partial);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    missing
+}
+
+/// Writes the rules of the `baselines` missing from the theme `f` to a `.patch.css` file
+/// next to it, with the values of the baselines as defaults. Returns the path of the
+/// written file.
+crate fn write_theme_patch<P: AsRef<Path>>(
     f: &P,
-    against: &CssPath,
+    baselines: &[&Baseline],
     diag: &Handler,
-) -> (bool, Vec<String>) {
+) -> Option<PathBuf> {
+    let f = f.as_ref();
     let data = match fs::read(f) {
         Ok(c) => c,
         Err(e) => {
             diag.struct_err(&e.to_string()).emit();
-            return (false, vec![]);
+            return None;
+        }
+    };
+
+    let paths = load_css_paths(&data, BytePos(0));
+    let mut patch = CssPath::new(paths.name.clone(), DUMMY_SP);
+    for baseline in baselines {
+        patch.children.extend(get_missing_rules(&baseline.paths, &paths));
+    }
+    let descrs = baselines.iter().map(|baseline| baseline.descr()).collect::<Vec<_>>();
+    let mut out = format!(
+        "/* Rules missing from \"{}\", with the values of {}. */\n",
+        f.display(),
+        descrs.join(" and ")
+    );
+    patch.children_to_css(&mut out, 0);
+
+    let patch_file = f.with_extension("patch.css");
+    if let Err(e) = fs::write(&patch_file, out) {
+        diag.struct_err(&format!("failed to write \"{}\": {}", patch_file.display(), e)).emit();
+        return None;
+    }
+    Some(patch_file)
+}
+
+/// Lists the `.css` files of the directory `dir`, sorted by name, to check them with
+/// `--check-theme`. Patch files written by `--write-theme-patch` are skipped.
+crate fn theme_files_in(dir: &Path, diag: &Handler) -> Option<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            diag.struct_err(&format!("failed to read \"{}\": {}", dir.display(), e)).emit();
+            return None;
         }
     };
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => {
+                diag.struct_err(&format!("failed to read \"{}\": {}", dir.display(), e)).emit();
+                return None;
+            }
+        };
+        let is_patch = path
+            .file_name()
+            .map_or(false, |name| name.to_str().map_or(false, |name| name.ends_with(".patch.css")));
+        if path.is_file() && path.extension() == Some("css".as_ref()) && !is_patch {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Some(files)
+}
 
-    let paths = load_css_paths(&data);
+/// Loads the theme `f` into `source_map` and parses its rules.
+crate fn load_theme<P: AsRef<Path>>(
+    f: &P,
+    source_map: &SourceMap,
+    diag: &Handler,
+) -> Option<CssPath> {
+    let file = match source_map.load_file(f.as_ref()) {
+        Ok(file) => file,
+        Err(e) => {
+            diag.struct_err(&format!("failed to read \"{}\": {}", f.as_ref().display(), e)).emit();
+            return None;
+        }
+    };
+
+    let data = file.src.as_ref().map_or("", |src| &src[..]);
+    if let Some(base) = extended_theme(data.as_bytes()) {
+        if builtin_theme(base).is_none() {
+            diag.struct_err(&format!(
+                "\"{}\" extends the unknown theme \"{}\"",
+                f.as_ref().display(),
+                base
+            ))
+            .help("only the built-in \"light\", \"dark\" and \"ayu\" themes can be extended")
+            .emit();
+            return None;
+        }
+    }
+    Some(load_css_paths(data.as_bytes(), file.start_pos))
+}
+
+/// Loads the theme `f` into `source_map` and returns its differences with `against`,
+/// pointing into the theme file.
+crate fn test_theme_against<P: AsRef<Path>>(
+    f: &P,
+    against: &CssPath,
+    source_map: &SourceMap,
+    diag: &Handler,
+) -> (bool, Vec<Difference>) {
+    let paths = match load_theme(f, source_map, diag) {
+        Some(paths) => paths,
+        None => return (false, vec![]),
+    };
     let mut ret = vec![];
     get_differences(against, &paths, &mut ret);
     (true, ret)
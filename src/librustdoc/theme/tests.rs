@@ -45,8 +45,8 @@ fn test_comments_in_rules() {
 
     let mut ret = Vec::new();
     get_differences(
-        &load_css_paths(against.as_bytes()),
-        &load_css_paths(text.as_bytes()),
+        &load_css_paths(against.as_bytes(), BytePos(0)),
+        &load_css_paths(text.as_bytes(), BytePos(0)),
         &mut ret,
     );
     assert!(ret.is_empty());
@@ -61,8 +61,8 @@ fn test_text() {
 c // sdf
 d {}
 "#;
-    let paths = load_css_paths(text.as_bytes());
-    assert!(paths.children.contains(&CssPath::new("a b c d".to_owned())));
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    assert!(paths.children.contains(&CssPath::new("a b c d".to_owned(), DUMMY_SP)));
 }
 
 #[test]
@@ -81,14 +81,140 @@ fn test_comparison() {
 }
 "#;
 
-    let against = load_css_paths(y.as_bytes());
-    let other = load_css_paths(x.as_bytes());
+    let against = load_css_paths(y.as_bytes(), BytePos(0));
+    let other = load_css_paths(x.as_bytes(), BytePos(0));
 
     let mut ret = Vec::new();
     get_differences(&against, &other, &mut ret);
     assert!(ret.is_empty());
     get_differences(&other, &against, &mut ret);
-    assert_eq!(ret, vec!["  Missing \"c\" rule".to_owned()]);
+    assert_eq!(
+        ret,
+        vec![Difference::MissingRule {
+            name: "c".to_owned(),
+            parent: Some(Span::with_root_ctxt(BytePos(9), BytePos(10))),
+        }]
+    );
+}
+
+#[test]
+fn test_selector_spans() {
+    let text = r#"
+a {
+    b, c /* d */ {}
+}
+"#;
+
+    let paths = load_css_paths(text.as_bytes(), BytePos(10));
+    let a = paths.children.iter().next().unwrap();
+    assert_eq!(a.span, Span::with_root_ctxt(BytePos(11), BytePos(12)));
+    let b = a.children.iter().next().unwrap();
+    assert_eq!(b.span, Span::with_root_ctxt(BytePos(19), BytePos(31)));
+    assert_eq!(&text[9..21], "b, c /* d */");
+}
+
+#[test]
+fn test_nested_rules() {
+    let text = r#"
+.a {
+    color: red;
+    & .b { color: blue; }
+    margin: 0;
+    .c, &:hover {
+        top: 0;
+        .d { left: 0; }
+    }
+}
+"#;
+
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    let mut out = String::new();
+    paths.print_tree(&mut out, 0);
+    assert_eq!(
+        out,
+        ".a { color: red; margin: 0 }\n    .b { color: blue }\n    .c,&:hover { top: 0 }\n        \
+         .d { left: 0 }\n"
+    );
+
+    let against = load_css_paths(b".a { .b {} }", BytePos(0));
+    let mut ret = Vec::new();
+    get_differences(&against, &paths, &mut ret);
+    assert!(ret.is_empty());
+    get_differences(&paths, &against, &mut ret);
+    assert_eq!(
+        ret,
+        vec![Difference::MissingRule {
+            name: ".c,&:hover".to_owned(),
+            parent: Some(Span::with_root_ctxt(BytePos(0), BytePos(2))),
+        }]
+    );
+}
+
+#[test]
+fn test_resolve_nested_selector() {
+    assert_eq!(resolve_nested_selector(".b,.c"), ".b,.c");
+    assert_eq!(resolve_nested_selector("& .b"), ".b");
+    assert_eq!(resolve_nested_selector("&>.b"), ">.b");
+    assert_eq!(resolve_nested_selector("&:hover, & .b"), "&:hover,.b");
+    assert_eq!(resolve_nested_selector(":is(& .a, .b), & .c"), ":is(& .a, .b),.c");
+    assert_eq!(resolve_nested_selector(".a & .b"), ".a & .b");
+}
+
+#[test]
+fn test_missing_rules_to_css() {
+    let against = r#"
+a {
+    color: red; /* the main color */
+    background: blue;
+}
+b {
+    c { d: e; }
+    f { g: h; }
+}
+"#;
+
+    let other = r#"
+b {
+    c {}
+}
+"#;
+
+    let mut patch = CssPath::new("parent".to_owned(), DUMMY_SP);
+    patch.children = get_missing_rules(
+        &load_css_paths(against.as_bytes(), BytePos(0)),
+        &load_css_paths(other.as_bytes(), BytePos(0)),
+    );
+    let mut out = String::new();
+    patch.children_to_css(&mut out, 0);
+    assert_eq!(
+        out,
+        "a {\n    color: red;\n    background: blue;\n}\nb {\n    f {\n        g: h;\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_print_tree() {
+    let text = r#"
+b {}
+a {
+    color: red;
+    margin: 0;
+}
+"#;
+
+    let mut out = String::new();
+    load_css_paths(text.as_bytes(), BytePos(0)).print_tree(&mut out, 0);
+    assert_eq!(out, "a { color: red; margin: 0 }\nb\n");
+
+    let mut child = CssPath::new("c".to_owned(), DUMMY_SP);
+    child.declarations = "d: e;".to_owned();
+    let mut at_rule = CssPath::new("@media print".to_owned(), DUMMY_SP);
+    at_rule.children.insert(child);
+    let mut parent = CssPath::new("parent".to_owned(), DUMMY_SP);
+    parent.children.insert(at_rule);
+    let mut out = String::new();
+    parent.print_tree(&mut out, 1);
+    assert_eq!(out, "    @media print\n        c { d: e }\n");
 }
 
 #[test]
@@ -108,10 +234,241 @@ fn test_with_minification() {
     let text = include_str!("../html/static/themes/dark.css");
     let minified = minifier::css::minify(&text).expect("CSS minification failed");
 
-    let against = load_css_paths(text.as_bytes());
-    let other = load_css_paths(minified.as_bytes());
+    let against = load_css_paths(text.as_bytes(), BytePos(0));
+    let other = load_css_paths(minified.as_bytes(), BytePos(0));
 
     let mut ret = Vec::new();
     get_differences(&against, &other, &mut ret);
     assert!(ret.is_empty());
 }
+
+#[test]
+fn test_extended_theme() {
+    assert_eq!(extended_theme(b"/* extends: light */\na {}"), Some("light"));
+    assert_eq!(extended_theme(b"\n  /*extends:dark*/"), Some("dark"));
+    assert_eq!(extended_theme(b"/* General structure */\na {}"), None);
+    assert_eq!(extended_theme(b"a {}\n/* extends: light */"), None);
+}
+
+#[test]
+fn test_inherited_rules() {
+    let light = load_css_paths(static_files::themes::LIGHT.as_bytes(), BytePos(0));
+
+    let text = "/* extends: light */\nbody { background-color: black; }\n.new-rule { color: red; }";
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    let mut ret = Vec::new();
+    get_differences(&light, &paths, &mut ret);
+    assert!(ret.is_empty());
+
+    // The theme's own rules replace the inherited ones and keep their spans.
+    let body = paths.children.iter().find(|path| path.name == "body").unwrap();
+    assert_eq!(body.declarations, "background-color: black;");
+    assert!(!body.span.is_dummy());
+    let new_rule = paths.children.iter().find(|path| path.name == ".new-rule").unwrap();
+    assert!(!new_rule.span.is_dummy());
+    for path in &paths.children {
+        if path.name != "body" && path.name != ".new-rule" {
+            assert!(path.span.is_dummy());
+        }
+    }
+
+    // Unknown themes are not inherited from.
+    let text = "/* extends: solarized */\nbody { background-color: black; }";
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    assert_eq!(paths.children.len(), 1);
+}
+
+#[test]
+fn test_classify_value() {
+    assert_eq!(classify_value("#fff"), ValueKind::Color);
+    assert_eq!(classify_value("#1F2b3c !important"), ValueKind::Color);
+    assert_eq!(classify_value("rgba(0, 0, 0, 0.5)"), ValueKind::Color);
+    assert_eq!(classify_value("Transparent"), ValueKind::Color);
+    assert_eq!(classify_value("12px"), ValueKind::Length);
+    assert_eq!(classify_value("-0.5rem"), ValueKind::Length);
+    assert_eq!(classify_value("100%"), ValueKind::Length);
+    assert_eq!(classify_value("0"), ValueKind::Length);
+    assert_eq!(classify_value("url(\"wheel.svg\")"), ValueKind::Url);
+    assert_eq!(classify_value("var(--main-color)"), ValueKind::Other);
+    assert_eq!(classify_value("#ffff0"), ValueKind::Other);
+    assert_eq!(classify_value("px"), ValueKind::Other);
+}
+
+#[test]
+fn test_custom_property_kinds() {
+    let against = r#"
+:root {
+    --main-color: #000;
+    --sidebar-width: 200px;
+    --font: "Fira Sans";
+}
+"#;
+    let other = r#"
+:root {
+    --main-color: 12px;
+    --sidebar-width: 15em;
+    --font: 12px;
+}
+"#;
+
+    let mut ret = Vec::new();
+    get_differences(
+        &load_css_paths(against.as_bytes(), BytePos(0)),
+        &load_css_paths(other.as_bytes(), BytePos(0)),
+        &mut ret,
+    );
+    assert_eq!(
+        ret,
+        vec![Difference::MismatchedProperty {
+            name: "--main-color".to_owned(),
+            expected: ValueKind::Color,
+            default_value: "#000".to_owned(),
+            found: ValueKind::Length,
+            rule: Some(Span::with_root_ctxt(BytePos(1), BytePos(6))),
+        }]
+    );
+}
+
+#[test]
+fn test_layer_rules() {
+    let text = r#"
+@layer reset, theme.colors;
+@layer theme {
+    @layer colors { .a { b: c; } }
+}
+@layer theme.fonts { .d { e: f; } }
+@layer reset {
+    @layer base, extra;
+    .g { h: i; }
+}
+@layer last;
+"#;
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    let mut out = String::new();
+    paths.print_tree(&mut out, 0);
+    assert_eq!(
+        out,
+        "@layer last\n@layer reset\n    .g { h: i }\n    @layer base\n    @layer extra\n\
+         @layer theme\n    @layer colors\n        .a { b: c }\n    @layer fonts\n        .d { e: f }\n"
+    );
+
+    let other = "@layer theme { @layer colors { .a { b: c; } } }";
+    let mut ret = Vec::new();
+    get_differences(&paths, &load_css_paths(other.as_bytes(), BytePos(0)), &mut ret);
+    let mut missing = ret
+        .into_iter()
+        .map(|difference| match difference {
+            Difference::MissingRule { name, .. } => name,
+            difference => panic!("unexpected difference: {:?}", difference),
+        })
+        .collect::<Vec<_>>();
+    missing.sort();
+    assert_eq!(missing, ["@layer fonts", "@layer last", "@layer reset"]);
+}
+
+#[test]
+fn test_font_face_rules() {
+    let text = r#"
+@font-face {
+    font-family: 'Fira Sans';
+    font-weight: 400;
+    src: url("FiraSans-Regular.woff2") format("woff2");
+}
+@font-face {
+    font-weight: 500;
+    font-family: 'Fira Sans';
+    src: url("FiraSans-Medium.woff2") format("woff2");
+}
+"#;
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    let mut names = paths.children.iter().map(|path| &*path.name).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["@font-face (Fira Sans, 400)", "@font-face (Fira Sans, 500)"]);
+
+    let other = "@font-face { font-family: \"Fira Sans\"; font-weight: 400; src: url(a.woff2); }";
+    let mut patch = CssPath::new("parent".to_owned(), DUMMY_SP);
+    patch.children = get_missing_rules(&paths, &load_css_paths(other.as_bytes(), BytePos(0)));
+    let mut out = String::new();
+    patch.children_to_css(&mut out, 0);
+    assert_eq!(
+        out,
+        "@font-face {\n    font-weight: 500;\n    font-family: 'Fira Sans';\n    \
+         src: url(\"FiraSans-Medium.woff2\") format(\"woff2\");\n}\n"
+    );
+}
+
+#[test]
+fn test_container_rules() {
+    let text = r#"
+.sidebar { container: sidebar / inline-size; }
+@container sidebar (min-width: 400px) {
+    .item { display: flex; }
+}
+.card {
+    color: red;
+    @container (max-width: 200px) { color: blue; }
+}
+"#;
+    let paths = load_css_paths(text.as_bytes(), BytePos(0));
+    let container = paths.children.iter().find(|path| path.name.starts_with("@container")).unwrap();
+    assert_eq!(container.children.iter().map(|path| &*path.name).collect::<Vec<_>>(), [".item"]);
+
+    let card = paths.children.iter().find(|path| path.name == ".card").unwrap();
+    assert_eq!(card.declarations, "color: red;");
+    let nested = card.children.iter().next().unwrap();
+    assert!(nested.name.starts_with("@container"));
+    assert_eq!(nested.declarations, "color: blue;");
+
+    let mut ret = Vec::new();
+    get_differences(&paths, &paths, &mut ret);
+    assert!(ret.is_empty());
+}
+
+#[test]
+fn test_unknown_rules() {
+    let against = load_css_paths(
+        b".sidebar { color: red; }
+.content .item { color: blue; }
+@media (max-width: 700px) { .sidebar { display: none; } }",
+        BytePos(0),
+    );
+    let text = ".sidebarr { color: red; }
+.content .item { color: blue; }
+.unrelated-rule {}
+@media (max-width: 700px) { .sidebar {} .sidebr { display: none; } }";
+    let other = load_css_paths(text.as_bytes(), BytePos(0));
+
+    let unknown = get_unknown_rules(&[&against], &other);
+    assert_eq!(
+        unknown.iter().map(|rule| (&*rule.name, rule.similar.as_deref())).collect::<Vec<_>>(),
+        [(".sidebarr", Some(".sidebar")), (".unrelated-rule", None), (".sidebr", Some(".sidebar"))]
+    );
+    let span = unknown[0].span;
+    assert_eq!(&text[span.lo().0 as usize..span.hi().0 as usize], ".sidebarr");
+
+    // A rule only needs to be known to one of the baselines.
+    let unrelated = load_css_paths(b".unrelated-rule {}", BytePos(0));
+    let unknown = get_unknown_rules(&[&against, &unrelated], &other);
+    assert_eq!(
+        unknown.iter().map(|rule| &*rule.name).collect::<Vec<_>>(),
+        [".sidebarr", ".sidebr"]
+    );
+
+    assert!(get_unknown_rules(&[&against], &against).is_empty());
+}
+
+#[test]
+fn test_theme_files_in() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in &["dark.css", "custom.css", "custom.patch.css", "README.md"] {
+        fs::write(dir.path().join(name), "").unwrap();
+    }
+    // Only files are themes, whatever their name.
+    fs::create_dir(dir.path().join("old.css")).unwrap();
+
+    let diag = Handler::with_tty_emitter(rustc_errors::ColorConfig::Never, true, None, None);
+    assert_eq!(
+        theme_files_in(dir.path(), &diag),
+        Some(vec![dir.path().join("custom.css"), dir.path().join("dark.css")])
+    );
+}
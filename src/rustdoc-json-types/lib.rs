@@ -26,6 +26,8 @@ pub struct Crate {
     pub paths: HashMap<Id, ItemSummary>,
     /// Maps `crate_id` of items to a crate name and html_root_url if it exists.
     pub external_crates: HashMap<u32, ExternalCrate>,
+    /// The results of checking the themes given with `--theme` against the baseline themes.
+    pub theme_checks: Vec<ThemeCheck>,
     /// A single version number to be used in the future when making backwards incompatible changes
     /// to the JSON output.
     pub format_version: u32,
@@ -37,6 +39,32 @@ pub struct ExternalCrate {
     pub html_root_url: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ThemeCheck {
+    /// The path of the theme, as given to `--theme`.
+    pub path: PathBuf,
+    /// Whether the theme could be read and parsed. A theme which could not has no `differences`.
+    pub parsed: bool,
+    pub differences: Vec<ThemeDifference>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ThemeDifference {
+    /// The `--theme-baseline` file the theme differs from, or `None` for the default theme.
+    pub baseline: Option<PathBuf>,
+    pub kind: ThemeDifferenceKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeDifferenceKind {
+    /// A rule of the baseline which is missing from the theme.
+    MissingRule { name: String },
+    /// A custom property the theme sets to another kind of value (like `"color"` or
+    /// `"length"`) than the baseline does.
+    MismatchedProperty { name: String, expected: String, found: String, default_value: String },
+}
+
 /// For external (not defined in the local crate) items, you don't get the same level of
 /// information. This struct should contain enough to generate a link/reference to the item in
 /// question, or can be used by a tool that takes the json output of multiple crates to find
@@ -76,6 +104,9 @@ pub struct Item {
     /// Stringified versions of the attributes on this item (e.g. `"#[inline]"`)
     pub attrs: Vec<String>,
     pub deprecation: Option<Deprecation>,
+    /// The version this item is stable since, taken from `#[stable]` or, for crates that don't
+    /// use stability attributes, from `#[doc(since = "...")]`.
+    pub since: Option<String>,
     #[serde(flatten)]
     pub inner: ItemEnum,
 }
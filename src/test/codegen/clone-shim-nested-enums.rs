@@ -0,0 +1,47 @@
+// Checks that derived `Clone` impls of enums nesting other `Copy` enums are lowered to a plain
+// copy, both in their monomorphized MIR (through `#[rustc_clone_shim_expect]`) and in the
+// generated code.
+//
+// no-system-llvm
+// compile-flags: -O
+// codegen-assert: clone_nested memcpy
+// codegen-assert: clone_nested_generic memcpy
+// codegen-assert: clone_nested_generic no-calls
+
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+pub enum Inner {
+    A(u8),
+    B(u16),
+    C(u32),
+}
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+pub enum Outer {
+    A(Inner),
+    B { x: Inner, y: u64 },
+    C,
+}
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+pub enum Generic<T> {
+    A(T),
+    B(Option<T>),
+    C { x: u16, y: T },
+}
+
+#[no_mangle]
+pub fn clone_nested(o: &Outer) -> Outer {
+    o.clone()
+}
+
+// `Generic<Outer>` is only known to be `Copy` once monomorphized.
+#[no_mangle]
+pub fn clone_nested_generic(g: &Generic<Outer>) -> Generic<Outer> {
+    g.clone()
+}
@@ -0,0 +1,48 @@
+// Checks the codegen of derived `Clone` impls for enums with `Copy` payloads
+// through `codegen-assert` headers instead of FileCheck patterns.
+//
+// no-system-llvm
+// compile-flags: -O
+// codegen-assert: clone_copy_foo memcpy
+// codegen-assert: clone_copy_bar memcpy
+// codegen-assert: clone_copy_bar no-calls
+// codegen-assert: clone_generic_copy memcpy
+
+#![crate_type = "lib"]
+
+#[derive(Clone, Copy)]
+pub enum Foo {
+    A(u8),
+    B(u16),
+    C(u32),
+}
+
+#[derive(Clone, Copy)]
+pub enum Bar {
+    A { x: u32, y: u32 },
+    B(u64),
+    C,
+}
+
+#[no_mangle]
+pub fn clone_copy_foo(f: &Foo) -> Foo {
+    f.clone()
+}
+
+#[no_mangle]
+pub fn clone_copy_bar(b: &Bar) -> Bar {
+    b.clone()
+}
+
+#[derive(Clone, Copy)]
+pub enum Generic<T> {
+    A(T),
+    B(u32),
+    C { x: u16, y: T },
+}
+
+// Derived `Clone` for a generic type which is `Copy` once monomorphized.
+#[no_mangle]
+pub fn clone_generic_copy(g: &Generic<u64>) -> Generic<u64> {
+    g.clone()
+}
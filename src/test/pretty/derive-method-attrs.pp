@@ -0,0 +1,21 @@
+#![feature(prelude_import)]
+#![no_std]
+#[prelude_import]
+use ::std::prelude::rust_2015::*;
+#[macro_use]
+extern crate std;
+// pretty-compare-only
+// pretty-mode:expanded
+// pp-exact:derive-method-attrs.pp
+
+// Derived methods are only `#[inline]`, the lint allow is on the impl.
+
+struct Config;
+#[automatically_derived]
+#[allow(unused_qualifications)]
+impl ::core::default::Default for Config {
+    #[inline]
+    fn default() -> Config { Config }
+}
+
+fn main() { }
@@ -0,0 +1,10 @@
+// pretty-compare-only
+// pretty-mode:expanded
+// pp-exact:derive-method-attrs.pp
+
+// Derived methods are only `#[inline]`, the lint allow is on the impl.
+
+#[derive(Default)]
+struct Config;
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![crate_type = "lib"]
+
+pub fn split(pair: (String, String)) -> String {
+    let first = pair.0;
+    drop(pair.1);
+    first
+}
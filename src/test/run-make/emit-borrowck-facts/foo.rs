@@ -0,0 +1,6 @@
+#![crate_type = "rlib"]
+
+pub fn first(v: &mut Vec<i32>) -> &i32 {
+    v.push(0);
+    &v[0]
+}
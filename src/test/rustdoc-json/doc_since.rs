@@ -0,0 +1,8 @@
+#![feature(doc_since)]
+
+// @is doc_since.json "$.index[*][?(@.name=='foo')].since" \"1.2.0\"
+#[doc(since = "1.2.0")]
+pub fn foo() {}
+
+// @has - "$.index[*][?(@.name=='bar')]"
+pub fn bar() {}
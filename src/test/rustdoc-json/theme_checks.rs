@@ -0,0 +1,9 @@
+// compile-flags: --theme {{src-base}}/auxiliary/theme_checks.css
+
+// @count theme_checks.json "$.theme_checks[*]" 1
+// @is - "$.theme_checks[0].parsed" true
+// @is - "$.theme_checks[0].differences[0].baseline" null
+// @has - "$.theme_checks[0].differences[*].kind.missing_rule.name"
+
+// @has - "$.index[*][?(@.name=='foo')]"
+pub fn foo() {}
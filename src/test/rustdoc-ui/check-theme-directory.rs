@@ -0,0 +1,4 @@
+// compile-flags: -Z unstable-options --check-theme {{src-base}}/themes-dir
+// compile-flags: --theme-baseline {{src-base}}/themes/baseline.css
+
+// Every theme of the directory is checked, and the check fails if one of them does.
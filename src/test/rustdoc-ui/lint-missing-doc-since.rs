@@ -0,0 +1,35 @@
+#![feature(doc_since)]
+#![deny(rustdoc::missing_doc_since)]
+
+//! crate level doc
+
+/// doc
+#[doc(since = "1.0.0")]
+pub fn documented() {}
+
+/// doc
+pub fn undocumented() {} //~^ ERROR missing `#[doc(since = "...")]`
+
+/// doc
+#[doc(since = "1.0.0")]
+pub struct Foo {
+    pub field: u8,
+}
+
+impl Foo {
+    /// doc
+    pub fn method() {} //~^ ERROR missing `#[doc(since = "...")]`
+}
+
+impl Clone for Foo {
+    fn clone(&self) -> Self {
+        Foo { field: self.field }
+    }
+}
+
+#[allow(rustdoc::missing_doc_since)]
+/// doc
+pub fn allowed() {}
+
+/// doc
+fn private() {}
@@ -0,0 +1,2 @@
+// compile-flags: -Z unstable-options --check-theme {{src-base}}/themes/matching.css
+// compile-flags: --theme-baseline {{src-base}}/themes/extended-baseline.css
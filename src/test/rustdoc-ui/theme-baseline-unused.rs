@@ -0,0 +1,5 @@
+// check-pass
+// compile-flags: -Z unstable-options --theme-baseline {{src-base}}/themes/missing.css
+
+// The baseline is only loaded when a theme is checked against it, so this file not existing
+// is not an error.
@@ -0,0 +1,3 @@
+// check-pass
+// compile-flags: -Z unstable-options --check-theme {{src-base}}/themes/matching.css
+// compile-flags: --theme-baseline {{src-base}}/themes/baseline.css
@@ -0,0 +1,19 @@
+#![crate_name = "foo"]
+#![feature(doc_since)]
+
+// @has 'foo/struct.Foo.html' '//span[@class="since"]' '1.2.0'
+#[doc(since = "1.2.0")]
+pub struct Foo;
+
+impl Foo {
+    // @has 'foo/struct.Foo.html' '//*[@id="method.bar"]//span[@class="since"]' '1.3.0'
+    #[doc(since = "1.3.0")]
+    pub fn bar() {}
+
+    // @!has 'foo/struct.Foo.html' '//*[@id="method.baz"]//span[@class="since"]'
+    #[doc(since = "1.2.0")]
+    pub fn baz() {}
+}
+
+// @!has 'foo/struct.NoSince.html' '//span[@class="since"]'
+pub struct NoSince;
@@ -0,0 +1,20 @@
+// edition:2018
+// Check the suggestions for a spawned future that isn't `Send` because of a type parameter.
+
+use std::future::Future;
+
+fn spawn<F: Future + Send + 'static>(_: F) {}
+
+async fn wait() {}
+
+async fn hold<T>(value: T) {
+    wait().await;
+    drop(value);
+}
+
+fn spawn_generic<T: 'static>(value: T) {
+    spawn(hold(value));
+    //~^ ERROR future cannot be sent between threads safely
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Assigning through a reference created by borrowing another place points at each borrow the
+// reference is reached through, and names the place which is assigned in the end.
+
+struct S {
+    f: i32,
+}
+
+fn reborrow_chain() {
+    let mut x = 0;
+    let a = &mut x;
+    let b = &mut *a;
+    let shared = &*b;
+    *b = 1;
+    //~^ ERROR cannot assign to `*b` because it is borrowed
+    drop(shared);
+}
+
+fn field_through_borrow() {
+    let mut s = S { f: 0 };
+    let r = &mut s;
+    let f = &r.f;
+    r.f = 1;
+    //~^ ERROR cannot assign to `r.f` because it is borrowed
+    drop(f);
+}
+
+fn main() {}
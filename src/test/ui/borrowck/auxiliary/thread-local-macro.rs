@@ -0,0 +1,10 @@
+#![feature(thread_local)]
+
+#[macro_export]
+macro_rules! local_ref {
+    () => {{
+        #[thread_local]
+        static VALUE: u8 = 0;
+        &VALUE
+    }};
+}
@@ -0,0 +1,20 @@
+#![feature(raw_ref_op)]
+
+fn moved() {
+    let x = String::new();
+    drop(x);
+    let p = &raw const x; //~ ERROR raw borrow of moved value: `x`
+}
+
+fn partially_moved() {
+    let mut x = (String::new(), String::new());
+    drop(x.0);
+    let p = &raw mut x; //~ ERROR raw borrow of partially moved value: `x`
+}
+
+fn uninitialized() {
+    let x: i32;
+    let p = &raw const x; //~ ERROR raw borrow of possibly-uninitialized variable: `x`
+}
+
+fn main() {}
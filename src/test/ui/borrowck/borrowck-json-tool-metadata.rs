@@ -0,0 +1,13 @@
+// compile-flags: --error-format=json
+
+// Borrowck errors identify what they are about in the `tool_metadata` of the JSON output,
+// e.g. the borrow they conflict with by its place and its position in the body.
+
+fn main() {
+    let mut x = 0;
+    let first = &mut x;
+    let second = &mut x;
+    *first += 1;
+    *second += 1;
+    //~^^^ ERROR cannot borrow `x` as mutable more than once at a time
+}
@@ -0,0 +1,22 @@
+// Check that a borrow which conflicts with several earlier borrows points out
+// all of them, except for those that were already reported as conflicting.
+
+fn shared() {
+    let mut x = 0;
+    let a = &x;
+    let b = &x;
+    let c = &mut x; //~ ERROR cannot borrow `x` as mutable because it is also borrowed as immutable
+    println!("{} {} {}", a, b, c);
+}
+
+fn unique() {
+    let mut y = 0;
+    let p = &mut y;
+    let q = &mut y; //~ ERROR cannot borrow `y` as mutable more than once at a time
+    let r = &mut y; //~ ERROR cannot borrow `y` as mutable more than once at a time
+    *p += 1;
+    *q += 1;
+    *r += 1;
+}
+
+fn main() {}
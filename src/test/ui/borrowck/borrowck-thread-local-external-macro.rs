@@ -0,0 +1,15 @@
+// aux-build:thread-local-macro.rs
+
+// Borrows of thread-locals taken by a macro from another crate are reported at the
+// invocation of the macro.
+
+#![feature(thread_local)]
+
+#[macro_use]
+extern crate thread_local_macro;
+
+fn local() -> &'static u8 {
+    local_ref!() //~ ERROR thread-local variable borrowed past end of function
+}
+
+fn main() {}
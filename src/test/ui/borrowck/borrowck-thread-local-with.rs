@@ -0,0 +1,12 @@
+// The value of a `thread_local!` is only borrowed by the closure passed to `with`.
+
+thread_local!(static FOO: u8 = 3);
+
+fn get<'a>() -> &'a u8 {
+    FOO.with(|foo| foo)
+    //~^ ERROR cannot infer
+}
+
+fn main() {
+    println!("{}", get());
+}
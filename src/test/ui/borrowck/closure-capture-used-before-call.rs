@@ -0,0 +1,18 @@
+// When a closure's capture conflicts with a use of the captured variable by the enclosing
+// function before the closure is called, point at the capture and suggest how to end it.
+
+fn read(_: &i32) {}
+
+fn main() {
+    let mut x = 0;
+    let mut inc = || x += 1;
+    read(&x);
+    //~^ ERROR cannot borrow `x` as immutable because it is also borrowed as mutable
+    inc();
+
+    let mut v = vec![1];
+    let peek = || v.len();
+    v.push(2);
+    //~^ ERROR cannot borrow `v` as mutable because it is also borrowed as immutable
+    peek();
+}
@@ -0,0 +1,61 @@
+// Check that errors about possibly-uninitialized variables point at the conditions
+// deciding whether the variable is initialized.
+
+fn if_without_else(c: bool) -> i32 {
+    let x;
+    if c {
+        x = 1;
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn skipping_else(n: i32) -> i32 {
+    let x;
+    if n > 0 {
+        x = n;
+    } else {
+        println!("negative");
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn skipping_then(n: i32) -> i32 {
+    let x;
+    if n > 0 {
+        println!("positive");
+    } else {
+        x = n;
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn nested(a: bool, b: bool) -> i32 {
+    let x;
+    if a {
+        if b {
+            x = 1;
+        }
+    } else {
+        x = 2;
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn while_loop(mut n: i32) -> i32 {
+    let mut x;
+    while n > 0 {
+        x = n;
+        n -= 1;
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn never_initialized(c: bool) -> i32 {
+    let x: i32;
+    if c {
+        return 0;
+    }
+    x //~ ERROR use of possibly-uninitialized variable: `x`
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+// Calling a closure again after a call moved it is reported as such, along with a way to keep
+// the closure from moving what it captures. Borrowing is not suggested for values passed to
+// functions taking them by value, including generic ones like `drop`, `push` or `Some`.
+
+struct NotClone;
+
+fn consume(_: NotClone) {}
+
+fn main() {
+    let x = NotClone;
+    let f = || drop(x);
+    f();
+    f(); //~ ERROR closure `f` cannot be called more than once
+
+    let v = vec![1];
+    let g = || {
+        let _moved = v;
+    };
+    g();
+    g(); //~ ERROR closure `g` cannot be called more than once
+
+    let y = NotClone;
+    let h = || consume(y);
+    h();
+    h(); //~ ERROR closure `h` cannot be called more than once
+
+    let a = NotClone;
+    let i = || std::mem::forget(a);
+    i();
+    i(); //~ ERROR closure `i` cannot be called more than once
+
+    let b = NotClone;
+    let mut list = Vec::new();
+    let j = || list.push(b);
+    j();
+    j(); //~ ERROR closure `j` cannot be called more than once
+
+    let c = NotClone;
+    let k = || Some(c);
+    k();
+    k(); //~ ERROR closure `k` cannot be called more than once
+}
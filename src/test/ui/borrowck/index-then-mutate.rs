@@ -0,0 +1,27 @@
+// Overloaded index operators borrow the whole container, so conflicts between
+// them point that out and suggest ways to avoid keeping it borrowed.
+
+use std::collections::HashMap;
+
+fn vec() {
+    let mut v: Vec<Vec<usize>> = vec![vec![], vec![]];
+    v[0].push(v[1].len());
+    //~^ ERROR cannot borrow `v` as immutable because it is also borrowed as mutable
+}
+
+fn map() {
+    let mut map: HashMap<u32, Vec<usize>> = HashMap::new();
+    let first = &map[&0];
+    map.insert(1, vec![]);
+    //~^ ERROR cannot borrow `map` as mutable because it is also borrowed as immutable
+    first.len();
+}
+
+fn get_mut() {
+    // The borrow kept alive is not made by indexing, so there is nothing to note.
+    let mut map: HashMap<u32, Vec<usize>> = HashMap::new();
+    map.get_mut(&0).unwrap().push(map[&1].len());
+    //~^ ERROR cannot borrow `map` as immutable because it is also borrowed as mutable
+}
+
+fn main() {}
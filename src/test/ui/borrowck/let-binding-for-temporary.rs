@@ -0,0 +1,25 @@
+// run-rustfix
+// Suggest binding a temporary which is dropped while still borrowed to a variable.
+
+struct Wrapper(i32);
+
+impl Wrapper {
+    fn get(&self) -> &i32 {
+        &self.0
+    }
+
+    fn set(&mut self, value: i32) {
+        self.0 = value;
+    }
+}
+
+fn id<T>(x: T) -> T {
+    x
+}
+
+fn main() {
+    let binding = 1;
+    let value = Wrapper(binding).get(); //~ ERROR temporary value dropped while borrowed
+    let wrapper = id(&mut Wrapper(binding)); //~ ERROR temporary value dropped while borrowed
+    wrapper.set(*value);
+}
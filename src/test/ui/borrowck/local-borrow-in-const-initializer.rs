@@ -0,0 +1,19 @@
+// Borrows of locals that escape a `const` or `static` initializer explain that the
+// initializer's locals are dropped once it has been evaluated.
+
+const C: &'static i32 = {
+    let x = 1;
+    &x
+    //~^ ERROR `x` does not live long enough
+};
+
+static S: &'static i32 = {
+    let y = 2;
+    &y
+    //~^ ERROR `y` does not live long enough
+};
+
+// Promoted constants are fine.
+const OK: &'static i32 = &3;
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Moves by the `?` operator point at the `?` and suggest borrowing the contents of
+// an `Option` instead. Borrowing a `Result` would also borrow its error, which `?`
+// couldn't convert anymore, so nothing is suggested for it.
+
+fn total_len(x: Option<String>) -> Option<usize> {
+    let s = x?;
+    let t = x?;
+    //~^ ERROR use of moved value: `x`
+    Some(s.len() + t.len())
+}
+
+fn total_len_or_err(x: Result<String, String>) -> Result<usize, String> {
+    let s = x?;
+    let t = x?;
+    //~^ ERROR use of moved value: `x`
+    Ok(s.len() + t.len())
+}
+
+fn main() {}
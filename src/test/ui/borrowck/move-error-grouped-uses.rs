@@ -0,0 +1,10 @@
+// Check that the uses of a value after the same move are reported in a single error.
+
+fn main() {
+    let s = String::new();
+    let t = s;
+    let _a = &s; //~ ERROR borrow of moved value: `s`
+    let _b = s.len();
+    let _c = &s;
+    drop(t);
+}
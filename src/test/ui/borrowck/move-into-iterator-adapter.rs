@@ -0,0 +1,13 @@
+// Moves into closures passed to iterator adapters point at the adapter.
+
+fn main() {
+    let names = vec![String::from("a"), String::from("b")];
+    let suffix = String::from("!");
+    let greetings: Vec<String> = names.into_iter().map(move |name| name + &suffix).collect();
+    let _ = (greetings, suffix.len()); //~ ERROR borrow of moved value: `suffix`
+
+    // `Option::map` is not an iterator adapter.
+    let prefix = String::from("?");
+    let greeting = Some(String::from("c")).map(move |name| prefix + &name);
+    let _ = (greeting, prefix.len()); //~ ERROR borrow of moved value: `prefix`
+}
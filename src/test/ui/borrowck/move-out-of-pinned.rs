@@ -0,0 +1,38 @@
+// Moves out of values that may be pinned are noted with `#![feature(unsafe_pinned)]`.
+
+#![feature(unsafe_pinned)]
+
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+struct Pinned {
+    _data: String,
+    _pin: PhantomPinned,
+}
+
+struct Plain {
+    _data: String,
+}
+
+fn move_not_unpin(r: &mut Pinned) -> Pinned {
+    *r
+    //~^ ERROR cannot move out of `*r` which is behind a mutable reference
+}
+
+fn move_from_get_unchecked_mut(p: Pin<&mut Plain>) -> Plain {
+    let r = unsafe { Pin::get_unchecked_mut(p) };
+    *r
+    //~^ ERROR cannot move out of `*r` which is behind a mutable reference
+}
+
+fn move_unpin(r: &mut Plain) -> Plain {
+    *r
+    //~^ ERROR cannot move out of `*r` which is behind a mutable reference
+}
+
+fn move_generic<T>(r: &mut T) -> T {
+    *r
+    //~^ ERROR cannot move out of `*r` which is behind a mutable reference
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Moving a field out of `self` in a method of a type implementing `Drop`
+// suggests how to take the value out instead.
+
+struct Handle;
+
+struct Connection {
+    name: String,
+    socket: Option<Handle>,
+    handle: Handle,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {}
+}
+
+impl Connection {
+    fn into_name(self) -> String {
+        self.name
+        //~^ ERROR cannot move out of type `Connection`, which implements the `Drop` trait
+    }
+
+    fn into_socket(mut self) -> Option<Handle> {
+        self.socket
+        //~^ ERROR cannot move out of type `Connection`, which implements the `Drop` trait
+    }
+
+    fn into_handle(self) -> Handle {
+        self.handle
+        //~^ ERROR cannot move out of type `Connection`, which implements the `Drop` trait
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Moving out of a `static mut` is an error even inside `unsafe`, point at the static
+// to explain that `unsafe` only allows reading and assigning it.
+
+static mut NAMES: Vec<String> = Vec::new();
+
+fn main() {
+    unsafe {
+        let names = NAMES;
+        //~^ ERROR cannot move out of static item `NAMES`
+        drop(names);
+    }
+}
@@ -0,0 +1,19 @@
+// A closure which is not `Copy` because it moves a captured variable is
+// explained even when it is moved out of a field, but one which only mutates
+// a captured variable is not said to be `FnOnce`.
+
+struct Wrapper<F>(F);
+
+fn consume<F>(_: F) {}
+
+fn main() {
+    let x = String::new();
+    let w = Wrapper(|| drop(x));
+    consume(w.0);
+    consume(w.0); //~ ERROR use of moved value: `w.0`
+
+    let mut y = 0;
+    let v = Wrapper(|| y += 1);
+    consume(v.0);
+    consume(v.0); //~ ERROR use of moved value: `v.0`
+}
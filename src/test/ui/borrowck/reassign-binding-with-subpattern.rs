@@ -0,0 +1,8 @@
+// The suggestion to make a binding with a subpattern mutable keeps the subpattern.
+
+fn main() {
+    let x @ (_, _) = (1, 2);
+    x = (3, 4);
+    //~^ ERROR cannot assign twice to immutable variable `x`
+    drop(x);
+}
@@ -0,0 +1,41 @@
+// Suggest moving the first of two conflicting borrows past the uses of the second one, when
+// that is all it takes to make them not overlap.
+
+fn use_ref(_: &i32) {}
+fn use_mut(_: &mut i32) {}
+
+fn shared_after_mutable() {
+    let mut x = 0;
+    let a = &mut x;
+    let b = &x; //~ ERROR cannot borrow `x` as immutable because it is also borrowed as mutable
+    use_ref(b);
+    use_mut(a);
+}
+
+fn two_mutable() {
+    let mut x = 0;
+    let a = &mut x;
+    let b = &mut x; //~ ERROR cannot borrow `x` as mutable more than once at a time
+    use_mut(b);
+    use_ref(b);
+    use_mut(a);
+}
+
+fn still_used_afterwards() {
+    let mut x = 0;
+    let a = &mut x;
+    let b = &x; //~ ERROR cannot borrow `x` as immutable because it is also borrowed as mutable
+    use_ref(b);
+    use_mut(a);
+    use_ref(b);
+}
+
+fn comment_after_first_borrow() {
+    let mut x = 0;
+    let a = &mut x; // stays where it is
+    let b = &x; //~ ERROR cannot borrow `x` as immutable because it is also borrowed as mutable
+    use_ref(b);
+    use_mut(a);
+}
+
+fn main() {}
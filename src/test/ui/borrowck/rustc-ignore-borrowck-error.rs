@@ -0,0 +1,36 @@
+// Borrowck errors whose codes are listed in `#[rustc_ignore_borrowck_error]` are emitted as
+// warnings.
+
+#![feature(rustc_attrs)]
+
+#[rustc_ignore_borrowck_error(E0502)]
+fn ignored(v: &mut Vec<usize>) {
+    let first = &v[0];
+    v.push(1); //~ WARNING cannot borrow `*v` as mutable because it is also borrowed as immutable
+    let _x = *first;
+}
+
+#[rustc_ignore_borrowck_error(E0502)]
+fn ignored_in_closure(v: &mut Vec<usize>) {
+    let mut f = || {
+        let first = &v[0];
+        v.push(1);
+        //~^ WARNING cannot borrow `*v` as mutable because it is also borrowed as immutable
+        let _x = *first;
+    };
+    f();
+}
+
+#[rustc_ignore_borrowck_error(E0499)]
+fn not_ignored(v: &mut Vec<usize>) {
+    let first = &v[0];
+    v.push(1); //~ ERROR cannot borrow `*v` as mutable because it is also borrowed as immutable
+    let _x = *first;
+}
+
+#[rustc_ignore_borrowck_error(E05, borrowck)]
+//~^ ERROR expected an error code like `E0502`
+//~| ERROR expected an error code like `E0502`
+fn malformed() {}
+
+fn main() {}
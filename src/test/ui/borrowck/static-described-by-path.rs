@@ -0,0 +1,15 @@
+// Statics sharing their name with another item are described by their path in borrowck errors,
+// which point at their definition.
+
+mod a {
+    pub static CONFIG: u32 = 0;
+}
+
+mod b {
+    pub static CONFIG: String = String::new();
+}
+
+fn main() {
+    a::CONFIG = 1; //~ ERROR cannot assign to immutable static item `a::CONFIG`
+    let _config = b::CONFIG; //~ ERROR cannot move out of static item `b::CONFIG`
+}
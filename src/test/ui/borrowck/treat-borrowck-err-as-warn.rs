@@ -0,0 +1,20 @@
+// Check that `-Z treat-borrowck-err-as-warn` only downgrades the listed errors.
+
+// compile-flags: -Z treat-borrowck-err-as-warn=E0502,E0506
+
+fn main() {
+    let mut x = 0;
+    let r = &x;
+    x += 1; //~ WARNING cannot assign to `x` because it is borrowed
+    println!("{}", r);
+
+    let mut y = 0;
+    let a = &y;
+    let b = &mut y; //~ WARNING cannot borrow `y` as mutable because it is also borrowed as immutable
+    *b += 1;
+    println!("{}", a);
+
+    let z = String::new();
+    drop(z);
+    drop(z); //~ ERROR use of moved value: `z`
+}
@@ -0,0 +1,29 @@
+// Moves in the scrutinee of a `while let` loop happen again on every iteration.
+
+struct Queue(Vec<u32>);
+
+impl Queue {
+    fn pop_front_move(self) -> Option<u32> {
+        self.0.into_iter().next()
+    }
+}
+
+fn first(v: Vec<u32>) -> Option<u32> {
+    v.into_iter().next()
+}
+
+fn method(queue: Queue) {
+    while let Some(x) = queue.pop_front_move() {
+        //~^ ERROR use of moved value: `queue`
+        println!("{}", x);
+    }
+}
+
+fn argument(v: Vec<u32>) {
+    while let Some(x) = first(v) {
+        //~^ ERROR use of moved value: `v`
+        println!("{}", x);
+    }
+}
+
+fn main() {}
@@ -9,5 +9,5 @@ fn main() {
     };
     debug_dump_dict();
     debug_dump_dict();
-    //~^ ERROR use of moved value: `debug_dump_dict`
+    //~^ ERROR closure `debug_dump_dict` cannot be called more than once
 }
@@ -0,0 +1,33 @@
+// Check that borrowck labels name the precise path captured by the closure,
+// not just the root variable.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING: the feature `capture_disjoint_fields` is incomplete
+
+struct Config { name: String, verbose: bool }
+struct App { config: Config }
+
+impl App {
+    fn rename(&mut self) {
+        let c = || {
+            println!("{}", self.config.name);
+        };
+
+        self.config.name = format!("new-name");
+        //~^ ERROR: cannot assign to `self.config.name` because it is borrowed
+        c();
+    }
+}
+
+fn move_name() {
+    let app = App { config: Config { name: format!("name"), verbose: false } };
+    let c = move || {
+        drop(app.config.name);
+    };
+
+    println!("{}", app.config.name);
+    //~^ ERROR: borrow of moved value: `app.config.name`
+    c();
+}
+
+fn main() {}
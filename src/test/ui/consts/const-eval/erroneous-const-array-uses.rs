@@ -0,0 +1,18 @@
+// build-fail
+
+// Uses of an erroneous constant are reported once per body.
+
+#![warn(const_err)]
+
+fn black_box<T>(_: T) {
+    unimplemented!()
+}
+
+const FOO: u8 = [5u8][1];
+//~^ WARN any use of this value will cause an error
+//~| WARN this was previously accepted by the compiler but is being phased out
+
+fn main() {
+    black_box([FOO, FOO, FOO, FOO, FOO, FOO, FOO, FOO]);
+    //~^ ERROR erroneous constant used
+}
@@ -0,0 +1,28 @@
+#![feature(core_intrinsics)]
+#![feature(const_int_unchecked_arith)]
+
+use std::intrinsics;
+
+// `exact_div` is UB if the division has a remainder, if the divisor is zero,
+// or if it overflows.
+
+const EXACT: i32 = unsafe { intrinsics::exact_div(6_i32, 2) };
+const EXACT_NEG: i64 = unsafe { intrinsics::exact_div(-12_i64, 4) };
+
+const NOT_MULTIPLE: i32 = unsafe { intrinsics::exact_div(7_i32, 2) };
+//~^ ERROR any use of this value will cause an error
+//~| WARN this was previously accepted by the compiler but is being phased out
+const NOT_MULTIPLE_U8: u8 = unsafe { intrinsics::exact_div(255_u8, 2) };
+//~^ ERROR any use of this value will cause an error
+//~| WARN this was previously accepted by the compiler but is being phased out
+const BY_ZERO: u32 = unsafe { intrinsics::exact_div(0_u32, 0) };
+//~^ ERROR any use of this value will cause an error
+//~| WARN this was previously accepted by the compiler but is being phased out
+const OVERFLOW: i8 = unsafe { intrinsics::exact_div(i8::MIN, -1) };
+//~^ ERROR any use of this value will cause an error
+//~| WARN this was previously accepted by the compiler but is being phased out
+
+fn main() {
+    assert_eq!(EXACT, 3);
+    assert_eq!(EXACT_NEG, -3);
+}
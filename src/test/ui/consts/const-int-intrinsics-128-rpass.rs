@@ -0,0 +1,34 @@
+// run-pass
+// Check that the integer intrinsics used by core evaluate in constants with
+// 128-bit operands.
+
+#![feature(core_intrinsics)]
+#![feature(const_int_unchecked_arith)]
+
+use std::intrinsics;
+
+const BSWAP: u128 = intrinsics::bswap(0x0102030405060708090a0b0c0d0e0f10u128);
+const BSWAP_SIGNED: i128 = intrinsics::bswap(-2i128);
+const BITREVERSE: u128 = intrinsics::bitreverse(0x0fu128);
+const BITREVERSE_SIGNED: i128 = intrinsics::bitreverse(1i128);
+const CTPOP: u128 = intrinsics::ctpop(u128::MAX);
+const CTLZ: i128 = intrinsics::ctlz(1i128);
+const CTTZ: u128 = intrinsics::cttz(1u128 << 100);
+const ROTATE: u128 = intrinsics::rotate_left(1u128 << 127, 1);
+const EXACT_DIV: i128 = unsafe { intrinsics::exact_div(-(1i128 << 100), 1 << 50) };
+const UNCHECKED_SHL: u128 = unsafe { intrinsics::unchecked_shl(1u128, 127) };
+const SATURATING_ADD: i128 = intrinsics::saturating_add(i128::MAX, 1);
+
+fn main() {
+    assert_eq!(BSWAP, 0x100f0e0d0c0b0a090807060504030201);
+    assert_eq!(BSWAP_SIGNED, 0xfeffffffffffffffffffffffffffffffu128 as i128);
+    assert_eq!(BITREVERSE, 0xf0000000000000000000000000000000);
+    assert_eq!(BITREVERSE_SIGNED, i128::MIN);
+    assert_eq!(CTPOP, 128);
+    assert_eq!(CTLZ, 127);
+    assert_eq!(CTTZ, 100);
+    assert_eq!(ROTATE, 1);
+    assert_eq!(EXACT_DIV, -(1 << 50));
+    assert_eq!(UNCHECKED_SHL, 1 << 127);
+    assert_eq!(SATURATING_ADD, i128::MAX);
+}
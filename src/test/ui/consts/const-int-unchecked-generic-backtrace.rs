@@ -0,0 +1,30 @@
+// build-fail
+
+#![feature(core_intrinsics)]
+#![feature(const_int_unchecked_arith)]
+
+use std::intrinsics;
+
+// The erroneous constant is only reached through a chain of generic functions, all of which are
+// shown, down to the call in `main` choosing the shift amount.
+
+struct Shl<const N: u32>;
+
+impl<const N: u32> Shl<N> {
+    const VALUE: u32 = unsafe { intrinsics::unchecked_shl(1_u32, N) };
+    //~^ ERROR any use of this value will cause an error
+    //~| WARN this was previously accepted by the compiler but is being phased out
+}
+
+fn shl<const N: u32>() -> u32 {
+    Shl::<N>::VALUE //~ ERROR erroneous constant encountered
+}
+
+fn shl_twice<const N: u32>() -> u32 {
+    shl::<N>() + shl::<N>()
+}
+
+fn main() {
+    shl_twice::<8>();
+    shl_twice::<200>();
+}
@@ -0,0 +1,26 @@
+// build-fail
+
+#![feature(core_intrinsics)]
+#![feature(const_int_unchecked_arith)]
+
+use std::intrinsics;
+
+// `unchecked_shl` is only misused for some values of `N`, so the error is only found when
+// `shl` is instantiated with them.
+
+struct Shl<const N: u32>;
+
+impl<const N: u32> Shl<N> {
+    const VALUE: u32 = unsafe { intrinsics::unchecked_shl(1_u32, N) };
+    //~^ ERROR any use of this value will cause an error
+    //~| WARN this was previously accepted by the compiler but is being phased out
+}
+
+fn shl<const N: u32>() -> u32 {
+    Shl::<N>::VALUE //~ ERROR erroneous constant encountered
+}
+
+fn main() {
+    shl::<8>();
+    shl::<200>();
+}
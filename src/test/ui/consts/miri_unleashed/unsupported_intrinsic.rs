@@ -0,0 +1,13 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(core_intrinsics)]
+#![allow(const_err)]
+
+// A test demonstrating that calling an intrinsic the interpreter does not
+// support is reported with the name of the intrinsic.
+
+static C: f32 = unsafe { std::intrinsics::sqrtf32(4.0) };
+//~^ ERROR could not evaluate static initializer
+//~| NOTE calling intrinsic `sqrtf32` is not supported in constants
+
+fn main() {}
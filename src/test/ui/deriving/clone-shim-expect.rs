@@ -0,0 +1,69 @@
+// Checks that `#[rustc_clone_shim_expect]` reports the monomorphized `Clone::clone`
+// of types which are not lowered the way the attribute expects.
+
+// build-fail
+// compile-flags: --crate-type lib
+
+#![feature(rustc_attrs)]
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+pub enum Copyable {
+    A(u8),
+    B(u16),
+}
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+pub enum Nested {
+    A(Copyable),
+    B(Option<Generic<u8>>),
+    C,
+}
+
+#[derive(Clone, Copy)]
+#[rustc_clone_shim_expect(memcpy)]
+//~^ ERROR `Clone::clone` for `Generic<String>` is not a memcpy
+pub enum Generic<T> {
+    A(T),
+    B(u32),
+}
+
+#[derive(Clone)]
+#[rustc_clone_shim_expect(memcpy, no_branches)]
+//~^ ERROR `Clone::clone` for `NotCopy` is not a memcpy
+//~| ERROR `Clone::clone` for `NotCopy` branches
+pub enum NotCopy {
+    A(u8),
+    B(String),
+}
+
+#[derive(Clone)]
+#[rustc_clone_shim_expect(no_branches, no_calls, fast)]
+//~^ ERROR `Clone::clone` for `Wrapper` calls other functions
+//~| ERROR unrecognized clone shim expectation `fast`
+pub struct Wrapper(String);
+
+pub fn clone_copyable(x: &Copyable) -> Copyable {
+    x.clone()
+}
+
+pub fn clone_nested(x: &Nested) -> Nested {
+    x.clone()
+}
+
+pub fn clone_generic_copy(x: &Generic<u64>) -> Generic<u64> {
+    x.clone()
+}
+
+pub fn clone_generic_string(x: &Generic<String>) -> Generic<String> {
+    x.clone()
+}
+
+pub fn clone_not_copy(x: &NotCopy) -> NotCopy {
+    x.clone()
+}
+
+pub fn clone_wrapper(x: &Wrapper) -> Wrapper {
+    x.clone()
+}
@@ -0,0 +1,23 @@
+#![feature(partial_ord_by_discriminant)]
+
+#[derive(PartialEq, PartialOrd)]
+#[partial_ord(by_discriminant)]
+//~^ ERROR `#[partial_ord(by_discriminant)]` can only be used on enums
+struct Point(u32, u32);
+
+#[derive(PartialEq, PartialOrd)]
+#[partial_ord(by_variant)] //~ ERROR expected `#[partial_ord(by_discriminant)]`
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+// The attribute is a helper of `derive(PartialOrd)`, so it can't be used without it.
+#[derive(PartialEq)]
+#[partial_ord(by_discriminant)] //~ ERROR cannot find attribute `partial_ord` in this scope
+enum Level {
+    Low,
+    High,
+}
+
+fn main() {}
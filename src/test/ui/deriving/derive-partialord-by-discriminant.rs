@@ -0,0 +1,36 @@
+// run-pass
+// Check that `#[partial_ord(by_discriminant)]` only compares the discriminants.
+
+#![feature(partial_ord_by_discriminant)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, PartialOrd)]
+#[partial_ord(by_discriminant)]
+enum Task {
+    Low(String),
+    Normal { name: String, retries: u32 },
+    High(f64),
+    Idle,
+}
+
+#[derive(PartialEq, PartialOrd)]
+#[partial_ord(by_discriminant)]
+enum Level {
+    Debug = 2,
+    Info = 1,
+}
+
+fn main() {
+    let low = |name: &str| Task::Low(name.to_string());
+    let normal = Task::Normal { name: "b".to_string(), retries: 3 };
+
+    assert_eq!(low("b").partial_cmp(&low("a")), Some(Ordering::Equal));
+    assert!(low("b") != low("a"));
+    assert!(low("z") < normal);
+    assert!(Task::High(f64::NAN) > normal);
+    assert_eq!(Task::High(f64::NAN).partial_cmp(&Task::High(0.0)), Some(Ordering::Equal));
+    assert!(Task::Idle > Task::High(1.0));
+
+    assert!(Level::Debug > Level::Info);
+}
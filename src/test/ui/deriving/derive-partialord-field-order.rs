@@ -0,0 +1,25 @@
+#![deny(partial_ord_field_order)]
+
+#[derive(PartialEq, PartialOrd)]
+struct Release {
+    version: String, //~ ERROR the derived ordering compares the string field `version` before `major`
+    major: u32,
+    minor: u32,
+}
+
+#[derive(PartialEq, PartialOrd)]
+struct Label<'a>(u8, &'a str, u16); //~ ERROR the derived ordering compares the string field `1` before `2`
+
+// The string field is compared last, so it only breaks ties.
+#[derive(PartialEq, PartialOrd)]
+struct Named {
+    rank: u32,
+    name: String,
+}
+
+#[derive(PartialEq, PartialOrd)]
+struct Name {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,77 @@
+// run-pass
+// Check that with `#![feature(derive_relaxed_bounds)]`, derived impls are bounded on the types
+// of the fields they use rather than on the type parameters, so that type parameters which only
+// appear in skipped fields, or in field types implementing the trait regardless of them, are not
+// required to implement the derived trait.
+
+#![feature(derive_skip_fields, derive_relaxed_bounds)]
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+struct Unit;
+
+#[derive(PartialOrd, Ord)]
+struct Tagged<T, Tag> {
+    value: T,
+    #[partial_ord(skip)]
+    #[ord(skip)]
+    _tag: PhantomData<Tag>,
+}
+
+impl<T: PartialEq, Tag> PartialEq for Tagged<T, Tag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, Tag> Eq for Tagged<T, Tag> {}
+
+#[derive(PartialOrd)]
+enum Reading<U: ?Sized> {
+    Celsius(f64, #[partial_ord(skip)] Box<U>),
+    Missing,
+}
+
+impl<U: ?Sized> PartialEq for Reading<U> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Reading::Celsius(a, _), Reading::Celsius(b, _)) => a == b,
+            (Reading::Missing, Reading::Missing) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+struct Handle<T> {
+    id: u32,
+    _marker: PhantomData<T>,
+}
+
+#[derive(PartialEq, PartialOrd)]
+struct Labeled<T: ?Sized> {
+    rank: u8,
+    label: T,
+}
+
+fn tagged(value: i32) -> Tagged<i32, Unit> {
+    Tagged { value, _tag: PhantomData }
+}
+
+fn main() {
+    assert!(tagged(1) < tagged(2));
+    assert_eq!(tagged(3).cmp(&tagged(3)), Ordering::Equal);
+
+    let celsius = |value: f64| Reading::Celsius(value, Box::new("sensor") as Box<dyn Debug>);
+    assert_eq!(celsius(1.0).partial_cmp(&celsius(2.0)), Some(Ordering::Less));
+    assert!(celsius(5.0) < Reading::Missing);
+
+    let handle = |id| Handle::<Unit> { id, _marker: PhantomData };
+    assert!(handle(1) < handle(2));
+
+    let short: &Labeled<[u8]> = &Labeled { rank: 1, label: [1, 2] };
+    let long: &Labeled<[u8]> = &Labeled { rank: 1, label: [1, 3, 0] };
+    assert!(short < long);
+}
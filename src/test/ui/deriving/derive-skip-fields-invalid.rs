@@ -0,0 +1,18 @@
+#![feature(derive_skip_fields)]
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u32,
+    #[partial_ord(ignore)] //~ ERROR expected `#[partial_ord(skip)]`
+    #[ord(skip, always)] //~ ERROR expected `#[ord(skip)]`
+    build: String,
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum Reading {
+    Timed(#[partial_ord(first)] u64, f64), //~ ERROR expected `#[partial_ord(skip)]`
+    Named { #[partial_ord(skip = "yes")] name: &'static str, value: f64 },
+    //~^ ERROR expected `#[partial_ord(skip)]`
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+// run-pass
+// Check that fields marked `#[partial_ord(skip)]` and `#[ord(skip)]` are left out of the
+// derived comparisons.
+
+#![feature(derive_skip_fields)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u32,
+    minor: u32,
+    #[partial_ord(skip)]
+    #[ord(skip)]
+    build: String,
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum Sample {
+    Timed(#[partial_ord(skip)] u64, f64),
+    Named { #[partial_ord(skip)] name: &'static str, value: f64 },
+}
+
+fn version(major: u32, minor: u32, build: &str) -> Version {
+    Version { major, minor, build: build.to_string() }
+}
+
+fn main() {
+    assert!(version(1, 2, "b") < version(1, 3, "a"));
+    assert!(version(2, 0, "a") > version(1, 9, "b"));
+    assert_eq!(version(1, 2, "b").cmp(&version(1, 2, "a")), Ordering::Equal);
+    assert_eq!(version(1, 2, "b").partial_cmp(&version(1, 2, "a")), Some(Ordering::Equal));
+
+    assert_eq!(Sample::Timed(9, 1.0).partial_cmp(&Sample::Timed(1, 2.0)), Some(Ordering::Less));
+    assert_eq!(Sample::Timed(9, 1.0).partial_cmp(&Sample::Timed(1, 1.0)), Some(Ordering::Equal));
+    let named = |name, value| Sample::Named { name, value };
+    assert_eq!(named("a", 3.0).partial_cmp(&named("b", 2.0)), Some(Ordering::Greater));
+    assert!(Sample::Timed(0, 5.0) < Sample::Named { name: "a", value: 0.0 });
+}
@@ -0,0 +1,94 @@
+// run-pass
+// Check that the comparisons derived with `-Z derive-cmp-strategy=chain` behave like the
+// default nested matches, including short-circuiting and incomparable fields.
+
+// compile-flags: -Z derive-cmp-strategy=chain
+
+#![feature(derive_skip_fields)]
+
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+
+pub struct FailCmp;
+impl PartialEq for FailCmp {
+    fn eq(&self, _: &FailCmp) -> bool { panic!("eq") }
+}
+
+impl PartialOrd for FailCmp {
+    fn partial_cmp(&self, _: &FailCmp) -> Option<Ordering> { panic!("partial_cmp") }
+}
+
+impl Eq for FailCmp {}
+
+impl Ord for FailCmp {
+    fn cmp(&self, _: &FailCmp) -> Ordering { panic!("cmp") }
+}
+
+#[derive(PartialEq, PartialOrd, Eq, Ord)]
+struct ShortCircuit {
+    x: isize,
+    y: FailCmp,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Triple(u8, u8, u8);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Unit;
+
+#[derive(PartialEq, PartialOrd)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum Shape {
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Tagged {
+    #[partial_ord(skip)]
+    #[ord(skip)]
+    tag: u32,
+    value: i32,
+    extra: i32,
+}
+
+fn main() {
+    let a = ShortCircuit { x: 1, y: FailCmp };
+    let b = ShortCircuit { x: 2, y: FailCmp };
+    assert!(a < b);
+    assert_eq!(a.cmp(&b), Less);
+
+    for &(l, r) in &[(0, 0), (0, 1), (1, 0)] {
+        for &(m, n) in &[(0, 0), (0, 1), (1, 0)] {
+            let lhs = Triple(l, m, 5);
+            let rhs = Triple(r, n, 5);
+            let expected = l.cmp(&r).then(m.cmp(&n));
+            assert_eq!(lhs.cmp(&rhs), expected);
+            assert_eq!(lhs.partial_cmp(&rhs), Some(expected));
+        }
+    }
+    assert_eq!(Triple(1, 2, 3).cmp(&Triple(1, 2, 4)), Less);
+    assert_eq!(Triple(1, 2, 4).cmp(&Triple(1, 2, 3)), Greater);
+
+    assert_eq!(Unit.cmp(&Unit), Equal);
+    assert_eq!(Unit.partial_cmp(&Unit), Some(Equal));
+
+    let nan = f64::NAN;
+    assert_eq!(Point { x: 0.0, y: 0.0 }.partial_cmp(&Point { x: 0.0, y: 1.0 }), Some(Less));
+    assert_eq!(Point { x: nan, y: 0.0 }.partial_cmp(&Point { x: 0.0, y: 0.0 }), None);
+    assert_eq!(Point { x: 0.0, y: nan }.partial_cmp(&Point { x: 0.0, y: 0.0 }), None);
+    assert_eq!(Point { x: 1.0, y: nan }.partial_cmp(&Point { x: 0.0, y: 0.0 }), Some(Greater));
+
+    assert!(Shape::Circle(2.0) < Shape::Rect { w: 0.0, h: 0.0 });
+    assert!(Shape::Rect { w: 1.0, h: 3.0 } < Shape::Rect { w: 1.0, h: 4.0 });
+    assert_eq!(Shape::Rect { w: nan, h: 0.0 }.partial_cmp(&Shape::Rect { w: 0.0, h: 0.0 }), None);
+
+    let t1 = Tagged { tag: 9, value: 1, extra: 2 };
+    let t2 = Tagged { tag: 0, value: 1, extra: 3 };
+    assert_eq!(t1.cmp(&t2), Less);
+    assert_eq!(t1.partial_cmp(&t2), Some(Less));
+}
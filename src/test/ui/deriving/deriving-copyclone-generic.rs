@@ -0,0 +1,39 @@
+// run-pass
+//! Test that #[derive(Copy, Clone)] on a generic type produces a shallow copy
+//! for instantiations that are Copy, even when a member violates RFC 1521
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A struct that pretends to be Copy, but actually does something
+/// in its Clone impl
+#[derive(Copy)]
+struct Liar;
+
+/// Static cooperating with the rogue Clone impl
+static CLONED: AtomicBool = AtomicBool::new(false);
+
+impl Clone for Liar {
+    fn clone(&self) -> Self {
+        // this makes Clone vs Copy observable
+        CLONED.store(true, Ordering::SeqCst);
+
+        *self
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Generic<T> {
+    A(T),
+    B(u32),
+}
+
+fn main() {
+    let _ = Generic::A(Liar).clone();
+    // if Generic<Liar> was byte-for-byte copied, CLONED will still be false
+    assert!(!CLONED.load(Ordering::SeqCst));
+
+    // instantiations which aren't Copy still use the derived field-by-field clone
+    let s = Generic::A(String::from("hello")).clone();
+    assert!(matches!(s, Generic::A(ref s) if s == "hello"));
+    assert!(matches!(Generic::<String>::B(3).clone(), Generic::B(3)));
+}
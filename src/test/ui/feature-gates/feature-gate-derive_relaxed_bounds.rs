@@ -0,0 +1,31 @@
+// Without `#![feature(derive_relaxed_bounds)]`, derives require all type parameters to
+// implement the trait, even those only appearing in skipped fields.
+
+#![feature(derive_skip_fields)]
+
+use std::marker::PhantomData;
+
+struct Unit;
+
+#[derive(PartialOrd, Ord)]
+struct Tagged<T, Tag> {
+    value: T,
+    #[partial_ord(skip)]
+    #[ord(skip)]
+    _tag: PhantomData<Tag>,
+}
+
+impl<T: PartialEq, Tag> PartialEq for Tagged<T, Tag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, Tag> Eq for Tagged<T, Tag> {}
+
+fn is_ord<T: Ord>() {}
+
+fn main() {
+    is_ord::<Tagged<i32, Unit>>();
+    //~^ ERROR the trait bound `Unit: Ord` is not satisfied
+}
@@ -0,0 +1,9 @@
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u32,
+    #[partial_ord(skip)] //~ ERROR `#[partial_ord(skip)]` is experimental
+    #[ord(skip)] //~ ERROR `#[ord(skip)]` is experimental
+    build: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(PartialEq, PartialOrd)]
+#[partial_ord(by_discriminant)] //~ ERROR `#[partial_ord(by_discriminant)]` is experimental
+enum Priority {
+    Low(u32),
+    High(u32),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+// The note about moving out of a value that may be pinned needs `#![feature(unsafe_pinned)]`.
+
+use std::marker::PhantomPinned;
+
+struct Pinned {
+    _data: String,
+    _pin: PhantomPinned,
+}
+
+fn move_not_unpin(r: &mut Pinned) -> Pinned {
+    *r
+    //~^ ERROR cannot move out of `*r` which is behind a mutable reference
+}
+
+fn main() {}
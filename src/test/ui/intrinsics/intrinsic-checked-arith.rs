@@ -0,0 +1,43 @@
+// run-pass
+// compile-flags: -C overflow-checks=off
+
+#![feature(core_intrinsics, const_checked_arith)]
+
+use std::intrinsics::{checked_add, checked_mul, checked_shl, checked_shr, checked_sub};
+
+const ADD: (Option<u8>, Option<u8>) = (checked_add(200u8, 55), checked_add(200u8, 56));
+const SUB: (Option<i8>, Option<i8>) = (checked_sub(-100i8, 28), checked_sub(-100i8, 29));
+const MUL: (Option<u32>, Option<u32>) =
+    (checked_mul(1u32 << 16, 0xffff), checked_mul(1 << 16, 1 << 16));
+const SHL: (Option<u8>, Option<u8>) = (checked_shl(1u8, 7), checked_shl(1u8, 8));
+const SHR: (Option<i16>, Option<i16>) = (checked_shr(-0x100i16, 4), checked_shr(-0x100i16, 256));
+
+#[inline(never)]
+fn id<T>(x: T) -> T {
+    x
+}
+
+fn main() {
+    assert_eq!(ADD, (Some(255), None));
+    assert_eq!(SUB, (Some(-128), None));
+    assert_eq!(MUL, (Some(0xffff_0000), None));
+    assert_eq!(SHL, (Some(0x80), None));
+    assert_eq!(SHR, (Some(-0x10), None));
+
+    assert_eq!(checked_add(id(200u8), id(55)), Some(255));
+    assert_eq!(checked_add(id(200u8), id(56)), None);
+    assert_eq!(checked_sub(id(-100i8), id(28)), Some(-128));
+    assert_eq!(checked_sub(id(-100i8), id(29)), None);
+    assert_eq!(checked_mul(id(1u32 << 16), id(0xffff)), Some(0xffff_0000));
+    assert_eq!(checked_mul(id(1u32 << 16), id(1 << 16)), None);
+    assert_eq!(checked_mul(id(i128::MIN), id(-1)), None);
+
+    // The shift amount is checked before it is truncated to the width of the value.
+    assert_eq!(checked_shl(id(1u8), id(7)), Some(0x80));
+    assert_eq!(checked_shl(id(1u8), id(8)), None);
+    assert_eq!(checked_shl(id(1u8), id(256)), None);
+    assert_eq!(checked_shr(id(-0x100i16), id(4)), Some(-0x10));
+    assert_eq!(checked_shr(id(-0x100i16), id(256)), None);
+    assert_eq!(checked_shr(id(u64::MAX), id(63)), Some(1));
+    assert_eq!(checked_shr(id(u64::MAX), id(64)), None);
+}
@@ -6,7 +6,7 @@
 fn main() {
     let a = &FOO;
     //~^ ERROR thread-local variable borrowed past end of function
-    //~| NOTE thread-local variables cannot be borrowed beyond the end of the function
+    //~| NOTE thread-local variables cannot be borrowed past the end of the function
 
     std::thread::spawn(move || {
         println!("{}", a);
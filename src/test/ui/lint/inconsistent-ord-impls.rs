@@ -0,0 +1,56 @@
+// check-pass
+// Types deriving one of `PartialOrd` and `Ord` and implementing the other one
+// by hand are linted, unless the hand-written method defers to the derived one
+// with `self` as the receiver and `other` as the argument.
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Eq, PartialOrd)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl Ord for Version { //~ WARN `Ord` is implemented by hand, but `PartialOrd` is derived
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.minor.cmp(&other.minor)
+    }
+}
+
+#[derive(PartialEq, Eq, Ord)]
+struct Reversed(u32);
+
+impl PartialOrd for Reversed { //~ WARN `PartialOrd` is implemented by hand, but `Ord` is derived
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+#[derive(PartialEq, Eq, Ord)]
+struct Consistent(u32);
+
+impl PartialOrd for Consistent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(PartialEq, Eq, Ord)]
+struct ReversedCmp(u32);
+
+impl PartialOrd for ReversedCmp { //~ WARN `PartialOrd` is implemented by hand, but `Ord` is derived
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(other.0.cmp(&self.0))
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd)]
+struct Unwrapped(u32);
+
+impl Ord for Unwrapped {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+#![feature(core_intrinsics, unchecked_math)]
+
+use std::intrinsics;
+
+fn main() {
+    unsafe {
+        intrinsics::unchecked_shl(1_u32, 32);
+        //~^ ERROR this call to `unchecked_shl` has undefined behavior
+        intrinsics::unchecked_add(u8::MAX, 1);
+        //~^ ERROR this call to `unchecked_add` has undefined behavior
+        intrinsics::unchecked_rem(1_i32, 0);
+        //~^ ERROR this call to `unchecked_rem` has undefined behavior
+        intrinsics::exact_div(7_i32, 2);
+        //~^ ERROR this call to `exact_div` has undefined behavior
+
+        intrinsics::unchecked_shl(1_u32, 31);
+        intrinsics::unchecked_sub(2_u8, 1);
+        intrinsics::exact_div(8_i32, 2);
+        intrinsics::unchecked_mul(u32::MAX, 2);
+        //~^ ERROR this call to `unchecked_mul` has undefined behavior
+    }
+    propagated(0);
+    methods();
+}
+
+fn propagated(x: u32) {
+    let shift = 40;
+    let sum = u16::MAX;
+    unsafe {
+        intrinsics::unchecked_shl(1_u32, shift);
+        //~^ ERROR this call to `unchecked_shl` has undefined behavior
+        intrinsics::unchecked_add(sum, 1);
+        //~^ ERROR this call to `unchecked_add` has undefined behavior
+
+        intrinsics::unchecked_add(x, 1);
+    }
+}
+
+fn methods() {
+    unsafe {
+        let _ = 255_u8.unchecked_add(1);
+        //~^ ERROR this call to `unchecked_add` has undefined behavior
+        let _ = 1_u32.unchecked_shl(32);
+        //~^ ERROR this call to `unchecked_shl` has undefined behavior
+
+        let _ = 1_i32.unchecked_sub(1);
+    }
+}
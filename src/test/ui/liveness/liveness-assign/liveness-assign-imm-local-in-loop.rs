@@ -5,6 +5,7 @@ fn test() {
     loop {
         v = 1; //~ ERROR cannot assign twice to immutable variable `v`
                //~| NOTE cannot assign twice to immutable variable
+               //~| NOTE `v` is only assigned here, but the assignment runs again
     }
 }
 
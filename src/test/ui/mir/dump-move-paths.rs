@@ -0,0 +1,13 @@
+// Checks the move paths exposed by the `mir_move_paths` query.
+
+#![feature(rustc_attrs)]
+
+struct S;
+struct Pair(S, S);
+
+#[rustc_dump_move_paths]
+fn first(pair: Pair) -> S { //~ ERROR move paths
+    pair.0
+}
+
+fn main() {}
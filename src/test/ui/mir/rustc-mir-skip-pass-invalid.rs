@@ -0,0 +1,22 @@
+// Checks that malformed `#[rustc_mir(skip_pass = "...")]` attributes and names that aren't the
+// name of a MIR pass are reported, once each.
+
+#![feature(rustc_attrs)]
+
+#[rustc_mir(skip_pass)]
+//~^ ERROR `skip_pass` requires the name of a pass
+fn unnamed() {}
+
+#[rustc_mir(skip_pass = "ConstPropagation")]
+//~^ ERROR unknown MIR pass `ConstPropagation`
+fn misspelled() {}
+
+#[rustc_mir(skip_pass = "SimplifyCfg-initial", skip_pass = "SimplifyCfg")]
+//~^ ERROR unknown MIR pass `SimplifyCfg`
+fn unlabeled() {}
+
+fn main() {
+    unnamed();
+    misspelled();
+    unlabeled();
+}
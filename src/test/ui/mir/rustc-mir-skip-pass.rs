@@ -0,0 +1,20 @@
+// build-fail
+// Checks that `#[rustc_mir(skip_pass = "...")]` keeps a MIR pass from running on a single
+// function: `ConstProp` only reports the overflow in the function it runs on.
+
+#![feature(rustc_attrs)]
+
+fn checked() -> u8 {
+    u8::MAX + 1
+    //~^ ERROR this arithmetic operation will overflow
+}
+
+#[rustc_mir(skip_pass = "ConstProp")]
+fn skipped() -> u8 {
+    u8::MAX + 1
+}
+
+fn main() {
+    checked();
+    skipped();
+}
@@ -0,0 +1,74 @@
+// build-pass
+// compile-flags: -Z validate-mir -Z mir-opt-level=3
+
+// Exercises the kinds of projections checked by MIR validation after each pass:
+// derefs of references and boxes, enum downcasts, tuple, struct and upvar
+// fields, indexing and slice patterns.
+
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+use std::pin::Pin;
+
+struct Pair<T> {
+    first: T,
+    second: (T, Box<T>),
+}
+
+enum Shape {
+    Circle { radius: u32 },
+    Rect(u32, u32),
+    Empty,
+}
+
+fn area(shape: &Shape) -> u32 {
+    match *shape {
+        Shape::Circle { radius } => 3 * radius * radius,
+        Shape::Rect(w, h) => w * h,
+        Shape::Empty => 0,
+    }
+}
+
+fn ends(xs: &[u32]) -> Option<(u32, &[u32], u32)> {
+    match xs {
+        [first, middle @ .., last] => Some((*first, middle, *last)),
+        _ => None,
+    }
+}
+
+fn pair_sum(pair: Pair<u32>) -> u32 {
+    let Pair { first, second: (a, b) } = pair;
+    first + a + *b
+}
+
+fn main() {
+    let shapes = [Shape::Circle { radius: 1 }, Shape::Rect(2, 3), Shape::Empty];
+    let mut total = 0;
+    for i in 0..shapes.len() {
+        total += area(&shapes[i]);
+    }
+    assert_eq!(total, 9);
+
+    let xs = [1, 2, 3, 4];
+    assert_eq!(ends(&xs), Some((1, &xs[1..3], 4)));
+    let [a, .., d] = xs;
+    assert_eq!(a + d, 5);
+
+    let pair = Pair { first: 1, second: (2, Box::new(3)) };
+    assert_eq!(pair_sum(pair), 6);
+
+    let boxed = Box::new((String::from("a"), 1));
+    let (s, n) = *boxed;
+    let closure = move || s.len() + n;
+    assert_eq!(closure(), 2);
+
+    let mut gen = Box::pin(|| {
+        let shape = Shape::Rect(4, 5);
+        yield area(&shape);
+        if let Shape::Rect(w, _) = shape {
+            yield w;
+        }
+    });
+    let _ = Pin::as_mut(&mut gen).resume(());
+    let _ = Pin::as_mut(&mut gen).resume(());
+}
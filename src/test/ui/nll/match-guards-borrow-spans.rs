@@ -0,0 +1,28 @@
+// Check that borrows of bindings for the evaluation of a match guard point at
+// the guard the binding is borrowed for.
+
+fn mutate_in_guard(mut t: bool) {
+    match t {
+        s if { t = !t; s } => {} //~ ERROR cannot assign to `t` because it is borrowed
+        _ => {}
+    }
+}
+
+// Only the alternative binding `n` to `t.1` conflicts with the assignment.
+fn mutate_in_guard_of_or_pattern(mut t: (Result<u32, ()>, u32)) {
+    match t {
+        (Ok(n), _) | (Err(()), n) if { t.1 = 0; n > 0 } => {}
+        //~^ ERROR cannot assign to `t.1` because it is borrowed
+        _ => {}
+    }
+}
+
+// The fake borrows of the scrutinee are not attributed to the guard.
+fn mutate_scrutinee_in_guard(mut q: bool) {
+    match q {
+        true if { q = false; true } => {} //~ ERROR cannot assign `q` in match guard
+        _ => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// compile-flags: -Z print-generator-sizes
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+
+// This file illustrates how the locals held across each suspension point
+// of a generator contribute to its size.
+
+#![feature(generators, generator_trait, start)]
+
+use std::ops::Generator;
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let mut gen = Box::pin(move || {
+        let small = [1u8; 8];
+        yield;
+        let large = [2u8; 1024];
+        yield;
+        drop(small);
+        drop(large);
+    });
+    let _ = gen.as_mut().resume(());
+    let _ = gen.as_mut().resume(());
+    0
+}
@@ -0,0 +1,14 @@
+#![crate_type = "lib"]
+#![feature(doc_since)]
+
+#[doc(since = "1.2.0")] // ok!
+pub struct Foo;
+
+#[doc(since)] //~ ERROR
+pub struct Bar;
+
+#[doc(since = "soon")] //~ ERROR
+pub struct Baz;
+
+#[doc(since = "1..0")] //~ ERROR
+pub struct Qux;
@@ -7,5 +7,5 @@ fn main() {
     let mut counter: Vec<i32> = Vec::new();
     let tick = move || mem::drop(counter);
     tick();
-    tick(); //~ ERROR use of moved value: `tick`
+    tick(); //~ ERROR closure `tick` cannot be called more than once
 }
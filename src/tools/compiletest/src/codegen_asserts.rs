@@ -0,0 +1,185 @@
+//! Structured assertions on the LLVM IR emitted for codegen tests.
+//!
+//! Instead of hand-writing FileCheck patterns, a codegen test can state a
+//! property that a function's optimized IR must have:
+//!
+//! ```text
+//! // codegen-assert: clone_foo memcpy
+//! // codegen-assert: is_zero no-branches
+//! ```
+//!
+//! The function is looked up by its symbol name, so it should be marked
+//! `#[no_mangle]`. The IR is analyzed after compilation, independently of
+//! any `CHECK` lines the test may also contain.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// A property that the IR of a single function is expected to have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodegenProperty {
+    /// The body is a single basic block without any control flow.
+    NoBranches,
+    /// The body does not call any function other than LLVM intrinsics.
+    NoCalls,
+    /// The body only moves data around: loads, stores, address computations
+    /// and `llvm.memcpy`, without any branches or other calls.
+    Memcpy,
+}
+
+impl FromStr for CodegenProperty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "no-branches" => Ok(CodegenProperty::NoBranches),
+            "no-calls" => Ok(CodegenProperty::NoCalls),
+            "memcpy" => Ok(CodegenProperty::Memcpy),
+            _ => Err(format!(
+                "unknown codegen property `{}`, expected one of `no-branches`, `no-calls`, `memcpy`",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CodegenProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CodegenProperty::NoBranches => "no-branches",
+            CodegenProperty::NoCalls => "no-calls",
+            CodegenProperty::Memcpy => "memcpy",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A `codegen-assert` header: `<symbol> <property>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodegenAssert {
+    pub function: String,
+    pub property: CodegenProperty,
+}
+
+impl FromStr for CodegenAssert {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(function), Some(property), None) => {
+                Ok(CodegenAssert { function: function.to_owned(), property: property.parse()? })
+            }
+            _ => Err(format!(
+                "malformed `codegen-assert` header `{}`, expected `<fn> <property>`",
+                s
+            )),
+        }
+    }
+}
+
+/// Opcodes that only move data, as allowed by [`CodegenProperty::Memcpy`].
+const DATA_MOVEMENT_OPCODES: &[&str] =
+    &["load", "store", "getelementptr", "bitcast", "insertvalue", "extractvalue", "ret"];
+
+/// Intrinsics which do not count as calls for [`CodegenProperty::Memcpy`].
+const DATA_MOVEMENT_INTRINSICS: &[&str] =
+    &["@llvm.memcpy.", "@llvm.lifetime.", "@llvm.dbg.", "@llvm.assume("];
+
+/// Checks the assertion against the textual LLVM IR of a module, returning a
+/// human readable explanation of the failure, if any.
+pub fn check(ir: &str, assert: &CodegenAssert) -> Result<(), String> {
+    let body = function_body(ir, &assert.function).ok_or_else(|| {
+        format!("no definition of `@{}` found in the emitted IR", assert.function)
+    })?;
+
+    let fail = |line: &str, why: &str| {
+        Err(format!(
+            "`{}` is not `{}`: {}\n    {}",
+            assert.function,
+            assert.property,
+            why,
+            line.trim()
+        ))
+    };
+
+    let mut seen_instruction = false;
+    for line in body {
+        let line = strip_comment(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        if is_label(line) {
+            // The label of the entry block is optional, any other label
+            // starts a new basic block.
+            if seen_instruction && assert.property != CodegenProperty::NoCalls {
+                return fail(line, "found more than one basic block");
+            }
+            continue;
+        }
+        seen_instruction = true;
+        let opcode = opcode(line);
+        match assert.property {
+            CodegenProperty::NoBranches => {
+                if matches!(opcode, "br" | "switch" | "indirectbr" | "invoke" | "callbr") {
+                    return fail(line, "found a branch");
+                }
+            }
+            CodegenProperty::NoCalls => {
+                if matches!(opcode, "call" | "invoke" | "callbr") && !line.contains("@llvm.") {
+                    return fail(line, "found a call");
+                }
+            }
+            CodegenProperty::Memcpy => {
+                if opcode == "call" {
+                    if !DATA_MOVEMENT_INTRINSICS.iter().any(|intrinsic| line.contains(intrinsic)) {
+                        return fail(line, "found a call");
+                    }
+                } else if !DATA_MOVEMENT_OPCODES.contains(&opcode) {
+                    return fail(line, "found an instruction that does more than copy data");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the lines between the opening and closing braces of the
+/// definition of `@name`.
+fn function_body<'a>(ir: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let plain = format!("@{}(", name);
+    let quoted = format!("@\"{}\"(", name);
+    let mut lines = ir.lines();
+    lines.find(|line| {
+        line.starts_with("define ") && (line.contains(&plain) || line.contains(&quoted))
+    })?;
+    Some(lines.take_while(|line| !line.starts_with('}')).collect())
+}
+
+fn strip_comment(line: &str) -> &str {
+    // IR string constants cannot appear inside function bodies, so a `;`
+    // always starts a comment here.
+    line.find(';').map_or(line, |pos| &line[..pos])
+}
+
+fn is_label(line: &str) -> bool {
+    !line.starts_with(char::is_whitespace) && line.trim_end().ends_with(':')
+}
+
+/// Extracts the opcode of an instruction, skipping over an optional
+/// `%result =` assignment and call modifiers like `tail`.
+fn opcode(line: &str) -> &str {
+    let line = line.trim();
+    let instruction = match line.find(" = ") {
+        Some(pos) if line.starts_with('%') => &line[pos + 3..],
+        _ => line,
+    };
+    instruction
+        .split_whitespace()
+        .find(|word| !matches!(*word, "tail" | "musttail" | "notail"))
+        .unwrap_or("")
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+const IR: &str = r#"
+; ModuleID = 'issue_69174.3a1fbbbh-cgu.0'
+source_filename = "issue_69174.3a1fbbbh-cgu.0"
+
+; Function Attrs: nounwind nonlazybind uwtable
+define void @clone_foo(%Foo* noalias nocapture sret(%Foo) dereferenceable(8) %0, %Foo* noalias nocapture readonly align 4 dereferenceable(8) %f) unnamed_addr #0 {
+start:
+  %1 = bitcast %Foo* %0 to i8*
+  %2 = bitcast %Foo* %f to i8*
+  tail call void @llvm.memcpy.p0i8.p0i8.i64(i8* align 4 %1, i8* align 4 %2, i64 8, i1 false) #2
+  ret void
+}
+
+; Function Attrs: nounwind nonlazybind uwtable
+define void @clone_bar(%Bar* noalias nocapture sret(%Bar) dereferenceable(8) %0, %Bar* noalias nocapture readonly align 4 dereferenceable(8) %b) unnamed_addr #0 {
+start:
+  %1 = getelementptr inbounds %Bar, %Bar* %b, i64 0, i32 0
+  %2 = load i32, i32* %1, align 4, !range !2
+  %_2 = zext i32 %2 to i64
+  switch i64 %_2, label %bb2 [
+    i64 0, label %bb3
+  ]
+
+bb2:                                              ; preds = %start
+  tail call void @"_ZN4core9panicking5panic17h0123456789abcdefE"()
+  unreachable
+
+bb3:                                              ; preds = %start
+  ret void
+}
+"#;
+
+#[test]
+fn parse_header() {
+    assert_eq!(
+        "clone_foo memcpy".parse(),
+        Ok(CodegenAssert { function: "clone_foo".to_owned(), property: CodegenProperty::Memcpy })
+    );
+    assert_eq!(
+        " is_zero   no-branches ".parse::<CodegenAssert>().map(|a| a.property),
+        Ok(CodegenProperty::NoBranches)
+    );
+    assert!("clone_foo".parse::<CodegenAssert>().is_err());
+    assert!("clone_foo memcpy extra".parse::<CodegenAssert>().is_err());
+    assert!("clone_foo memmove".parse::<CodegenAssert>().is_err());
+}
+
+#[test]
+fn memcpy_clone() {
+    let assert = "clone_foo memcpy".parse().unwrap();
+    assert_eq!(check(IR, &assert), Ok(()));
+    let assert = "clone_foo no-branches".parse().unwrap();
+    assert_eq!(check(IR, &assert), Ok(()));
+    let assert = "clone_foo no-calls".parse().unwrap();
+    assert_eq!(check(IR, &assert), Ok(()));
+}
+
+#[test]
+fn branching_clone() {
+    let assert = "clone_bar memcpy".parse().unwrap();
+    assert!(check(IR, &assert).unwrap_err().contains("does more than copy data"));
+    let assert = "clone_bar no-branches".parse().unwrap();
+    assert!(check(IR, &assert).unwrap_err().contains("found a branch"));
+    let assert = "clone_bar no-calls".parse().unwrap();
+    assert!(check(IR, &assert).unwrap_err().contains("found a call"));
+}
+
+#[test]
+fn missing_function() {
+    let assert = "clone_baz memcpy".parse().unwrap();
+    assert!(check(IR, &assert).unwrap_err().contains("no definition of `@clone_baz`"));
+}
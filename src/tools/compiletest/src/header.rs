@@ -7,6 +7,7 @@
 
 use tracing::*;
 
+use crate::codegen_asserts::CodegenAssert;
 use crate::common::{CompareMode, Config, Debugger, FailMode, Mode, PanicStrategy, PassMode};
 use crate::util;
 use crate::{extract_cdb_version, extract_gdb_version};
@@ -349,6 +350,8 @@ pub struct TestProps {
     // If true, `rustfix` will only apply `MachineApplicable` suggestions.
     pub rustfix_only_machine_applicable: bool,
     pub assembly_output: Option<String>,
+    // Structured assertions on the emitted LLVM IR of codegen tests
+    pub codegen_asserts: Vec<CodegenAssert>,
     // If true, the test is expected to ICE
     pub should_ice: bool,
     // If true, the stderr is expected to be different across bit-widths.
@@ -391,6 +394,7 @@ pub fn new() -> Self {
             run_rustfix: false,
             rustfix_only_machine_applicable: false,
             assembly_output: None,
+            codegen_asserts: vec![],
             should_ice: false,
             stderr_per_bitwidth: false,
         }
@@ -561,6 +565,10 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                 if !self.stderr_per_bitwidth {
                     self.stderr_per_bitwidth = config.parse_stderr_per_bitwidth(ln);
                 }
+
+                if let Some(assert) = config.parse_codegen_assert(ln) {
+                    self.codegen_asserts.push(assert);
+                }
             });
         }
 
@@ -812,6 +820,15 @@ fn parse_assembly_output(&self, line: &str) -> Option<String> {
         self.parse_name_value_directive(line, "assembly-output").map(|r| r.trim().to_string())
     }
 
+    fn parse_codegen_assert(&self, line: &str) -> Option<CodegenAssert> {
+        self.parse_name_value_directive(line, "codegen-assert").map(|value| {
+            if self.mode != Mode::Codegen {
+                panic!("`codegen-assert` header is only supported in codegen tests");
+            }
+            value.parse().unwrap_or_else(|e| panic!("{}", e))
+        })
+    }
+
     fn parse_env(&self, line: &str, name: &str) -> Option<(String, String)> {
         self.parse_name_value_directive(line, name).map(|nv| {
             // nv is either FOO or FOO=BAR
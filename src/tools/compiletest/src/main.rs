@@ -27,6 +27,7 @@
 #[cfg(test)]
 mod tests;
 
+pub mod codegen_asserts;
 pub mod common;
 pub mod errors;
 pub mod header;